@@ -0,0 +1,145 @@
+use super::TwitterApi;
+use crate::authorization::Authorization;
+use crate::data::{WebhookConfig, WebhookSubscriptions};
+use crate::error::{Error, Result};
+use crate::id::UserId;
+use crate::query::UrlQueryExt;
+use reqwest::{Method, StatusCode};
+use url::Url;
+
+/// The Account Activity API is only available in v1.1 and its responses are not wrapped in
+/// a `data` object, so these calls return the plain responses.
+impl<A> TwitterApi<A>
+where
+    A: Authorization,
+{
+    fn account_activity_url(&self, env_name: &str, path: impl AsRef<str>) -> Result<Url> {
+        self.url(format!(
+            "../1.1/account_activity/all/{}/{}",
+            env_name,
+            path.as_ref()
+        ))
+    }
+    /// Register `url` as the webhook of `env_name`. Twitter validates it with a CRC check
+    /// before replying.
+    pub async fn post_account_activity_webhook(
+        &self,
+        env_name: &str,
+        url: impl ToString,
+    ) -> Result<WebhookConfig> {
+        let mut endpoint = self.account_activity_url(env_name, "webhooks.json")?;
+        endpoint.append_query_val("url", url);
+        Ok(self
+            .execute(self.request(Method::POST, endpoint).build()?)
+            .await?
+            .json()
+            .await?)
+    }
+    pub async fn get_account_activity_webhooks(
+        &self,
+        env_name: &str,
+    ) -> Result<Vec<WebhookConfig>> {
+        Ok(self
+            .execute(
+                self.request(
+                    Method::GET,
+                    self.account_activity_url(env_name, "webhooks.json")?,
+                )
+                .build()?,
+            )
+            .await?
+            .json()
+            .await?)
+    }
+    /// Trigger a CRC check of the webhook, e.g. to re-enable it after it failed one
+    pub async fn put_account_activity_webhook(
+        &self,
+        env_name: &str,
+        webhook_id: &str,
+    ) -> Result<()> {
+        self.execute(
+            self.request(
+                Method::PUT,
+                self.account_activity_url(env_name, format!("webhooks/{webhook_id}.json"))?,
+            )
+            .build()?,
+        )
+        .await?;
+        Ok(())
+    }
+    pub async fn delete_account_activity_webhook(
+        &self,
+        env_name: &str,
+        webhook_id: &str,
+    ) -> Result<()> {
+        self.execute(
+            self.request(
+                Method::DELETE,
+                self.account_activity_url(env_name, format!("webhooks/{webhook_id}.json"))?,
+            )
+            .build()?,
+        )
+        .await?;
+        Ok(())
+    }
+    /// Subscribe the webhook of `env_name` to the events of the authorizing user
+    pub async fn post_account_activity_subscription(&self, env_name: &str) -> Result<()> {
+        self.execute(
+            self.request(
+                Method::POST,
+                self.account_activity_url(env_name, "subscriptions.json")?,
+            )
+            .build()?,
+        )
+        .await?;
+        Ok(())
+    }
+    /// Whether the webhook of `env_name` is subscribed to the events of the authorizing user
+    pub async fn get_account_activity_subscription(&self, env_name: &str) -> Result<bool> {
+        let req = self
+            .request(
+                Method::GET,
+                self.account_activity_url(env_name, "subscriptions.json")?,
+            )
+            .build()?;
+        match self.execute(req).await {
+            Ok(_) => Ok(true),
+            Err(Error::Api(err)) if err.status == StatusCode::NOT_FOUND => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+    pub async fn delete_account_activity_subscription(
+        &self,
+        env_name: &str,
+        user_id: impl Into<UserId>,
+    ) -> Result<()> {
+        self.execute(
+            self.request(
+                Method::DELETE,
+                self.account_activity_url(
+                    env_name,
+                    format!("subscriptions/{}.json", user_id.into()),
+                )?,
+            )
+            .build()?,
+        )
+        .await?;
+        Ok(())
+    }
+    pub async fn get_account_activity_subscriptions(
+        &self,
+        env_name: &str,
+    ) -> Result<WebhookSubscriptions> {
+        Ok(self
+            .execute(
+                self.request(
+                    Method::GET,
+                    self.account_activity_url(env_name, "subscriptions/list.json")?,
+                )
+                .build()?,
+            )
+            .await?
+            .json()
+            .await?)
+    }
+}
@@ -1,18 +1,37 @@
 use crate::api_result::{ApiPayload, ApiResponse, ApiResponseExt, ApiResult};
 use crate::authorization::Authorization;
+use crate::cache::ResponseCache;
+#[cfg(feature = "cassette")]
+use crate::cassette::Cassette;
 use crate::error::Result;
+use crate::metrics::{ApiCall, Metrics};
+#[cfg(feature = "tower")]
+use crate::middleware::{BoxError, ServiceTransport};
+use crate::quota::QuotaGuard;
+use crate::requests::Batcher;
+#[cfg(feature = "tower")]
+use crate::transport::TransportResponse;
+use crate::transport::{RateLimit, ReqwestTransport, TwitterTransport};
 use crate::utils::JsonStream;
+use crate::utils::{endpoint_template, trace_event};
 use futures::prelude::*;
 use reqwest::header::AUTHORIZATION;
-use reqwest::{Client, IntoUrl, Method, Url};
+use reqwest::{Client, IntoUrl, Method, Request, Response, Url};
 use serde::de::DeserializeOwned;
+use std::fmt;
 use std::sync::Arc;
+use std::time::Instant;
 
-#[derive(Debug)]
 pub struct TwitterApi<A> {
     client: Client,
+    transport: Arc<dyn TwitterTransport>,
+    metrics: Option<Arc<dyn Metrics>>,
+    cache: Option<Arc<ResponseCache>>,
+    quota_guard: Option<Arc<QuotaGuard>>,
     base_url: Url,
     auth: Arc<A>,
+    #[cfg(feature = "cassette")]
+    cassette: Option<Arc<Cassette>>,
 }
 
 impl<A> TwitterApi<A>
@@ -20,10 +39,17 @@ where
     A: Authorization,
 {
     pub fn new(auth: A) -> Self {
+        let transport = ReqwestTransport::default();
         Self {
-            client: Client::builder().pool_max_idle_per_host(0).build().unwrap(),
+            client: transport.client().clone(),
+            transport: Arc::new(transport),
+            metrics: None,
+            cache: None,
+            quota_guard: None,
             base_url: Url::parse("https://api.twitter.com/2/").unwrap(),
             auth: Arc::new(auth),
+            #[cfg(feature = "cassette")]
+            cassette: None,
         }
     }
 
@@ -31,6 +57,88 @@ where
         &self.auth
     }
 
+    /// Send requests to `base_url` instead of `https://api.twitter.com/2/`, e.g. a proxy or
+    /// a mock server. Must end with a `/`.
+    pub fn with_base_url(mut self, base_url: Url) -> Self {
+        self.base_url = base_url;
+        self
+    }
+
+    /// Send all requests through `transport` instead of the default [`ReqwestTransport`]
+    pub fn with_transport(mut self, transport: impl TwitterTransport) -> Self {
+        self.transport = Arc::new(transport);
+        self
+    }
+
+    /// Send all requests through a tower `service`, usually layers on top of a
+    /// [`ReqwestTransport`]
+    #[cfg(feature = "tower")]
+    pub fn with_service<S>(self, service: S) -> Self
+    where
+        S: tower::Service<Request, Response = TransportResponse> + Clone + Send + 'static,
+        S::Error: Into<BoxError>,
+        S::Future: Send,
+    {
+        self.with_transport(ServiceTransport::new(service))
+    }
+
+    /// Wrap the default [`ReqwestTransport`] of this client in a tower `layer`
+    #[cfg(feature = "tower")]
+    pub fn with_layer<L>(self, layer: L) -> Self
+    where
+        L: tower::Layer<ReqwestTransport>,
+        L::Service: tower::Service<Request, Response = TransportResponse> + Clone + Send + 'static,
+        <L::Service as tower::Service<Request>>::Error: Into<BoxError>,
+        <L::Service as tower::Service<Request>>::Future: Send,
+    {
+        let service = layer.layer(ReqwestTransport::new(self.client.clone()));
+        self.with_service(service)
+    }
+
+    /// Report every API call made through this client to `metrics`
+    pub fn with_metrics(mut self, metrics: impl Metrics) -> Self {
+        self.metrics = Some(Arc::new(metrics));
+        self
+    }
+
+    /// Answer repeated `GET` requests from `cache`
+    pub fn with_cache(mut self, cache: impl Into<Arc<ResponseCache>>) -> Self {
+        self.cache = Some(cache.into());
+        self
+    }
+
+    /// Send all requests past the cache, e.g. in jobs which must see the current state
+    pub fn without_cache(mut self) -> Self {
+        self.cache = None;
+        self
+    }
+
+    /// Check the Tweet usage of the project with `guard` before requests consuming Tweets
+    pub fn with_quota_guard(mut self, guard: impl Into<Arc<QuotaGuard>>) -> Self {
+        self.quota_guard = Some(guard.into());
+        self
+    }
+
+    /// Record or replay all requests made through this client with `cassette`
+    #[cfg(feature = "cassette")]
+    pub fn with_cassette(mut self, cassette: impl Into<Arc<Cassette>>) -> Self {
+        self.cassette = Some(cassette.into());
+        self
+    }
+
+    #[cfg(feature = "cassette")]
+    pub fn cassette(&self) -> Option<&Cassette> {
+        self.cassette.as_deref()
+    }
+
+    /// Coalesce concurrent single user and tweet lookups into batched requests
+    pub fn batcher(&self) -> Batcher<A>
+    where
+        A: Send + Sync + 'static,
+    {
+        Batcher::new(self)
+    }
+
     pub(crate) fn url(&self, url: impl AsRef<str>) -> Result<Url> {
         Ok(self.base_url.join(url.as_ref())?)
     }
@@ -39,22 +147,109 @@ where
         self.client.request(method, url)
     }
 
+    /// Authorize and execute a request, failing on non-success status codes
+    pub(crate) async fn execute(&self, req: Request) -> Result<Response> {
+        self.execute_unchecked(req)
+            .await?
+            .api_error_for_status()
+            .await
+    }
+
+    /// Authorize and execute a request, reporting it to the metrics hook
+    pub(crate) async fn execute_unchecked(&self, req: Request) -> Result<Response> {
+        if let Some(guard) = self.quota_guard.as_ref() {
+            guard.check(self, &req).await?;
+        }
+        self.instrumented(req, true).await
+    }
+
+    /// Execute a request to a pre-signed URL, like the upload and download URLs of
+    /// compliance jobs, which must not carry the credentials of the client
+    pub(crate) async fn execute_presigned(&self, req: Request) -> Result<Response> {
+        self.instrumented(req, false)
+            .await?
+            .api_error_for_status()
+            .await
+    }
+
+    async fn instrumented(&self, req: Request, authorize: bool) -> Result<Response> {
+        let method = req.method().clone();
+        let endpoint = endpoint_template(req.url().path());
+        #[cfg(feature = "tracing")]
+        let span = tracing::info_span!(
+            "twitter_api",
+            method = %method,
+            endpoint = %endpoint,
+            pagination_token = tracing::field::Empty,
+            status = tracing::field::Empty,
+            rate_limit_remaining = tracing::field::Empty,
+            latency_ms = tracing::field::Empty,
+        );
+        #[cfg(feature = "tracing")]
+        if let Some((_, token)) = req
+            .url()
+            .query_pairs()
+            .find(|(key, _)| key == "pagination_token" || key == "next_token")
+        {
+            span.record("pagination_token", &*token);
+        }
+
+        let started = Instant::now();
+        let response = self.dispatch(req, authorize);
+        #[cfg(feature = "tracing")]
+        let response = tracing::Instrument::instrument(response, span.clone());
+        let response = response.await;
+
+        let call = ApiCall {
+            method: &method,
+            endpoint: &endpoint,
+            status: response.as_ref().ok().map(|res| res.status()),
+            rate_limit: response
+                .as_ref()
+                .ok()
+                .and_then(|res| RateLimit::from_headers(res.headers())),
+            latency: started.elapsed(),
+            error: response.as_ref().err(),
+        };
+        #[cfg(feature = "tracing")]
+        call.trace(&span);
+        if let Some(metrics) = self.metrics.as_ref() {
+            metrics.record(&call);
+        }
+        response
+    }
+
+    pub(crate) async fn dispatch(&self, mut req: Request, authorize: bool) -> Result<Response> {
+        #[cfg(feature = "cassette")]
+        if let Some(cassette) = self.cassette.as_ref().filter(|c| c.is_replaying()) {
+            return cassette.play(&req);
+        }
+        if authorize {
+            let authorization = self.auth.header(&req).await?;
+            let _ = req.headers_mut().insert(AUTHORIZATION, authorization);
+        }
+        #[cfg(feature = "cassette")]
+        if let Some(cassette) = self.cassette.as_ref() {
+            let recorded = cassette.recorded_request(&req);
+            let response = self.transport.execute(req).await?.into_response();
+            return Ok(cassette.capture(recorded, response));
+        }
+        Ok(self.transport.execute(req).await?.into_response())
+    }
+
     pub(crate) async fn send<T: DeserializeOwned, M: DeserializeOwned>(
         &self,
         req: reqwest::RequestBuilder,
     ) -> ApiResult<A, T, M> {
-        let mut req = req.build()?;
-        let authorization = self.auth.header(&req).await?;
-        let _ = req.headers_mut().insert(AUTHORIZATION, authorization);
+        let req = req.build()?;
         let url = req.url().clone();
-        let response = self
-            .client
-            .execute(req)
-            .await?
-            .api_error_for_status()
-            .await?
-            .json()
-            .await?;
+        let response = match self.cache.as_ref() {
+            Some(cache) if cache.is_cacheable(&req) => cache.fetch(self, req).await?,
+            Some(cache) if req.method() != Method::GET => cache.write(self, req).await?,
+            _ => self.execute(req).await?,
+        }
+        .json()
+        .await?;
         Ok(ApiResponse::new(self, url, response))
     }
 
@@ -62,17 +257,12 @@ where
         &self,
         req: reqwest::RequestBuilder,
     ) -> Result<impl Stream<Item = Result<ApiPayload<T, M>>>> {
-        let mut req = req.build()?;
-        let authorization = self.auth.header(&req).await?;
-        let _ = req.headers_mut().insert(AUTHORIZATION, authorization);
-        Ok(JsonStream::new(
-            self.client
-                .execute(req)
-                .await?
-                .api_error_for_status()
-                .await?
-                .bytes_stream(),
-        ))
+        let req = req.build()?;
+        #[cfg(feature = "tracing")]
+        let endpoint = endpoint_template(req.url().path());
+        let response = self.execute(req).await?;
+        trace_event!(tracing::Level::INFO, endpoint = %endpoint, "stream connected");
+        Ok(JsonStream::new(response.bytes_stream()))
     }
 }
 
@@ -80,8 +270,23 @@ impl<A> Clone for TwitterApi<A> {
     fn clone(&self) -> Self {
         Self {
             client: self.client.clone(),
+            transport: self.transport.clone(),
+            metrics: self.metrics.clone(),
+            cache: self.cache.clone(),
+            quota_guard: self.quota_guard.clone(),
             base_url: self.base_url.clone(),
             auth: self.auth.clone(),
+            #[cfg(feature = "cassette")]
+            cassette: self.cassette.clone(),
         }
     }
 }
+
+impl<A: fmt::Debug> fmt::Debug for TwitterApi<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TwitterApi")
+            .field("base_url", &self.base_url)
+            .field("auth", &self.auth)
+            .finish_non_exhaustive()
+    }
+}
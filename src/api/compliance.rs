@@ -2,9 +2,9 @@ use super::TwitterApi;
 use crate::api_result::ApiResult;
 use crate::authorization::Authorization;
 use crate::data::{ComplianceJob, ComplianceJobKind};
-use crate::id::IntoNumericId;
+use crate::id::ComplianceJobId;
 use crate::query::{ComplianceJobKindQuery, GetComplianceJobsRequestBuilder, UrlQueryExt};
-use crate::requests::ComplianceJobBuilder;
+use crate::requests::{ComplianceJobBuilder, ComplianceJobRunner};
 use reqwest::Method;
 
 impl<A> TwitterApi<A>
@@ -21,12 +21,18 @@ where
     }
     pub async fn get_compliance_job(
         &self,
-        id: impl IntoNumericId,
+        id: impl Into<ComplianceJobId>,
     ) -> ApiResult<A, ComplianceJob, ()> {
-        self.send(self.request(Method::GET, self.url(format!("compliance/job/{id}"))?))
-            .await
+        self.send(self.request(
+            Method::GET,
+            self.url(format!("compliance/job/{}", id.into()))?,
+        ))
+        .await
     }
     pub fn post_compliance_job(&self, kind: ComplianceJobKind) -> ComplianceJobBuilder<A> {
         ComplianceJobBuilder::new(self, self.url("compliance/jobs").unwrap(), kind)
     }
+    pub fn compliance_job_runner(&self, kind: ComplianceJobKind) -> ComplianceJobRunner<A> {
+        ComplianceJobRunner::new(self, kind)
+    }
 }
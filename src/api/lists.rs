@@ -2,29 +2,30 @@ use super::TwitterApi;
 use crate::api_result::ApiResult;
 use crate::authorization::Authorization;
 use crate::data::{Deleted, Following, IsMember, List, Pinned, Tweet, Updated, User};
-use crate::id::IntoNumericId;
+use crate::id::{ListId, UserId};
 use crate::meta::ResultCountMeta;
 use crate::query::{
     GetLimitedRelatedTweetsRequestBuilder, GetListsRequestBuilder, GetPaginatedListsRequestBuilder,
     GetRelatedUsersRequestBuilder,
 };
-use crate::requests::{ListBuilder, ListId, UserId};
+use crate::requests::{ListBuilder, ListIdRequest, ListMemberSync, UserIdRequest};
 use reqwest::Method;
 
 impl<A> TwitterApi<A>
 where
     A: Authorization,
 {
-    pub fn get_list(&self, id: impl IntoNumericId) -> GetListsRequestBuilder<A, List, ()> {
-        GetListsRequestBuilder::new(self, self.url(format!("lists/{id}")).unwrap())
+    pub fn get_list(&self, id: impl Into<ListId>) -> GetListsRequestBuilder<A, List, ()> {
+        GetListsRequestBuilder::new(self, self.url(format!("lists/{}", id.into())).unwrap())
     }
     pub fn get_user_owned_lists(
         &self,
-        id: impl IntoNumericId,
+        id: impl Into<UserId>,
     ) -> GetPaginatedListsRequestBuilder<A, Vec<List>, ResultCountMeta> {
         GetPaginatedListsRequestBuilder::new(
             self,
-            self.url(format!("users/{id}/owned_lists")).unwrap(),
+            self.url(format!("users/{}/owned_lists", id.into()))
+                .unwrap(),
         )
     }
     pub fn post_list(&self, name: impl ToString) -> ListBuilder<A, List> {
@@ -32,124 +33,161 @@ where
         builder.name(name);
         builder
     }
-    pub fn put_list(&self, id: impl IntoNumericId) -> ListBuilder<A, Updated> {
-        ListBuilder::new(self, self.url(format!("lists/{id}")).unwrap(), Method::PUT)
+    pub fn put_list(&self, id: impl Into<ListId>) -> ListBuilder<A, Updated> {
+        ListBuilder::new(
+            self,
+            self.url(format!("lists/{}", id.into())).unwrap(),
+            Method::PUT,
+        )
     }
-    pub async fn delete_list(&self, id: impl IntoNumericId) -> ApiResult<A, Deleted, ()> {
-        self.send(self.request(Method::DELETE, self.url(format!("lists/{id}"))?))
+    pub async fn delete_list(&self, id: impl Into<ListId>) -> ApiResult<A, Deleted, ()> {
+        self.send(self.request(Method::DELETE, self.url(format!("lists/{}", id.into()))?))
             .await
     }
     pub fn get_list_tweets(
         &self,
-        id: impl IntoNumericId,
+        id: impl Into<ListId>,
     ) -> GetLimitedRelatedTweetsRequestBuilder<A, Vec<Tweet>, ResultCountMeta> {
         GetLimitedRelatedTweetsRequestBuilder::new(
             self,
-            self.url(format!("lists/{id}/tweets")).unwrap(),
+            self.url(format!("lists/{}/tweets", id.into())).unwrap(),
         )
     }
     pub fn get_user_list_memberships(
         &self,
-        id: impl IntoNumericId,
+        id: impl Into<UserId>,
     ) -> GetPaginatedListsRequestBuilder<A, Vec<List>, ResultCountMeta> {
         GetPaginatedListsRequestBuilder::new(
             self,
-            self.url(format!("users/{id}/list_memberships")).unwrap(),
+            self.url(format!("users/{}/list_memberships", id.into()))
+                .unwrap(),
         )
     }
     pub fn get_list_members(
         &self,
-        id: impl IntoNumericId,
+        id: impl Into<ListId>,
     ) -> GetRelatedUsersRequestBuilder<A, Vec<User>, ResultCountMeta> {
-        GetRelatedUsersRequestBuilder::new(self, self.url(format!("lists/{id}/members")).unwrap())
+        GetRelatedUsersRequestBuilder::new(
+            self,
+            self.url(format!("lists/{}/members", id.into())).unwrap(),
+        )
+    }
+    /// Add and remove members of a list until they match `desired`
+    pub fn sync_list_members(
+        &self,
+        id: impl Into<ListId>,
+        desired: impl IntoIterator<Item = impl Into<UserId>>,
+    ) -> ListMemberSync<A> {
+        ListMemberSync::new(self, id, desired)
     }
     pub async fn post_list_member(
         &self,
-        id: impl IntoNumericId,
-        user_id: impl IntoNumericId,
+        id: impl Into<ListId>,
+        user_id: impl Into<UserId>,
     ) -> ApiResult<A, IsMember, ()> {
         self.send(
-            self.request(Method::POST, self.url(format!("lists/{id}/members"))?)
-                .json(&UserId::from(user_id)),
+            self.request(
+                Method::POST,
+                self.url(format!("lists/{}/members", id.into()))?,
+            )
+            .json(&UserIdRequest::from(user_id.into())),
         )
         .await
     }
     pub async fn delete_list_member(
         &self,
-        id: impl IntoNumericId,
-        user_id: impl IntoNumericId,
+        id: impl Into<ListId>,
+        user_id: impl Into<UserId>,
     ) -> ApiResult<A, IsMember, ()> {
         self.send(self.request(
             Method::DELETE,
-            self.url(format!("lists/{id}/members/{user_id}"))?,
+            self.url(format!("lists/{}/members/{}", id.into(), user_id.into()))?,
         ))
         .await
     }
     pub fn get_list_followers(
         &self,
-        id: impl IntoNumericId,
+        id: impl Into<ListId>,
     ) -> GetRelatedUsersRequestBuilder<A, Vec<User>, ResultCountMeta> {
-        GetRelatedUsersRequestBuilder::new(self, self.url(format!("lists/{id}/followers")).unwrap())
+        GetRelatedUsersRequestBuilder::new(
+            self,
+            self.url(format!("lists/{}/followers", id.into())).unwrap(),
+        )
     }
     pub fn get_user_followed_lists(
         &self,
-        id: impl IntoNumericId,
+        id: impl Into<UserId>,
     ) -> GetPaginatedListsRequestBuilder<A, Vec<List>, ResultCountMeta> {
         GetPaginatedListsRequestBuilder::new(
             self,
-            self.url(format!("users/{id}/followed_lists")).unwrap(),
+            self.url(format!("users/{}/followed_lists", id.into()))
+                .unwrap(),
         )
     }
     pub async fn post_user_followed_list(
         &self,
-        id: impl IntoNumericId,
-        list_id: impl IntoNumericId,
+        id: impl Into<UserId>,
+        list_id: impl Into<ListId>,
     ) -> ApiResult<A, Following, ()> {
         self.send(
             self.request(
                 Method::POST,
-                self.url(format!("users/{id}/followed_lists"))?,
+                self.url(format!("users/{}/followed_lists", id.into()))?,
             )
-            .json(&ListId::from(list_id)),
+            .json(&ListIdRequest::from(list_id.into())),
         )
         .await
     }
     pub async fn delete_user_followed_list(
         &self,
-        id: impl IntoNumericId,
-        list_id: impl IntoNumericId,
+        id: impl Into<UserId>,
+        list_id: impl Into<ListId>,
     ) -> ApiResult<A, Following, ()> {
         self.send(self.request(
             Method::DELETE,
-            self.url(format!("users/{id}/followed_lists/{list_id}"))?,
+            self.url(format!(
+                "users/{}/followed_lists/{}",
+                id.into(),
+                list_id.into()
+            ))?,
         ))
         .await
     }
     pub fn get_user_pinned_lists(
         &self,
-        id: impl IntoNumericId,
+        id: impl Into<UserId>,
     ) -> GetListsRequestBuilder<A, Vec<List>, ()> {
-        GetListsRequestBuilder::new(self, self.url(format!("users/{id}/lists")).unwrap())
+        GetListsRequestBuilder::new(
+            self,
+            self.url(format!("users/{}/lists", id.into())).unwrap(),
+        )
     }
     pub async fn post_user_pinned_list(
         &self,
-        id: impl IntoNumericId,
-        list_id: impl IntoNumericId,
+        id: impl Into<UserId>,
+        list_id: impl Into<ListId>,
     ) -> ApiResult<A, Pinned, ()> {
         self.send(
-            self.request(Method::POST, self.url(format!("users/{id}/pinned_lists"))?)
-                .json(&ListId::from(list_id)),
+            self.request(
+                Method::POST,
+                self.url(format!("users/{}/pinned_lists", id.into()))?,
+            )
+            .json(&ListIdRequest::from(list_id.into())),
         )
         .await
     }
     pub async fn delete_user_pinned_list(
         &self,
-        id: impl IntoNumericId,
-        list_id: impl IntoNumericId,
+        id: impl Into<UserId>,
+        list_id: impl Into<ListId>,
     ) -> ApiResult<A, Pinned, ()> {
         self.send(self.request(
             Method::DELETE,
-            self.url(format!("users/{id}/pinned_lists/{list_id}"))?,
+            self.url(format!(
+                "users/{}/pinned_lists/{}",
+                id.into(),
+                list_id.into()
+            ))?,
         ))
         .await
     }
@@ -1,8 +1,11 @@
+#[cfg(feature = "webhook")]
+mod account_activity;
 mod base;
 mod compliance;
 mod lists;
 mod spaces;
 mod tweets;
+mod usage;
 mod users;
 mod with_user_ctx;
 
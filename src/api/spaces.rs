@@ -1,7 +1,7 @@
 use super::TwitterApi;
 use crate::authorization::Authorization;
 use crate::data::{Space, Tweet, User};
-use crate::id::{IntoNumericId, IntoStringId};
+use crate::id::{SpaceId, UserId};
 use crate::meta::SimpleResultCountMeta;
 use crate::query::{
     GetRelatedTweetsRequestBuilder, GetRelatedUsersRequestBuilder, GetSpacesRequestBuilder,
@@ -15,34 +15,40 @@ where
 {
     pub fn get_spaces(
         &self,
-        ids: impl IntoIterator<Item = impl IntoStringId>,
+        ids: impl IntoIterator<Item = impl Into<SpaceId>>,
     ) -> GetSpacesRequestBuilder<A, Vec<Space>, ()> {
         let mut url = self.url("spaces").unwrap();
-        url.append_query_seq("ids", ids);
+        url.append_query_seq("ids", ids.into_iter().map(Into::<SpaceId>::into));
         GetSpacesRequestBuilder::new(self, url)
     }
-    pub fn get_space(&self, id: impl IntoStringId) -> GetSpacesRequestBuilder<A, Space, ()> {
-        GetSpacesRequestBuilder::new(self, self.url(url!("spaces/{}", id)).unwrap())
+    pub fn get_space(&self, id: impl Into<SpaceId>) -> GetSpacesRequestBuilder<A, Space, ()> {
+        GetSpacesRequestBuilder::new(self, self.url(url!("spaces/{}", id.into())).unwrap())
     }
     pub fn get_spaces_by_creator_ids(
         &self,
-        user_ids: impl IntoIterator<Item = impl IntoNumericId>,
+        user_ids: impl IntoIterator<Item = impl Into<UserId>>,
     ) -> GetSpacesRequestBuilder<A, Vec<Space>, SimpleResultCountMeta> {
         let mut url = self.url("spaces/by/creator_ids").unwrap();
-        url.append_query_seq("user_ids", user_ids);
+        url.append_query_seq("user_ids", user_ids.into_iter().map(Into::<UserId>::into));
         GetSpacesRequestBuilder::new(self, url)
     }
     pub fn get_space_buyers(
         &self,
-        id: impl IntoStringId,
+        id: impl Into<SpaceId>,
     ) -> GetRelatedUsersRequestBuilder<A, Vec<User>, ()> {
-        GetRelatedUsersRequestBuilder::new(self, self.url(url!("spaces/{}/buyers", id)).unwrap())
+        GetRelatedUsersRequestBuilder::new(
+            self,
+            self.url(url!("spaces/{}/buyers", id.into())).unwrap(),
+        )
     }
     pub fn get_space_tweets(
         &self,
-        id: impl IntoStringId,
+        id: impl Into<SpaceId>,
     ) -> GetRelatedTweetsRequestBuilder<A, Vec<Tweet>, ()> {
-        GetRelatedTweetsRequestBuilder::new(self, self.url(url!("spaces/{}/tweets", id)).unwrap())
+        GetRelatedTweetsRequestBuilder::new(
+            self,
+            self.url(url!("spaces/{}/tweets", id.into())).unwrap(),
+        )
     }
     pub fn get_spaces_search(
         &self,
@@ -4,15 +4,18 @@ use crate::authorization::Authorization;
 use crate::data::{
     Bookmarked, Deleted, Hidden, Liked, Retweeted, StreamRule, Tweet, TweetsCount, User,
 };
-use crate::id::IntoNumericId;
+use crate::error::{Error, Result};
+use crate::id::{TweetId, UserId};
+use crate::links::TwitterUrl;
 use crate::meta::{ResultCountMeta, SentMeta, TweetsCountsMeta, TweetsMeta};
 use crate::query::{
     GetRelatedTweetsRequestBuilder, GetStreamRulesRequestBuilder, GetTimelineRequestBuilder,
     GetTweetUsersRequestBuilder, GetTweetsCountsRequestBuilder, GetTweetsRequestBuilder,
     GetTweetsSearchRequestBuilder, GetTweetsStreamRequestBuilder, UrlQueryExt,
 };
-use crate::requests::{StreamRuleBuilder, TweetBuilder, TweetId};
+use crate::requests::{Backfill, StreamRuleBuilder, TweetBuilder, TweetIdRequest};
 use reqwest::Method;
+use time::OffsetDateTime;
 
 impl<A> TwitterApi<A>
 where
@@ -20,33 +23,47 @@ where
 {
     pub fn get_tweets(
         &self,
-        ids: impl IntoIterator<Item = impl IntoNumericId>,
+        ids: impl IntoIterator<Item = impl Into<TweetId>>,
     ) -> GetTweetsRequestBuilder<A, Vec<Tweet>, ()> {
         let mut url = self.url("tweets").unwrap();
-        url.append_query_seq("ids", ids);
+        url.append_query_seq("ids", ids.into_iter().map(Into::<TweetId>::into));
         GetTweetsRequestBuilder::new(self, url)
     }
-    pub fn get_tweet(&self, id: impl IntoNumericId) -> GetTweetsRequestBuilder<A, Tweet, ()> {
-        GetTweetsRequestBuilder::new(self, self.url(format!("tweets/{id}")).unwrap())
+    pub fn get_tweet(&self, id: impl Into<TweetId>) -> GetTweetsRequestBuilder<A, Tweet, ()> {
+        GetTweetsRequestBuilder::new(self, self.url(format!("tweets/{}", id.into())).unwrap())
+    }
+    pub fn get_tweet_by_url(&self, url: &str) -> Result<GetTweetsRequestBuilder<A, Tweet, ()>> {
+        let id = TwitterUrl::parse(url)?
+            .tweet_id()
+            .ok_or_else(|| Error::UnrecognizedUrl(url.to_string()))?;
+        Ok(self.get_tweet(id))
     }
     pub fn post_tweet(&self) -> TweetBuilder<A> {
         TweetBuilder::new(self, self.url("tweets").unwrap())
     }
-    pub async fn delete_tweet(&self, id: impl IntoNumericId) -> ApiResult<A, Deleted, ()> {
-        self.send(self.request(Method::DELETE, self.url(format!("tweets/{id}"))?))
+    pub async fn delete_tweet(&self, id: impl Into<TweetId>) -> ApiResult<A, Deleted, ()> {
+        self.send(self.request(Method::DELETE, self.url(format!("tweets/{}", id.into()))?))
             .await
     }
     pub fn get_user_tweets(
         &self,
-        user_id: impl IntoNumericId,
+        user_id: impl Into<UserId>,
     ) -> GetTimelineRequestBuilder<A, Vec<Tweet>, TweetsMeta> {
-        GetTimelineRequestBuilder::new(self, self.url(format!("users/{user_id}/tweets")).unwrap())
+        GetTimelineRequestBuilder::new(
+            self,
+            self.url(format!("users/{}/tweets", user_id.into()))
+                .unwrap(),
+        )
     }
     pub fn get_user_mentions(
         &self,
-        user_id: impl IntoNumericId,
+        user_id: impl Into<UserId>,
     ) -> GetTimelineRequestBuilder<A, Vec<Tweet>, TweetsMeta> {
-        GetTimelineRequestBuilder::new(self, self.url(format!("users/{user_id}/mentions")).unwrap())
+        GetTimelineRequestBuilder::new(
+            self,
+            self.url(format!("users/{}/mentions", user_id.into()))
+                .unwrap(),
+        )
     }
     pub fn get_tweets_search_recent(
         &self,
@@ -64,6 +81,19 @@ where
         url.append_query_val("query", query);
         GetTweetsSearchRequestBuilder::new(self, url)
     }
+    /// Collect all Tweets matching `query` between `start_time` and `end_time` from the
+    /// full-archive search, with checkpoints to resume from
+    pub fn backfill(
+        &self,
+        query: impl ToString,
+        start_time: OffsetDateTime,
+        end_time: OffsetDateTime,
+    ) -> Backfill<A>
+    where
+        A: Send + Sync + 'static,
+    {
+        Backfill::new(self, query, start_time, end_time)
+    }
     pub fn get_tweets_counts_recent(
         &self,
         query: impl ToString,
@@ -96,126 +126,151 @@ where
     }
     pub fn get_tweet_retweeted_by(
         &self,
-        id: impl IntoNumericId,
+        id: impl Into<TweetId>,
     ) -> GetTweetUsersRequestBuilder<A, Vec<User>, ResultCountMeta> {
         GetTweetUsersRequestBuilder::new(
             self,
-            self.url(format!("tweets/{id}/retweeted_by")).unwrap(),
+            self.url(format!("tweets/{}/retweeted_by", id.into()))
+                .unwrap(),
         )
     }
     pub async fn post_user_retweet(
         &self,
-        user_id: impl IntoNumericId,
-        tweet_id: impl IntoNumericId,
+        user_id: impl Into<UserId>,
+        tweet_id: impl Into<TweetId>,
     ) -> ApiResult<A, Retweeted, ()> {
         self.send(
-            self.request(Method::POST, self.url(format!("users/{user_id}/retweets"))?)
-                .json(&TweetId::from(tweet_id)),
+            self.request(
+                Method::POST,
+                self.url(format!("users/{}/retweets", user_id.into()))?,
+            )
+            .json(&TweetIdRequest::from(tweet_id.into())),
         )
         .await
     }
     pub async fn delete_user_retweet(
         &self,
-        user_id: impl IntoNumericId,
-        tweet_id: impl IntoNumericId,
+        user_id: impl Into<UserId>,
+        tweet_id: impl Into<TweetId>,
     ) -> ApiResult<A, Retweeted, ()> {
         self.send(self.request(
             Method::DELETE,
-            self.url(format!("users/{user_id}/retweets/{tweet_id}"))?,
+            self.url(format!(
+                "users/{}/retweets/{}",
+                user_id.into(),
+                tweet_id.into()
+            ))?,
         ))
         .await
     }
     pub fn get_tweet_quote_tweets(
         &self,
-        id: impl IntoNumericId,
+        id: impl Into<TweetId>,
     ) -> GetRelatedTweetsRequestBuilder<A, Vec<Tweet>, ResultCountMeta> {
         GetRelatedTweetsRequestBuilder::new(
             self,
-            self.url(format!("tweets/{id}/quote_tweets")).unwrap(),
+            self.url(format!("tweets/{}/quote_tweets", id.into()))
+                .unwrap(),
         )
     }
     pub fn get_tweet_liking_users(
         &self,
-        id: impl IntoNumericId,
+        id: impl Into<TweetId>,
     ) -> GetTweetUsersRequestBuilder<A, Vec<User>, ResultCountMeta> {
         GetTweetUsersRequestBuilder::new(
             self,
-            self.url(format!("tweets/{id}/liking_users")).unwrap(),
+            self.url(format!("tweets/{}/liking_users", id.into()))
+                .unwrap(),
         )
     }
     pub fn get_user_liked_tweets(
         &self,
-        id: impl IntoNumericId,
+        id: impl Into<UserId>,
     ) -> GetRelatedTweetsRequestBuilder<A, Vec<Tweet>, ResultCountMeta> {
         GetRelatedTweetsRequestBuilder::new(
             self,
-            self.url(format!("users/{id}/liked_tweets")).unwrap(),
+            self.url(format!("users/{}/liked_tweets", id.into()))
+                .unwrap(),
         )
     }
     pub async fn post_user_like(
         &self,
-        user_id: impl IntoNumericId,
-        tweet_id: impl IntoNumericId,
+        user_id: impl Into<UserId>,
+        tweet_id: impl Into<TweetId>,
     ) -> ApiResult<A, Liked, ()> {
         self.send(
-            self.request(Method::POST, self.url(format!("users/{user_id}/likes"))?)
-                .json(&TweetId::from(tweet_id)),
+            self.request(
+                Method::POST,
+                self.url(format!("users/{}/likes", user_id.into()))?,
+            )
+            .json(&TweetIdRequest::from(tweet_id.into())),
         )
         .await
     }
     pub async fn delete_user_like(
         &self,
-        user_id: impl IntoNumericId,
-        tweet_id: impl IntoNumericId,
+        user_id: impl Into<UserId>,
+        tweet_id: impl Into<TweetId>,
     ) -> ApiResult<A, Liked, ()> {
         self.send(self.request(
             Method::DELETE,
-            self.url(format!("users/{user_id}/likes/{tweet_id}"))?,
+            self.url(format!(
+                "users/{}/likes/{}",
+                user_id.into(),
+                tweet_id.into()
+            ))?,
         ))
         .await
     }
     pub fn get_user_bookmarks(
         &self,
-        id: impl IntoNumericId,
+        id: impl Into<UserId>,
     ) -> GetRelatedTweetsRequestBuilder<A, Vec<Tweet>, ResultCountMeta> {
         GetRelatedTweetsRequestBuilder::new(
             self,
-            self.url(format!("users/{id}/bookmarks")).unwrap(),
+            self.url(format!("users/{}/bookmarks", id.into())).unwrap(),
         )
     }
     pub async fn post_user_bookmark(
         &self,
-        user_id: impl IntoNumericId,
-        tweet_id: impl IntoNumericId,
+        user_id: impl Into<UserId>,
+        tweet_id: impl Into<TweetId>,
     ) -> ApiResult<A, Bookmarked, ()> {
         self.send(
             self.request(
                 Method::POST,
-                self.url(format!("users/{user_id}/bookmarks"))?,
+                self.url(format!("users/{}/bookmarks", user_id.into()))?,
             )
-            .json(&TweetId::from(tweet_id)),
+            .json(&TweetIdRequest::from(tweet_id.into())),
         )
         .await
     }
     pub async fn delete_user_bookmark(
         &self,
-        user_id: impl IntoNumericId,
-        tweet_id: impl IntoNumericId,
+        user_id: impl Into<UserId>,
+        tweet_id: impl Into<TweetId>,
     ) -> ApiResult<A, Bookmarked, ()> {
         self.send(self.request(
             Method::DELETE,
-            self.url(format!("users/{user_id}/bookmarks/{tweet_id}"))?,
+            self.url(format!(
+                "users/{}/bookmarks/{}",
+                user_id.into(),
+                tweet_id.into()
+            ))?,
         ))
         .await
     }
     pub async fn put_tweet_hidden(
         &self,
-        id: impl IntoNumericId,
+        id: impl Into<TweetId>,
         hidden: bool,
     ) -> ApiResult<A, Hidden, ()> {
         self.send(
-            self.request(Method::PUT, self.url(format!("tweets/{id}/hidden"))?)
-                .json(&Hidden::from(hidden)),
+            self.request(
+                Method::PUT,
+                self.url(format!("tweets/{}/hidden", id.into()))?,
+            )
+            .json(&Hidden::from(hidden)),
         )
         .await
     }
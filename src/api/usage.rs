@@ -0,0 +1,14 @@
+use super::TwitterApi;
+use crate::authorization::Authorization;
+use crate::data::TweetUsage;
+use crate::query::GetUsageTweetsRequestBuilder;
+
+impl<A> TwitterApi<A>
+where
+    A: Authorization,
+{
+    /// The Tweets consumed by the project of the authorizing app against its monthly cap
+    pub fn get_usage_tweets(&self) -> GetUsageTweetsRequestBuilder<A, TweetUsage, ()> {
+        GetUsageTweetsRequestBuilder::new(self, self.url("usage/tweets").unwrap())
+    }
+}
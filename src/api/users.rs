@@ -2,10 +2,12 @@ use super::TwitterApi;
 use crate::api_result::ApiResult;
 use crate::authorization::Authorization;
 use crate::data::{Blocking, Following, Muting, User};
-use crate::id::IntoNumericId;
+use crate::id::UserId;
 use crate::meta::ResultCountMeta;
 use crate::query::{GetRelatedUsersRequestBuilder, GetUsersRequestBuilder, UrlQueryExt};
-use crate::requests::TargetUserId;
+use crate::requests::{
+    BulkAction, BulkRelationship, FollowDirection, FollowGraph, TargetUserIdRequest,
+};
 use crate::utils::url;
 use reqwest::Method;
 
@@ -15,14 +17,14 @@ where
 {
     pub fn get_users(
         &self,
-        ids: impl IntoIterator<Item = impl IntoNumericId>,
+        ids: impl IntoIterator<Item = impl Into<UserId>>,
     ) -> GetUsersRequestBuilder<A, Vec<User>, ()> {
         let mut url = self.url("users").unwrap();
-        url.append_query_seq("ids", ids);
+        url.append_query_seq("ids", ids.into_iter().map(Into::<UserId>::into));
         GetUsersRequestBuilder::new(self, url)
     }
-    pub fn get_user(&self, id: impl IntoNumericId) -> GetUsersRequestBuilder<A, User, ()> {
-        GetUsersRequestBuilder::new(self, self.url(format!("users/{id}")).unwrap())
+    pub fn get_user(&self, id: impl Into<UserId>) -> GetUsersRequestBuilder<A, User, ()> {
+        GetUsersRequestBuilder::new(self, self.url(format!("users/{}", id.into())).unwrap())
     }
     pub fn get_users_by_usernames(
         &self,
@@ -46,91 +48,136 @@ where
     }
     pub fn get_user_followers(
         &self,
-        id: impl IntoNumericId,
+        id: impl Into<UserId>,
     ) -> GetRelatedUsersRequestBuilder<A, Vec<User>, ResultCountMeta> {
-        GetRelatedUsersRequestBuilder::new(self, self.url(format!("users/{id}/followers")).unwrap())
+        GetRelatedUsersRequestBuilder::new(
+            self,
+            self.url(format!("users/{}/followers", id.into())).unwrap(),
+        )
     }
     pub fn get_user_following(
         &self,
-        id: impl IntoNumericId,
+        id: impl Into<UserId>,
     ) -> GetRelatedUsersRequestBuilder<A, Vec<User>, ResultCountMeta> {
-        GetRelatedUsersRequestBuilder::new(self, self.url(format!("users/{id}/following")).unwrap())
+        GetRelatedUsersRequestBuilder::new(
+            self,
+            self.url(format!("users/{}/following", id.into())).unwrap(),
+        )
+    }
+    /// Apply `action` to many users within the write limits of its endpoint
+    pub fn bulk(&self, action: BulkAction) -> BulkRelationship<A> {
+        BulkRelationship::new(self, action)
+    }
+    /// Take complete snapshots of the followers or followings of a user
+    pub fn follow_graph(
+        &self,
+        id: impl Into<UserId>,
+        direction: FollowDirection,
+    ) -> FollowGraph<A> {
+        FollowGraph::new(self, id, direction)
     }
     pub async fn post_user_following(
         &self,
-        id: impl IntoNumericId,
-        target_user_id: impl IntoNumericId,
+        id: impl Into<UserId>,
+        target_user_id: impl Into<UserId>,
     ) -> ApiResult<A, Following, ()> {
         self.send(
-            self.request(Method::POST, self.url(format!("users/{id}/following"))?)
-                .json(&TargetUserId::from(target_user_id)),
+            self.request(
+                Method::POST,
+                self.url(format!("users/{}/following", id.into()))?,
+            )
+            .json(&TargetUserIdRequest::from(target_user_id.into())),
         )
         .await
     }
     pub async fn delete_user_following(
         &self,
-        source_user_id: impl IntoNumericId,
-        target_user_id: impl IntoNumericId,
+        source_user_id: impl Into<UserId>,
+        target_user_id: impl Into<UserId>,
     ) -> ApiResult<A, Following, ()> {
         self.send(self.request(
             Method::DELETE,
-            self.url(format!("users/{source_user_id}/following/{target_user_id}"))?,
+            self.url(format!(
+                "users/{}/following/{}",
+                source_user_id.into(),
+                target_user_id.into()
+            ))?,
         ))
         .await
     }
     pub fn get_user_blocking(
         &self,
-        id: impl IntoNumericId,
+        id: impl Into<UserId>,
     ) -> GetRelatedUsersRequestBuilder<A, Vec<User>, ResultCountMeta> {
-        GetRelatedUsersRequestBuilder::new(self, self.url(format!("users/{id}/blocking")).unwrap())
+        GetRelatedUsersRequestBuilder::new(
+            self,
+            self.url(format!("users/{}/blocking", id.into())).unwrap(),
+        )
     }
     pub async fn post_user_blocking(
         &self,
-        id: impl IntoNumericId,
-        target_user_id: impl IntoNumericId,
+        id: impl Into<UserId>,
+        target_user_id: impl Into<UserId>,
     ) -> ApiResult<A, Blocking, ()> {
         self.send(
-            self.request(Method::POST, self.url(format!("users/{id}/blocking"))?)
-                .json(&TargetUserId::from(target_user_id)),
+            self.request(
+                Method::POST,
+                self.url(format!("users/{}/blocking", id.into()))?,
+            )
+            .json(&TargetUserIdRequest::from(target_user_id.into())),
         )
         .await
     }
     pub async fn delete_user_blocking(
         &self,
-        source_user_id: impl IntoNumericId,
-        target_user_id: impl IntoNumericId,
+        source_user_id: impl Into<UserId>,
+        target_user_id: impl Into<UserId>,
     ) -> ApiResult<A, Blocking, ()> {
         self.send(self.request(
             Method::DELETE,
-            self.url(format!("users/{source_user_id}/blocking/{target_user_id}"))?,
+            self.url(format!(
+                "users/{}/blocking/{}",
+                source_user_id.into(),
+                target_user_id.into()
+            ))?,
         ))
         .await
     }
     pub fn get_user_muting(
         &self,
-        id: impl IntoNumericId,
+        id: impl Into<UserId>,
     ) -> GetRelatedUsersRequestBuilder<A, Vec<User>, ResultCountMeta> {
-        GetRelatedUsersRequestBuilder::new(self, self.url(format!("users/{id}/muting")).unwrap())
+        GetRelatedUsersRequestBuilder::new(
+            self,
+            self.url(format!("users/{}/muting", id.into())).unwrap(),
+        )
     }
     pub async fn post_user_muting(
         &self,
-        id: impl IntoNumericId,
-        target_user_id: impl IntoNumericId,
+        id: impl Into<UserId>,
+        target_user_id: impl Into<UserId>,
     ) -> ApiResult<A, Muting, ()> {
         self.send(
-            self.request(Method::POST, self.url(format!("users/{id}/muting"))?)
-                .json(&TargetUserId::from(target_user_id)),
+            self.request(
+                Method::POST,
+                self.url(format!("users/{}/muting", id.into()))?,
+            )
+            .json(&TargetUserIdRequest::from(target_user_id.into())),
         )
         .await
     }
     pub async fn delete_user_muting(
         &self,
-        source_user_id: impl IntoNumericId,
-        target_user_id: impl IntoNumericId,
+        source_user_id: impl Into<UserId>,
+        target_user_id: impl Into<UserId>,
     ) -> ApiResult<A, Muting, ()> {
         self.send(self.request(
             Method::DELETE,
-            self.url(format!("users/{source_user_id}/muting/{target_user_id}"))?,
+            self.url(format!(
+                "users/{}/muting/{}",
+                source_user_id.into(),
+                target_user_id.into()
+            ))?,
         ))
         .await
     }
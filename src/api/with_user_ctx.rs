@@ -3,7 +3,7 @@ use crate::api_result::ApiResult;
 use crate::authorization::Authorization;
 use crate::data::{Blocking, Following, List, Muting, Pinned, Space, Tweet, User};
 use crate::error::Result;
-use crate::id::{IntoNumericId, NumericId};
+use crate::id::{ListId, UserId};
 use crate::meta::{ResultCountMeta, SimpleResultCountMeta, TweetsMeta};
 use crate::query::{
     GetPaginatedListsRequestBuilder, GetRelatedUsersRequestBuilder, GetSpacesRequestBuilder,
@@ -11,7 +11,7 @@ use crate::query::{
 };
 
 pub struct TwitterApiWithUserCtx<A> {
-    user_id: NumericId,
+    user_id: UserId,
     client: TwitterApi<A>,
 }
 
@@ -49,7 +49,7 @@ where
     }
     pub async fn post_my_followed_list(
         &self,
-        list_id: impl IntoNumericId,
+        list_id: impl Into<ListId>,
     ) -> ApiResult<A, Following, ()> {
         self.client
             .post_user_followed_list(self.user_id, list_id)
@@ -57,7 +57,7 @@ where
     }
     pub async fn delete_my_followed_list(
         &self,
-        list_id: impl IntoNumericId,
+        list_id: impl Into<ListId>,
     ) -> ApiResult<A, Following, ()> {
         self.client
             .delete_user_followed_list(self.user_id, list_id)
@@ -65,7 +65,7 @@ where
     }
     pub async fn post_my_pinned_list(
         &self,
-        list_id: impl IntoNumericId,
+        list_id: impl Into<ListId>,
     ) -> ApiResult<A, Pinned, ()> {
         self.client
             .post_user_pinned_list(self.user_id, list_id)
@@ -73,7 +73,7 @@ where
     }
     pub async fn delete_my_pinned_list(
         &self,
-        list_id: impl IntoNumericId,
+        list_id: impl Into<ListId>,
     ) -> ApiResult<A, Pinned, ()> {
         self.client
             .delete_user_pinned_list(self.user_id, list_id)
@@ -96,7 +96,7 @@ where
     }
     pub async fn post_my_following(
         &self,
-        target_user_id: impl IntoNumericId,
+        target_user_id: impl Into<UserId>,
     ) -> ApiResult<A, Following, ()> {
         self.client
             .post_user_following(self.user_id, target_user_id)
@@ -104,7 +104,7 @@ where
     }
     pub async fn delete_my_following(
         &self,
-        target_user_id: impl IntoNumericId,
+        target_user_id: impl Into<UserId>,
     ) -> ApiResult<A, Following, ()> {
         self.client
             .delete_user_following(self.user_id, target_user_id)
@@ -115,7 +115,7 @@ where
     }
     pub async fn post_my_blocking(
         &self,
-        target_user_id: impl IntoNumericId,
+        target_user_id: impl Into<UserId>,
     ) -> ApiResult<A, Blocking, ()> {
         self.client
             .post_user_blocking(self.user_id, target_user_id)
@@ -123,7 +123,7 @@ where
     }
     pub async fn delete_my_blocking(
         &self,
-        target_user_id: impl IntoNumericId,
+        target_user_id: impl Into<UserId>,
     ) -> ApiResult<A, Blocking, ()> {
         self.client
             .delete_user_blocking(self.user_id, target_user_id)
@@ -131,7 +131,7 @@ where
     }
     pub async fn post_my_muting(
         &self,
-        target_user_id: impl IntoNumericId,
+        target_user_id: impl Into<UserId>,
     ) -> ApiResult<A, Muting, ()> {
         self.client
             .post_user_muting(self.user_id, target_user_id)
@@ -139,7 +139,7 @@ where
     }
     pub async fn delete_my_muting(
         &self,
-        target_user_id: impl IntoNumericId,
+        target_user_id: impl Into<UserId>,
     ) -> ApiResult<A, Muting, ()> {
         self.client
             .delete_user_muting(self.user_id, target_user_id)
@@ -4,11 +4,13 @@ use crate::data::Expansions;
 use crate::error::{Error, Result};
 use crate::meta::PaginationMeta;
 use crate::query::UrlQueryExt;
+use crate::transport::RateLimit;
 use async_trait::async_trait;
 use reqwest::{Method, Response, StatusCode};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::collections::HashMap;
 use std::{fmt, ops};
+use time::OffsetDateTime;
 use url::Url;
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -67,6 +69,10 @@ pub struct ApiError {
     pub detail: String,
     #[serde(default)]
     pub errors: Vec<ApiErrorItem>,
+    /// When the rate limit resets according to the headers of the response, e.g. of a
+    /// `429 Too Many Requests`
+    #[serde(skip)]
+    pub rate_limit_reset: Option<OffsetDateTime>,
 }
 
 impl fmt::Display for ApiError {
@@ -207,15 +213,18 @@ impl ApiResponseExt for Response {
         if status.is_success() {
             Ok(self)
         } else {
+            let rate_limit_reset = RateLimit::from_headers(self.headers()).map(|limit| limit.reset);
             let text = self.text().await?;
             Err(Error::Api(
                 if let Ok(mut error) = serde_json::from_str::<ApiError>(&text) {
                     error.status = status;
+                    error.rate_limit_reset = rate_limit_reset;
                     error
                 } else {
                     ApiError {
                         status,
                         detail: text,
+                        rate_limit_reset,
                         ..Default::default()
                     }
                 },
@@ -84,7 +84,10 @@ impl From<Scope> for oauth2::Scope {
 }
 
 #[derive(Clone, Debug)]
-pub struct Oauth2Client(BasicClient);
+pub struct Oauth2Client {
+    client: BasicClient,
+    client_secret: Option<ClientSecret>,
+}
 
 impl Oauth2Client {
     /// Create a new private client, authorized with client secret.
@@ -102,10 +105,12 @@ impl Oauth2Client {
         client_secret: Option<impl ToString>,
         callback_url: Url,
     ) -> Self {
-        Self(
-            BasicClient::new(
+        let client_secret =
+            client_secret.map(|client_secret| ClientSecret::new(client_secret.to_string()));
+        Self {
+            client: BasicClient::new(
                 ClientId::new(client_id.to_string()),
-                client_secret.map(|client_secret| ClientSecret::new(client_secret.to_string())),
+                client_secret.clone(),
                 AuthUrl::from_url("https://twitter.com/i/oauth2/authorize".parse().unwrap()),
                 Some(TokenUrl::from_url(
                     "https://api.twitter.com/2/oauth2/token".parse().unwrap(),
@@ -115,7 +120,23 @@ impl Oauth2Client {
                 "https://api.twitter.com/2/oauth2/revoke".parse().unwrap(),
             ))
             .set_redirect_uri(RedirectUrl::from_url(callback_url)),
+            client_secret,
+        }
+    }
+
+    /// Request and revoke tokens at other endpoints than Twitter's, e.g. of a mock server
+    pub fn with_token_endpoints(self, token_url: Url, revocation_url: Url) -> Self {
+        let mut client = BasicClient::new(
+            self.client.client_id().clone(),
+            self.client_secret.clone(),
+            self.client.auth_url().clone(),
+            Some(TokenUrl::from_url(token_url)),
         )
+        .set_revocation_uri(RevocationUrl::from_url(revocation_url));
+        if let Some(redirect_url) = self.client.redirect_url() {
+            client = client.set_redirect_uri(redirect_url.clone());
+        }
+        Self { client, ..self }
     }
 
     pub fn auth_url(
@@ -123,7 +144,7 @@ impl Oauth2Client {
         challenge: PkceCodeChallenge,
         scopes: impl IntoIterator<Item = Scope>,
     ) -> (Url, CsrfToken) {
-        self.0
+        self.client
             .authorize_url(CsrfToken::new_random)
             .set_pkce_challenge(challenge)
             .add_scopes(scopes.into_iter().map(|s| s.into()))
@@ -136,7 +157,7 @@ impl Oauth2Client {
         verifier: PkceCodeVerifier,
     ) -> Result<Oauth2Token> {
         let res = self
-            .0
+            .client
             .exchange_code(code)
             .set_pkce_verifier(verifier)
             .request_async(oauth2::reqwest::async_http_client)
@@ -146,15 +167,15 @@ impl Oauth2Client {
 
     pub async fn revoke_token(&self, token: StandardRevocableToken) -> Result<()> {
         Ok(self
-            .0
+            .client
             .revoke_token(token)
-            .unwrap()
+            .map_err(|err| Error::custom(err.to_string()))?
             .request_async(oauth2::reqwest::async_http_client)
             .await?)
     }
 
     pub async fn refresh_token(&self, token: &RefreshToken) -> Result<Oauth2Token> {
-        self.0
+        self.client
             .exchange_refresh_token(token)
             .request_async(oauth2::reqwest::async_http_client)
             .await?
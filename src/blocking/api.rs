@@ -0,0 +1,256 @@
+use super::StreamIter;
+use crate::api_result::{ApiPayload, ApiResponse, ApiResult, PaginableApiResponse};
+use crate::authorization::Authorization;
+use crate::data::{
+    Blocking, Bookmarked, ComplianceJob, ComplianceJobKind, Deleted, Following, Hidden, IsMember,
+    Liked, List, Muting, Pinned, Retweeted, Space, StreamRule, Tweet, TweetUsage, TweetsCount,
+    Updated, User,
+};
+use crate::error::Result;
+use crate::id::{ComplianceJobId, ListId, SpaceId, TweetId, UserId};
+use crate::meta::{
+    PaginationMeta, ResultCountMeta, SentMeta, SimpleResultCountMeta, TweetsCountsMeta, TweetsMeta,
+};
+use crate::query::{
+    ComplianceJobKindQuery, GetComplianceJobsRequestBuilder, GetLimitedRelatedTweetsRequestBuilder,
+    GetListsRequestBuilder, GetPaginatedListsRequestBuilder, GetRelatedTweetsRequestBuilder,
+    GetRelatedUsersRequestBuilder, GetSpacesRequestBuilder, GetSpacesSearchRequestBuilder,
+    GetStreamRulesRequestBuilder, GetTimelineRequestBuilder, GetTweetUsersRequestBuilder,
+    GetTweetsCountsRequestBuilder, GetTweetsRequestBuilder, GetTweetsSearchRequestBuilder,
+    GetTweetsStreamRequestBuilder, GetUsageTweetsRequestBuilder, GetUsersRequestBuilder,
+};
+use crate::requests::{ComplianceJobBuilder, ListBuilder, StreamRuleBuilder, TweetBuilder};
+use futures::prelude::*;
+use reqwest::{IntoUrl, Method};
+use serde::de::DeserializeOwned;
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+/// Endpoints returning a request builder. The blocking version returns the same builder
+/// bound to the blocking client.
+macro_rules! blocking_builders {
+    ($(fn $name:ident(&self $(, $arg:ident: $ty:ty)*) -> $builder:ident<A $(, $gen:ty)*>;)*) => {
+        $(
+            pub fn $name(&self $(, $arg: $ty)*) -> $builder<A $(, $gen)*, Self> {
+                self.inner.$name($($arg),*).into_blocking(self)
+            }
+        )*
+    };
+}
+
+/// Endpoints sending a request straight away
+macro_rules! blocking_requests {
+    ($(fn $name:ident(&self $(, $arg:ident: $ty:ty)*) -> $ret:ty;)*) => {
+        $(
+            pub fn $name(&self $(, $arg: $ty)*) -> $ret {
+                self.block_on(self.inner.$name($($arg),*))
+            }
+        )*
+    };
+}
+
+#[derive(Debug)]
+pub struct TwitterApi<A> {
+    inner: crate::TwitterApi<A>,
+    runtime: Arc<Runtime>,
+}
+
+impl<A> TwitterApi<A>
+where
+    A: Authorization,
+{
+    pub fn new(auth: A) -> Self {
+        Self::from(crate::TwitterApi::new(auth))
+    }
+
+    pub fn auth(&self) -> &A {
+        self.inner.auth()
+    }
+
+    /// The async client backing this one
+    pub fn as_async(&self) -> &crate::TwitterApi<A> {
+        &self.inner
+    }
+
+    /// Run a future on the runtime of this client
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    pub fn next_page<T, M>(
+        &self,
+        response: &ApiResponse<A, T, M>,
+    ) -> Result<Option<ApiResponse<A, T, M>>>
+    where
+        A: Send + Sync,
+        T: DeserializeOwned + Send + Sync,
+        M: PaginationMeta + DeserializeOwned + Send + Sync,
+    {
+        self.block_on(response.next_page())
+    }
+
+    pub fn previous_page<T, M>(
+        &self,
+        response: &ApiResponse<A, T, M>,
+    ) -> Result<Option<ApiResponse<A, T, M>>>
+    where
+        A: Send + Sync,
+        T: DeserializeOwned + Send + Sync,
+        M: PaginationMeta + DeserializeOwned + Send + Sync,
+    {
+        self.block_on(response.previous_page())
+    }
+
+    pub(crate) fn request(&self, method: Method, url: impl IntoUrl) -> reqwest::RequestBuilder {
+        self.inner.request(method, url)
+    }
+
+    pub(crate) fn send<T: DeserializeOwned, M: DeserializeOwned>(
+        &self,
+        req: reqwest::RequestBuilder,
+    ) -> ApiResult<A, T, M> {
+        self.block_on(self.inner.send(req))
+    }
+
+    pub(crate) fn stream<T, M>(
+        &self,
+        req: reqwest::RequestBuilder,
+    ) -> Result<StreamIter<ApiPayload<T, M>>>
+    where
+        T: DeserializeOwned + Send + 'static,
+        M: DeserializeOwned + Send + 'static,
+    {
+        let response = self.block_on(self.inner.execute(req.build()?))?;
+        Ok(StreamIter::new(
+            self.runtime.clone(),
+            crate::utils::JsonStream::new(response.bytes_stream()).boxed(),
+        ))
+    }
+
+    pub fn get_tweet_by_url(
+        &self,
+        url: &str,
+    ) -> Result<GetTweetsRequestBuilder<A, Tweet, (), Self>> {
+        Ok(self.inner.get_tweet_by_url(url)?.into_blocking(self))
+    }
+
+    blocking_builders! {
+        fn get_tweets(&self, ids: impl IntoIterator<Item = impl Into<TweetId>>) -> GetTweetsRequestBuilder<A, Vec<Tweet>, ()>;
+        fn get_tweet(&self, id: impl Into<TweetId>) -> GetTweetsRequestBuilder<A, Tweet, ()>;
+        fn post_tweet(&self) -> TweetBuilder<A>;
+        fn get_user_tweets(&self, user_id: impl Into<UserId>) -> GetTimelineRequestBuilder<A, Vec<Tweet>, TweetsMeta>;
+        fn get_user_mentions(&self, user_id: impl Into<UserId>) -> GetTimelineRequestBuilder<A, Vec<Tweet>, TweetsMeta>;
+        fn get_tweets_search_recent(&self, query: impl ToString) -> GetTweetsSearchRequestBuilder<A, Vec<Tweet>, TweetsMeta>;
+        fn get_tweets_search_all(&self, query: impl ToString) -> GetTweetsSearchRequestBuilder<A, Vec<Tweet>, TweetsMeta>;
+        fn get_tweets_counts_recent(&self, query: impl ToString) -> GetTweetsCountsRequestBuilder<A, Vec<TweetsCount>, TweetsCountsMeta>;
+        fn get_tweets_counts_all(&self, query: impl ToString) -> GetTweetsCountsRequestBuilder<A, Vec<TweetsCount>, TweetsCountsMeta>;
+        fn get_tweets_search_stream_rules(&self) -> GetStreamRulesRequestBuilder<A, Vec<StreamRule>, SentMeta>;
+        fn get_tweets_search_stream(&self) -> GetTweetsStreamRequestBuilder<A, Tweet, SentMeta>;
+        fn post_tweets_search_stream_rule(&self) -> StreamRuleBuilder<A>;
+        fn get_tweets_sample_stream(&self) -> GetTweetsStreamRequestBuilder<A, Tweet, SentMeta>;
+        fn get_tweet_retweeted_by(&self, id: impl Into<TweetId>) -> GetTweetUsersRequestBuilder<A, Vec<User>, ResultCountMeta>;
+        fn get_tweet_quote_tweets(&self, id: impl Into<TweetId>) -> GetRelatedTweetsRequestBuilder<A, Vec<Tweet>, ResultCountMeta>;
+        fn get_tweet_liking_users(&self, id: impl Into<TweetId>) -> GetTweetUsersRequestBuilder<A, Vec<User>, ResultCountMeta>;
+        fn get_user_liked_tweets(&self, id: impl Into<UserId>) -> GetRelatedTweetsRequestBuilder<A, Vec<Tweet>, ResultCountMeta>;
+        fn get_user_bookmarks(&self, id: impl Into<UserId>) -> GetRelatedTweetsRequestBuilder<A, Vec<Tweet>, ResultCountMeta>;
+
+        fn get_users(&self, ids: impl IntoIterator<Item = impl Into<UserId>>) -> GetUsersRequestBuilder<A, Vec<User>, ()>;
+        fn get_user(&self, id: impl Into<UserId>) -> GetUsersRequestBuilder<A, User, ()>;
+        fn get_users_by_usernames(&self, usernames: impl IntoIterator<Item = impl ToString>) -> GetUsersRequestBuilder<A, Vec<User>, ()>;
+        fn get_user_by_username(&self, username: impl ToString) -> GetUsersRequestBuilder<A, User, ()>;
+        fn get_users_me(&self) -> GetUsersRequestBuilder<A, User, ()>;
+        fn get_user_followers(&self, id: impl Into<UserId>) -> GetRelatedUsersRequestBuilder<A, Vec<User>, ResultCountMeta>;
+        fn get_user_following(&self, id: impl Into<UserId>) -> GetRelatedUsersRequestBuilder<A, Vec<User>, ResultCountMeta>;
+        fn get_user_blocking(&self, id: impl Into<UserId>) -> GetRelatedUsersRequestBuilder<A, Vec<User>, ResultCountMeta>;
+        fn get_user_muting(&self, id: impl Into<UserId>) -> GetRelatedUsersRequestBuilder<A, Vec<User>, ResultCountMeta>;
+
+        fn get_list(&self, id: impl Into<ListId>) -> GetListsRequestBuilder<A, List, ()>;
+        fn get_user_owned_lists(&self, id: impl Into<UserId>) -> GetPaginatedListsRequestBuilder<A, Vec<List>, ResultCountMeta>;
+        fn post_list(&self, name: impl ToString) -> ListBuilder<A, List>;
+        fn put_list(&self, id: impl Into<ListId>) -> ListBuilder<A, Updated>;
+        fn get_list_tweets(&self, id: impl Into<ListId>) -> GetLimitedRelatedTweetsRequestBuilder<A, Vec<Tweet>, ResultCountMeta>;
+        fn get_user_list_memberships(&self, id: impl Into<UserId>) -> GetPaginatedListsRequestBuilder<A, Vec<List>, ResultCountMeta>;
+        fn get_list_members(&self, id: impl Into<ListId>) -> GetRelatedUsersRequestBuilder<A, Vec<User>, ResultCountMeta>;
+        fn get_list_followers(&self, id: impl Into<ListId>) -> GetRelatedUsersRequestBuilder<A, Vec<User>, ResultCountMeta>;
+        fn get_user_followed_lists(&self, id: impl Into<UserId>) -> GetPaginatedListsRequestBuilder<A, Vec<List>, ResultCountMeta>;
+        fn get_user_pinned_lists(&self, id: impl Into<UserId>) -> GetListsRequestBuilder<A, Vec<List>, ()>;
+
+        fn get_spaces(&self, ids: impl IntoIterator<Item = impl Into<SpaceId>>) -> GetSpacesRequestBuilder<A, Vec<Space>, ()>;
+        fn get_space(&self, id: impl Into<SpaceId>) -> GetSpacesRequestBuilder<A, Space, ()>;
+        fn get_spaces_by_creator_ids(&self, user_ids: impl IntoIterator<Item = impl Into<UserId>>) -> GetSpacesRequestBuilder<A, Vec<Space>, SimpleResultCountMeta>;
+        fn get_space_buyers(&self, id: impl Into<SpaceId>) -> GetRelatedUsersRequestBuilder<A, Vec<User>, ()>;
+        fn get_space_tweets(&self, id: impl Into<SpaceId>) -> GetRelatedTweetsRequestBuilder<A, Vec<Tweet>, ()>;
+        fn get_spaces_search(&self, query: impl ToString) -> GetSpacesSearchRequestBuilder<A, Vec<Space>, SimpleResultCountMeta>;
+
+        fn get_compliance_jobs(&self, kind: impl IntoIterator<Item = ComplianceJobKindQuery>) -> GetComplianceJobsRequestBuilder<A, Vec<ComplianceJob>, ()>;
+        fn post_compliance_job(&self, kind: ComplianceJobKind) -> ComplianceJobBuilder<A>;
+
+        fn get_usage_tweets(&self) -> GetUsageTweetsRequestBuilder<A, TweetUsage, ()>;
+    }
+
+    blocking_requests! {
+        fn delete_tweet(&self, id: impl Into<TweetId>) -> ApiResult<A, Deleted, ()>;
+        fn post_user_retweet(&self, user_id: impl Into<UserId>, tweet_id: impl Into<TweetId>) -> ApiResult<A, Retweeted, ()>;
+        fn delete_user_retweet(&self, user_id: impl Into<UserId>, tweet_id: impl Into<TweetId>) -> ApiResult<A, Retweeted, ()>;
+        fn post_user_like(&self, user_id: impl Into<UserId>, tweet_id: impl Into<TweetId>) -> ApiResult<A, Liked, ()>;
+        fn delete_user_like(&self, user_id: impl Into<UserId>, tweet_id: impl Into<TweetId>) -> ApiResult<A, Liked, ()>;
+        fn post_user_bookmark(&self, user_id: impl Into<UserId>, tweet_id: impl Into<TweetId>) -> ApiResult<A, Bookmarked, ()>;
+        fn delete_user_bookmark(&self, user_id: impl Into<UserId>, tweet_id: impl Into<TweetId>) -> ApiResult<A, Bookmarked, ()>;
+        fn put_tweet_hidden(&self, id: impl Into<TweetId>, hidden: bool) -> ApiResult<A, Hidden, ()>;
+
+        fn post_user_following(&self, id: impl Into<UserId>, target_user_id: impl Into<UserId>) -> ApiResult<A, Following, ()>;
+        fn delete_user_following(&self, source_user_id: impl Into<UserId>, target_user_id: impl Into<UserId>) -> ApiResult<A, Following, ()>;
+        fn post_user_blocking(&self, id: impl Into<UserId>, target_user_id: impl Into<UserId>) -> ApiResult<A, Blocking, ()>;
+        fn delete_user_blocking(&self, source_user_id: impl Into<UserId>, target_user_id: impl Into<UserId>) -> ApiResult<A, Blocking, ()>;
+        fn post_user_muting(&self, id: impl Into<UserId>, target_user_id: impl Into<UserId>) -> ApiResult<A, Muting, ()>;
+        fn delete_user_muting(&self, source_user_id: impl Into<UserId>, target_user_id: impl Into<UserId>) -> ApiResult<A, Muting, ()>;
+
+        fn delete_list(&self, id: impl Into<ListId>) -> ApiResult<A, Deleted, ()>;
+        fn post_list_member(&self, id: impl Into<ListId>, user_id: impl Into<UserId>) -> ApiResult<A, IsMember, ()>;
+        fn delete_list_member(&self, id: impl Into<ListId>, user_id: impl Into<UserId>) -> ApiResult<A, IsMember, ()>;
+        fn post_user_followed_list(&self, id: impl Into<UserId>, list_id: impl Into<ListId>) -> ApiResult<A, Following, ()>;
+        fn delete_user_followed_list(&self, id: impl Into<UserId>, list_id: impl Into<ListId>) -> ApiResult<A, Following, ()>;
+        fn post_user_pinned_list(&self, id: impl Into<UserId>, list_id: impl Into<ListId>) -> ApiResult<A, Pinned, ()>;
+        fn delete_user_pinned_list(&self, id: impl Into<UserId>, list_id: impl Into<ListId>) -> ApiResult<A, Pinned, ()>;
+
+        fn get_compliance_job(&self, id: impl Into<ComplianceJobId>) -> ApiResult<A, ComplianceJob, ()>;
+    }
+}
+
+#[cfg(feature = "oauth2")]
+impl<C, F> TwitterApi<crate::authorization::RefreshableOauth2Token<C>>
+where
+    C: Fn(crate::authorization::Oauth2Token) -> F + Send + Sync,
+    F: Future<Output = Result<()>> + Send,
+{
+    /// Refresh the OAuth2 token now instead of waiting for it to expire
+    pub fn refresh_auth(&self) -> Result<()> {
+        self.block_on(self.auth().refresh())
+    }
+    pub fn revoke_auth(&self) -> Result<()> {
+        self.block_on(self.auth().revoke())
+    }
+}
+
+impl<A> From<crate::TwitterApi<A>> for TwitterApi<A> {
+    fn from(inner: crate::TwitterApi<A>) -> Self {
+        Self {
+            inner,
+            runtime: Arc::new(
+                tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .expect("failed to build tokio runtime"),
+            ),
+        }
+    }
+}
+
+impl<A> Clone for TwitterApi<A> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            runtime: self.runtime.clone(),
+        }
+    }
+}
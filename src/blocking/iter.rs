@@ -0,0 +1,82 @@
+use super::TwitterApi;
+use crate::api_result::ApiResponse;
+use crate::authorization::Authorization;
+use crate::error::Result;
+use crate::meta::PaginationMeta;
+use crate::query::UrlQueryExt;
+use futures::stream::{BoxStream, StreamExt};
+use reqwest::Method;
+use serde::de::DeserializeOwned;
+use std::fmt;
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+use url::Url;
+
+/// Iterator over the pages of a paginated request. Stops after the last page or the
+/// first error.
+#[derive(Debug)]
+pub struct Pages<A, T, M> {
+    client: TwitterApi<A>,
+    next: Option<Url>,
+    return_ty: std::marker::PhantomData<(T, M)>,
+}
+
+impl<A, T, M> Pages<A, T, M> {
+    pub(crate) fn new(client: &TwitterApi<A>, url: Url) -> Self {
+        Self {
+            client: client.clone(),
+            next: Some(url),
+            return_ty: Default::default(),
+        }
+    }
+}
+
+impl<A, T, M> Iterator for Pages<A, T, M>
+where
+    A: Authorization,
+    T: DeserializeOwned,
+    M: PaginationMeta + DeserializeOwned,
+{
+    type Item = Result<ApiResponse<A, T, M>>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let url = self.next.take()?;
+        let response = match self
+            .client
+            .send::<T, M>(self.client.request(Method::GET, url.clone()))
+        {
+            Ok(response) => response,
+            Err(err) => return Some(Err(err)),
+        };
+        if let Some(token) = response.meta().and_then(|meta| meta.next_token()) {
+            let mut url = url;
+            url.replace_query_val("pagination_token", token);
+            self.next = Some(url);
+        }
+        Some(Ok(response))
+    }
+}
+
+/// Blocking iterator over the items of a streaming endpoint.
+pub struct StreamIter<T> {
+    runtime: Arc<Runtime>,
+    stream: BoxStream<'static, Result<T>>,
+}
+
+impl<T> StreamIter<T> {
+    pub(crate) fn new(runtime: Arc<Runtime>, stream: BoxStream<'static, Result<T>>) -> Self {
+        Self { runtime, stream }
+    }
+}
+
+impl<T> Iterator for StreamIter<T> {
+    type Item = Result<T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.runtime.block_on(self.stream.next())
+    }
+}
+
+impl<T> fmt::Debug for StreamIter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StreamIter").finish()
+    }
+}
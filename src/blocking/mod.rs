@@ -0,0 +1,47 @@
+//! A synchronous client, enabled with the `blocking` feature.
+//!
+//! [`TwitterApi`] mirrors the endpoints of the async [`crate::TwitterApi`] and drives the
+//! requests on an internal single threaded tokio runtime. Request builders returned from it
+//! have a blocking `send`, paginated requests can be walked with `pages` and streams are
+//! consumed as iterators.
+//!
+//! ```no_run
+//! use twitter_v2::authorization::BearerToken;
+//! use twitter_v2::blocking::TwitterApi;
+//! use twitter_v2::query::TweetField;
+//!
+//! # fn main() -> twitter_v2::Result<()> {
+//! let api = TwitterApi::new(BearerToken::new(std::env::var("APP_BEARER_TOKEN").unwrap()));
+//! let tweet = api
+//!     .get_tweet(1261326399320715264)
+//!     .tweet_fields([TweetField::AuthorId, TweetField::CreatedAt])
+//!     .send()?
+//!     .into_data();
+//! for page in api.get_user_followers(2244994945).max_results(1000).pages() {
+//!     let followers = page?.into_data();
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The blocking client must not be used from within an async runtime.
+
+mod api;
+mod iter;
+mod with_user_ctx;
+
+pub use api::TwitterApi;
+pub use iter::{Pages, StreamIter};
+pub use with_user_ctx::TwitterApiWithUserCtx;
+
+use std::future::Future;
+
+/// Run a future to completion on a fresh single threaded runtime. Useful for the
+/// async-only parts of the crate such as the OAuth2 token exchange.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("failed to build tokio runtime")
+        .block_on(future)
+}
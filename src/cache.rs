@@ -0,0 +1,429 @@
+//! Caching of API responses.
+//!
+//! A [`ResponseCache`] installed with [`TwitterApi::with_cache`](crate::TwitterApi::with_cache)
+//! answers repeated `GET` requests to lookup endpoints such as `users/:id`, `tweets/:id` or
+//! `lists/:id` from a [`CacheBackend`] until their time to live expires. Other endpoints, such
+//! as timelines, searches or follower lists, are only cached with a
+//! [TTL of their own](ResponseCache::endpoint_ttl).
+//! Entries are keyed by the request URL with normalized query parameters, so requests for
+//! the same resource with the same fields and expansions share an entry regardless of
+//! the order they were given in. Responses for resources which do not exist are cached as
+//! well, with a separate time to live. Requests with other methods and streams are never
+//! cached. A successful write through the client drops the cached responses of the written
+//! resource and its parent, e.g. adding a list member drops the cached members of the list.
+//!
+//! Expired entries carrying an `ETag` or `Last-Modified` header are revalidated with a
+//! conditional request instead of being fetched again.
+//!
+//! The cache key does not include the credentials of the client, so a cache should only be
+//! shared between clients authorized as the same user or app.
+//!
+//! ```
+//! use std::time::Duration;
+//! use twitter_v2::authorization::BearerToken;
+//! use twitter_v2::cache::ResponseCache;
+//! use twitter_v2::TwitterApi;
+//!
+//! let cache = ResponseCache::new()
+//!     .ttl(Duration::from_secs(60))
+//!     .endpoint_ttl("users/:id", Duration::from_secs(60 * 60))
+//!     .endpoint_ttl("users/:id/followers", Duration::from_secs(60));
+//! let api = TwitterApi::new(BearerToken::new("")).with_cache(cache);
+//! ```
+
+use crate::api_result::ApiResponseExt;
+use crate::authorization::Authorization;
+use crate::error::Result;
+use crate::utils::{endpoint_template, matches_endpoint};
+use crate::TwitterApi;
+use async_trait::async_trait;
+use reqwest::header::{HeaderValue, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::{Method, Request, Response, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use time::OffsetDateTime;
+use url::Url;
+
+/// Endpoints cached with the default TTL
+const LOOKUP_ENDPOINTS: &[&str] = &[
+    "lists/:id",
+    "spaces",
+    "spaces/:id",
+    "tweets",
+    "tweets/:id",
+    "users",
+    "users/:id",
+    "users/by",
+    "users/by/username/:username",
+];
+
+const NOT_FOUND_TYPE: &str = "https://api.twitter.com/2/problems/resource-not-found";
+
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct CachedResponse {
+    pub status: u16,
+    pub body: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<String>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub cached_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub expires_at: OffsetDateTime,
+}
+
+impl CachedResponse {
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= OffsetDateTime::now_utc()
+    }
+
+    fn into_response(self) -> Response {
+        let mut response = http::Response::new(self.body);
+        *response.status_mut() =
+            StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        response.into()
+    }
+}
+
+/// Storage of a [`ResponseCache`]. Backends may drop entries at any time.
+#[async_trait]
+pub trait CacheBackend: Send + Sync + 'static {
+    async fn get(&self, key: &str) -> Option<CachedResponse>;
+    async fn put(&self, key: &str, response: CachedResponse);
+    async fn remove(&self, key: &str);
+}
+
+/// An in-memory [`CacheBackend`] evicting the least recently used entry once `capacity`
+/// is reached
+pub struct MemoryCache {
+    capacity: usize,
+    state: Mutex<MemoryCacheState>,
+}
+
+#[derive(Default)]
+struct MemoryCacheState {
+    tick: u64,
+    entries: HashMap<String, (u64, CachedResponse)>,
+    recency: BTreeMap<u64, String>,
+}
+
+impl MemoryCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: Default::default(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&self) {
+        *self.state.lock().unwrap() = Default::default();
+    }
+}
+
+impl MemoryCacheState {
+    fn touch(&mut self, key: &str) -> Option<&CachedResponse> {
+        self.tick += 1;
+        let tick = self.tick;
+        let (used, _) = self.entries.get_mut(key)?;
+        self.recency.remove(used);
+        *used = tick;
+        self.recency.insert(tick, key.to_string());
+        self.entries.get(key).map(|(_, response)| response)
+    }
+}
+
+impl fmt::Debug for MemoryCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemoryCache")
+            .field("capacity", &self.capacity)
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+#[async_trait]
+impl CacheBackend for MemoryCache {
+    async fn get(&self, key: &str) -> Option<CachedResponse> {
+        self.state.lock().unwrap().touch(key).cloned()
+    }
+
+    async fn put(&self, key: &str, response: CachedResponse) {
+        let mut state = self.state.lock().unwrap();
+        if let Some((used, _)) = state.entries.remove(key) {
+            state.recency.remove(&used);
+        }
+        while state.entries.len() >= self.capacity.max(1) {
+            let Some((_, oldest)) = state.recency.pop_first() else {
+                break;
+            };
+            state.entries.remove(&oldest);
+        }
+        state.tick += 1;
+        let tick = state.tick;
+        state.recency.insert(tick, key.to_string());
+        state.entries.insert(key.to_string(), (tick, response));
+    }
+
+    async fn remove(&self, key: &str) {
+        let mut state = self.state.lock().unwrap();
+        if let Some((used, _)) = state.entries.remove(key) {
+            state.recency.remove(&used);
+        }
+    }
+}
+
+pub struct ResponseCache {
+    backend: Arc<dyn CacheBackend>,
+    ttl: Duration,
+    not_found_ttl: Duration,
+    endpoint_ttls: Vec<(String, Duration)>,
+    written: Mutex<HashMap<String, OffsetDateTime>>,
+}
+
+impl ResponseCache {
+    /// A cache of up to 1000 responses in memory
+    pub fn new() -> Self {
+        Self::with_backend(MemoryCache::new(1000))
+    }
+
+    pub fn with_backend(backend: impl CacheBackend) -> Self {
+        Self {
+            backend: Arc::new(backend),
+            ttl: Duration::from_secs(5 * 60),
+            not_found_ttl: Duration::from_secs(60),
+            endpoint_ttls: Vec::new(),
+            written: Default::default(),
+        }
+    }
+
+    /// The time to live of responses of lookup endpoints without a TTL of their own. Defaults
+    /// to 5 minutes.
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// The time to live of responses for resources which do not exist. Defaults to
+    /// 1 minute.
+    pub fn not_found_ttl(mut self, ttl: Duration) -> Self {
+        self.not_found_ttl = ttl;
+        self
+    }
+
+    /// The time to live of responses of `endpoint`, a path relative to the API base URL in
+    /// which segments starting with `:` match any value, e.g. `users/by/username/:username`.
+    /// Other than lookups, endpoints are only cached with a TTL of their own. A TTL of zero
+    /// disables caching of the endpoint.
+    pub fn endpoint_ttl(mut self, endpoint: impl ToString, ttl: Duration) -> Self {
+        self.endpoint_ttls.push((endpoint.to_string(), ttl));
+        self
+    }
+
+    pub fn backend(&self) -> &dyn CacheBackend {
+        &*self.backend
+    }
+
+    /// Drop the cached response for `url`
+    pub async fn invalidate(&self, url: &Url) {
+        self.backend.remove(&cache_key(&Method::GET, url)).await
+    }
+
+    fn ttl_for(&self, url: &Url) -> Duration {
+        let endpoint = endpoint_template(url.path());
+        match self
+            .endpoint_ttls
+            .iter()
+            .rev()
+            .find(|(pattern, _)| matches_endpoint(pattern, &endpoint))
+        {
+            Some((_, ttl)) => *ttl,
+            None if LOOKUP_ENDPOINTS.contains(&endpoint.as_str()) => self.ttl,
+            None => Duration::ZERO,
+        }
+    }
+
+    /// Whether `cached` was stored before a write to `url` or one of its children
+    fn is_outdated(&self, url: &Url, cached: &CachedResponse) -> bool {
+        let written = self.written.lock().unwrap();
+        written
+            .get(url.path())
+            .is_some_and(|at| *at >= cached.cached_at)
+    }
+
+    fn max_ttl(&self) -> Duration {
+        self.endpoint_ttls
+            .iter()
+            .map(|(_, ttl)| *ttl)
+            .chain([self.ttl, self.not_found_ttl])
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Execute a write and record it to outdate the cached reads of the written resource and
+    /// its parent
+    pub(crate) async fn write<A: Authorization>(
+        &self,
+        api: &TwitterApi<A>,
+        req: Request,
+    ) -> Result<Response> {
+        let path = req.url().path().to_string();
+        let response = api.execute(req).await?;
+        let now = OffsetDateTime::now_utc();
+        let mut written = self.written.lock().unwrap();
+        let oldest = now - self.max_ttl();
+        written.retain(|_, at| *at > oldest);
+        let parent = path
+            .rsplit_once('/')
+            .map_or(path.as_str(), |(parent, _)| parent);
+        written.insert(parent.to_string(), now);
+        written.insert(path, now);
+        Ok(response)
+    }
+
+    pub(crate) fn is_cacheable(&self, req: &Request) -> bool {
+        req.method() == Method::GET && !self.ttl_for(req.url()).is_zero()
+    }
+
+    pub(crate) async fn fetch<A: Authorization>(
+        &self,
+        api: &TwitterApi<A>,
+        mut req: Request,
+    ) -> Result<Response> {
+        let key = cache_key(req.method(), req.url());
+        let ttl = self.ttl_for(req.url());
+        let cached = self
+            .backend
+            .get(&key)
+            .await
+            .filter(|cached| !self.is_outdated(req.url(), cached));
+        if let Some(cached) = cached.as_ref() {
+            if !cached.is_expired() {
+                return cached.clone().into_response().api_error_for_status().await;
+            }
+            if let Some(etag) = cached.etag.as_deref().and_then(header_value) {
+                req.headers_mut().insert(IF_NONE_MATCH, etag);
+            }
+            if let Some(modified) = cached.last_modified.as_deref().and_then(header_value) {
+                req.headers_mut().insert(IF_MODIFIED_SINCE, modified);
+            }
+        }
+
+        let response = api.execute_unchecked(req).await?;
+        let status = response.status();
+        if let (StatusCode::NOT_MODIFIED, Some(mut cached)) = (status, cached) {
+            cached.cached_at = OffsetDateTime::now_utc();
+            cached.expires_at = cached.cached_at + ttl;
+            self.backend.put(&key, cached.clone()).await;
+            return cached.into_response().api_error_for_status().await;
+        }
+        if !status.is_success() && status != StatusCode::NOT_FOUND {
+            return response.api_error_for_status().await;
+        }
+
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value: &HeaderValue| value.to_str().ok())
+                .map(|value| value.to_string())
+        };
+        let etag = header(ETAG);
+        let last_modified = header(LAST_MODIFIED);
+        let body = response.text().await?;
+        let ttl = if status == StatusCode::NOT_FOUND || is_not_found(&body) {
+            self.not_found_ttl
+        } else {
+            ttl
+        };
+        let cached = CachedResponse {
+            status: status.as_u16(),
+            body,
+            etag,
+            last_modified,
+            cached_at: OffsetDateTime::now_utc(),
+            expires_at: OffsetDateTime::now_utc() + ttl,
+        };
+        if !ttl.is_zero() {
+            self.backend.put(&key, cached.clone()).await;
+        }
+        cached.into_response().api_error_for_status().await
+    }
+}
+
+impl Default for ResponseCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for ResponseCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResponseCache")
+            .field("ttl", &self.ttl)
+            .field("not_found_ttl", &self.not_found_ttl)
+            .field("endpoint_ttls", &self.endpoint_ttls)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The method and URL with query parameters, and the values of comma separated lists such
+/// as `tweet.fields`, in sorted order
+fn cache_key(method: &Method, url: &Url) -> String {
+    let mut query = url
+        .query_pairs()
+        .map(|(key, value)| {
+            let mut values = value.split(',').collect::<Vec<_>>();
+            values.sort_unstable();
+            (key.into_owned(), values.join(","))
+        })
+        .collect::<Vec<_>>();
+    query.sort();
+    let mut url = url.clone();
+    url.set_query(None);
+    if !query.is_empty() {
+        url.query_pairs_mut().extend_pairs(query);
+    }
+    format!("{method} {url}")
+}
+
+/// Whether a response only reports that the requested resources do not exist
+fn is_not_found(body: &str) -> bool {
+    #[derive(Deserialize)]
+    struct Payload {
+        data: Option<serde::de::IgnoredAny>,
+        #[serde(default)]
+        errors: Vec<ErrorItem>,
+    }
+    #[derive(Deserialize)]
+    struct ErrorItem {
+        #[serde(rename = "type")]
+        kind: Option<String>,
+    }
+    match serde_json::from_str::<Payload>(body) {
+        Ok(payload) => {
+            payload.data.is_none()
+                && !payload.errors.is_empty()
+                && payload
+                    .errors
+                    .iter()
+                    .all(|err| err.kind.as_deref() == Some(NOT_FOUND_TYPE))
+        }
+        Err(_) => false,
+    }
+}
+
+fn header_value(value: &str) -> Option<HeaderValue> {
+    HeaderValue::from_str(value).ok()
+}
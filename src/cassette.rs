@@ -0,0 +1,371 @@
+//! Record and replay HTTP interactions, enabled with the `cassette` feature.
+//!
+//! A [`Cassette`] attached to a [`TwitterApi`](crate::TwitterApi) either records every
+//! request and response going through the client to a JSON file, or answers requests from
+//! a previously recorded file without touching the network. Credentials are never written
+//! to disk: the `Authorization` header and token-like query parameters are redacted, and
+//! no authorization is computed at all while replaying.
+//!
+//! ```no_run
+//! use twitter_v2::authorization::BearerToken;
+//! use twitter_v2::cassette::Cassette;
+//! use twitter_v2::TwitterApi;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> twitter_v2::Result<()> {
+//! let api = TwitterApi::new(BearerToken::new("unused when replaying"))
+//!     .with_cassette(Cassette::auto("./fixtures/cassettes/get_tweet.json")?);
+//! let tweet = api.get_tweet(1261326399320715264).send().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{Error, Result};
+use futures::prelude::*;
+use futures::stream::BoxStream;
+use reqwest::header::HeaderMap;
+use reqwest::{Request, Response};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use url::Url;
+
+const REDACTED: &str = "REDACTED";
+
+const DEFAULT_REDACTED_HEADERS: &[&str] = &["authorization", "cookie", "set-cookie"];
+
+const DEFAULT_REDACTED_QUERY: &[&str] = &[
+    "access_token",
+    "oauth_token",
+    "oauth_signature",
+    "oauth_consumer_key",
+    "token",
+];
+
+// Describe the transferred body, which no longer holds once it is stored as plain text
+const DROPPED_RESPONSE_HEADERS: &[&str] =
+    &["content-encoding", "content-length", "transfer-encoding"];
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum CassetteMode {
+    /// Send requests over the network and append them to the cassette
+    Record,
+    /// Answer requests from the cassette, failing on requests that were not recorded
+    Replay,
+}
+
+/// Which parts of a request must be equal to a recorded one for it to be replayed
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct MatchOn {
+    pub method: bool,
+    pub path: bool,
+    /// Query parameters are compared irrespective of their order
+    pub query: bool,
+    pub body: bool,
+}
+
+impl Default for MatchOn {
+    fn default() -> Self {
+        Self {
+            method: true,
+            path: true,
+            query: true,
+            body: false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub url: Url,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct RecordedResponse {
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    pub body: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct Interaction {
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CassetteFile {
+    interactions: Vec<Interaction>,
+}
+
+#[derive(Debug, Default)]
+struct CassetteState {
+    interactions: Vec<Interaction>,
+    played: Vec<bool>,
+}
+
+#[derive(Debug)]
+pub struct Cassette {
+    path: PathBuf,
+    mode: CassetteMode,
+    match_on: MatchOn,
+    redacted_headers: Vec<String>,
+    redacted_query: Vec<String>,
+    state: Mutex<CassetteState>,
+}
+
+impl Cassette {
+    /// Start an empty cassette that is written to `path` as interactions complete
+    pub fn record(path: impl AsRef<Path>) -> Self {
+        Self::new(path.as_ref(), CassetteMode::Record, Vec::new())
+    }
+
+    /// Load a recorded cassette from `path`
+    pub fn replay(path: impl AsRef<Path>) -> Result<Self> {
+        let file: CassetteFile = serde_json::from_reader(File::open(path.as_ref())?)?;
+        Ok(Self::new(
+            path.as_ref(),
+            CassetteMode::Replay,
+            file.interactions,
+        ))
+    }
+
+    /// Replay `path` if it exists, otherwise record it
+    pub fn auto(path: impl AsRef<Path>) -> Result<Self> {
+        if path.as_ref().exists() {
+            Self::replay(path)
+        } else {
+            Ok(Self::record(path))
+        }
+    }
+
+    fn new(path: &Path, mode: CassetteMode, interactions: Vec<Interaction>) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            mode,
+            match_on: MatchOn::default(),
+            redacted_headers: DEFAULT_REDACTED_HEADERS
+                .iter()
+                .map(|name| name.to_string())
+                .collect(),
+            redacted_query: DEFAULT_REDACTED_QUERY
+                .iter()
+                .map(|name| name.to_string())
+                .collect(),
+            state: Mutex::new(CassetteState {
+                played: vec![false; interactions.len()],
+                interactions,
+            }),
+        }
+    }
+
+    pub fn match_on(mut self, match_on: MatchOn) -> Self {
+        self.match_on = match_on;
+        self
+    }
+
+    /// Also redact the header `name` in recorded requests and responses
+    pub fn redact_header(mut self, name: impl AsRef<str>) -> Self {
+        self.redacted_headers
+            .push(name.as_ref().to_ascii_lowercase());
+        self
+    }
+
+    /// Also redact the query parameter `name` in recorded URLs
+    pub fn redact_query_param(mut self, name: impl ToString) -> Self {
+        self.redacted_query.push(name.to_string());
+        self
+    }
+
+    pub fn mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn interactions(&self) -> Vec<Interaction> {
+        self.state.lock().unwrap().interactions.clone()
+    }
+
+    /// Write the recorded interactions to the cassette file. Happens automatically
+    /// whenever a recorded response body is dropped.
+    pub fn save(&self) -> Result<()> {
+        if self.mode != CassetteMode::Record {
+            return Ok(());
+        }
+        let file = CassetteFile {
+            interactions: self.interactions(),
+        };
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        serde_json::to_writer_pretty(File::create(&self.path)?, &file)?;
+        Ok(())
+    }
+
+    pub(crate) fn is_replaying(&self) -> bool {
+        self.mode == CassetteMode::Replay
+    }
+
+    pub(crate) fn recorded_request(&self, req: &Request) -> RecordedRequest {
+        RecordedRequest {
+            method: req.method().to_string(),
+            url: self.redact_url(req.url()),
+            headers: self.redact_headers(req.headers()),
+            body: req
+                .body()
+                .and_then(|body| body.as_bytes())
+                .map(|bytes| String::from_utf8_lossy(bytes).into_owned()),
+        }
+    }
+
+    pub(crate) fn play(&self, req: &Request) -> Result<Response> {
+        let request = self.recorded_request(req);
+        let mut state = self.state.lock().unwrap();
+        let CassetteState {
+            interactions,
+            played,
+        } = &mut *state;
+        let (index, interaction) = interactions
+            .iter()
+            .enumerate()
+            .find(|(index, interaction)| {
+                !played[*index] && self.matches(&interaction.request, &request)
+            })
+            .ok_or_else(|| Error::CassetteMiss(format!("{} {}", request.method, request.url)))?;
+        played[index] = true;
+        let mut response = http::Response::builder().status(interaction.response.status);
+        for (name, value) in interaction.response.headers.iter() {
+            response = response.header(name, value);
+        }
+        Ok(response
+            .body(interaction.response.body.clone())
+            .map_err(|err| Error::custom(format!("invalid recorded response: {err}")))?
+            .into())
+    }
+
+    pub(crate) fn capture(
+        self: &Arc<Self>,
+        request: RecordedRequest,
+        response: Response,
+    ) -> Response {
+        let status = response.status();
+        let mut headers = response.headers().clone();
+        for name in DROPPED_RESPONSE_HEADERS {
+            headers.remove(*name);
+        }
+        let index = {
+            let mut state = self.state.lock().unwrap();
+            state.interactions.push(Interaction {
+                request,
+                response: RecordedResponse {
+                    status: status.as_u16(),
+                    headers: self.redact_headers(&headers),
+                    body: String::new(),
+                },
+            });
+            state.played.push(true);
+            state.interactions.len() - 1
+        };
+        let body = RecordingBody {
+            cassette: self.clone(),
+            index,
+            stream: response.bytes_stream().boxed(),
+            buffer: Vec::new(),
+        };
+        let mut recorded = http::Response::new(reqwest::Body::wrap_stream(body));
+        *recorded.status_mut() = status;
+        *recorded.headers_mut() = headers;
+        recorded.into()
+    }
+
+    fn matches(&self, recorded: &RecordedRequest, request: &RecordedRequest) -> bool {
+        (!self.match_on.method || recorded.method.eq_ignore_ascii_case(&request.method))
+            && (!self.match_on.path || recorded.url.path() == request.url.path())
+            && (!self.match_on.query || sorted_query(&recorded.url) == sorted_query(&request.url))
+            && (!self.match_on.body || recorded.body == request.body)
+    }
+
+    fn redact_url(&self, url: &Url) -> Url {
+        let mut url = url.clone();
+        if url.query().is_some() {
+            let pairs = url
+                .query_pairs()
+                .map(|(key, value)| {
+                    if self.redacted_query.iter().any(|redacted| *redacted == key) {
+                        (key.into_owned(), REDACTED.to_string())
+                    } else {
+                        (key.into_owned(), value.into_owned())
+                    }
+                })
+                .collect::<Vec<_>>();
+            url.query_pairs_mut().clear().extend_pairs(pairs);
+        }
+        url
+    }
+
+    fn redact_headers(&self, headers: &HeaderMap) -> BTreeMap<String, String> {
+        headers
+            .iter()
+            .map(|(name, value)| {
+                let value = if self
+                    .redacted_headers
+                    .iter()
+                    .any(|redacted| redacted == name.as_str())
+                {
+                    REDACTED.to_string()
+                } else {
+                    String::from_utf8_lossy(value.as_bytes()).into_owned()
+                };
+                (name.to_string(), value)
+            })
+            .collect()
+    }
+}
+
+fn sorted_query(url: &Url) -> Vec<(String, String)> {
+    let mut pairs = url.query_pairs().into_owned().collect::<Vec<_>>();
+    pairs.sort();
+    pairs
+}
+
+/// Passes the response body through, storing it in the recorded interaction once the
+/// body is dropped
+struct RecordingBody {
+    cassette: Arc<Cassette>,
+    index: usize,
+    stream: BoxStream<'static, reqwest::Result<bytes::Bytes>>,
+    buffer: Vec<u8>,
+}
+
+impl Stream for RecordingBody {
+    type Item = reqwest::Result<bytes::Bytes>;
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let poll = self.stream.poll_next_unpin(cx);
+        if let Poll::Ready(Some(Ok(chunk))) = &poll {
+            self.buffer.extend_from_slice(chunk);
+        }
+        poll
+    }
+}
+
+impl Drop for RecordingBody {
+    fn drop(&mut self) {
+        self.cassette.state.lock().unwrap().interactions[self.index]
+            .response
+            .body = String::from_utf8_lossy(&self.buffer).into_owned();
+        let _ = self.cassette.save();
+    }
+}
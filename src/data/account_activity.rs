@@ -0,0 +1,325 @@
+use crate::id::{TweetId, UserId};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+data_struct! {
+    /// A webhook registered with the Account Activity API
+    #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+    pub struct WebhookConfig {
+        pub id: String,
+        pub url: String,
+        /// Whether the last CRC check of the webhook succeeded
+        pub valid: bool,
+        pub created_timestamp: String,
+    }
+}
+
+data_struct! {
+    #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+    pub struct WebhookSubscription {
+        pub user_id: UserId,
+    }
+}
+
+data_struct! {
+    #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+    pub struct WebhookSubscriptions {
+        pub environment: String,
+        pub application_id: String,
+        pub subscriptions: Vec<WebhookSubscription>,
+    }
+}
+
+data_struct! {
+    partial
+    /// A user as delivered by the Account Activity API, in the v1.1 format. Only a few of its
+    /// fields are modeled, so unknown fields are never denied.
+    #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+    pub struct ActivityUser {
+        #[serde(rename = "id_str")]
+        pub id: UserId,
+        pub name: String,
+        pub screen_name: String,
+        #[serde(default)]
+        pub protected: bool,
+    }
+}
+
+data_struct! {
+    partial
+    /// A Tweet as delivered by the Account Activity API, in the v1.1 format. Only a few of its
+    /// fields are modeled, so unknown fields are never denied.
+    #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+    pub struct ActivityTweet {
+        #[serde(rename = "id_str")]
+        pub id: TweetId,
+        pub created_at: String,
+        pub text: String,
+        pub user: ActivityUser,
+        #[serde(default)]
+        pub in_reply_to_status_id_str: Option<TweetId>,
+        #[serde(default)]
+        pub in_reply_to_user_id_str: Option<UserId>,
+        #[serde(default)]
+        pub extended_tweet: Option<ExtendedTweet>,
+    }
+}
+
+impl ActivityTweet {
+    /// The text of the Tweet, which `text` only holds the first 140 characters of
+    pub fn full_text(&self) -> &str {
+        self.extended_tweet
+            .as_ref()
+            .map_or(&self.text, |extended| &extended.full_text)
+    }
+}
+
+data_struct! {
+    partial
+    /// Partially modeled like [`ActivityTweet`]
+    #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+    pub struct ExtendedTweet {
+        pub full_text: String,
+    }
+}
+
+data_struct! {
+    #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+    pub struct FavoriteEvent {
+        pub id: String,
+        pub created_at: String,
+        #[serde(with = "crate::utils::serde::string_u64")]
+        pub timestamp_ms: u64,
+        pub favorited_status: ActivityTweet,
+        pub user: ActivityUser,
+    }
+}
+
+data_struct! {
+    #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+    pub struct TweetDeleteEvent {
+        pub status: DeletedStatus,
+        #[serde(with = "crate::utils::serde::string_u64")]
+        pub timestamp_ms: u64,
+    }
+}
+
+data_struct! {
+    #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+    pub struct DeletedStatus {
+        pub id: TweetId,
+        pub user_id: UserId,
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum RelationshipEventKind {
+    Follow,
+    Unfollow,
+    Block,
+    Unblock,
+    Mute,
+    Unmute,
+}
+
+data_struct! {
+    /// A follow, block or mute, or the undoing of one, by `source` of `target`
+    #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+    pub struct RelationshipEvent {
+        #[serde(rename = "type")]
+        pub kind: RelationshipEventKind,
+        #[serde(with = "crate::utils::serde::string_u64")]
+        pub created_timestamp: u64,
+        pub source: ActivityUser,
+        pub target: ActivityUser,
+    }
+}
+
+/// Only new messages are delivered as direct message events
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum DirectMessageEventKind {
+    MessageCreate,
+}
+
+data_struct! {
+    #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+    pub struct DirectMessageEvent {
+        pub id: String,
+        #[serde(rename = "type")]
+        pub kind: DirectMessageEventKind,
+        #[serde(with = "crate::utils::serde::string_u64")]
+        pub created_timestamp: u64,
+        pub message_create: MessageCreate,
+    }
+}
+
+data_struct! {
+    #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+    pub struct MessageCreate {
+        pub target: MessageTarget,
+        pub sender_id: UserId,
+        pub message_data: MessageData,
+    }
+}
+
+data_struct! {
+    #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+    pub struct MessageTarget {
+        pub recipient_id: UserId,
+    }
+}
+
+data_struct! {
+    partial
+    /// Partially modeled like [`ActivityTweet`]
+    #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+    pub struct MessageData {
+        pub text: String,
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum AccountActivityEvent {
+    TweetCreate(ActivityTweet),
+    TweetDelete(TweetDeleteEvent),
+    Favorite(FavoriteEvent),
+    Follow(RelationshipEvent),
+    Unfollow(RelationshipEvent),
+    Block(RelationshipEvent),
+    Unblock(RelationshipEvent),
+    Mute(RelationshipEvent),
+    Unmute(RelationshipEvent),
+    DirectMessage(DirectMessageEvent),
+    /// An event of a kind without a variant, or which does not fit its model, by the name of
+    /// its list in the payload, e.g. `direct_message_indicate_typing_events`
+    Other {
+        kind: String,
+        event: Value,
+    },
+}
+
+/// A payload delivered to a webhook: the events concerning one subscribed user
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(from = "RawAccountActivity", into = "RawAccountActivity")]
+pub struct AccountActivity {
+    pub for_user_id: UserId,
+    /// Set on Tweets mentioning the user by an account the user has blocked
+    pub user_has_blocked: Option<bool>,
+    pub events: Vec<AccountActivityEvent>,
+    /// The users referenced by direct message events
+    pub users: HashMap<String, ActivityUser>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RawAccountActivity {
+    for_user_id: UserId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    user_has_blocked: Option<bool>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    users: HashMap<String, ActivityUser>,
+    #[serde(flatten)]
+    events: Map<String, Value>,
+}
+
+/// The typed event of the list `kind`, or `None` for lists without a variant and events which
+/// do not fit their model, e.g. of a `type` unknown to this crate
+fn typed_event(kind: &str, event: &Value) -> Option<AccountActivityEvent> {
+    fn parse<T: DeserializeOwned>(event: &Value) -> Option<T> {
+        T::deserialize(event).ok()
+    }
+    Some(match kind {
+        "tweet_create_events" => AccountActivityEvent::TweetCreate(parse(event)?),
+        "tweet_delete_events" => AccountActivityEvent::TweetDelete(parse(event)?),
+        "favorite_events" => AccountActivityEvent::Favorite(parse(event)?),
+        "direct_message_events" => AccountActivityEvent::DirectMessage(parse(event)?),
+        "follow_events" | "block_events" | "mute_events" => {
+            let event: RelationshipEvent = parse(event)?;
+            match event.kind {
+                RelationshipEventKind::Follow => AccountActivityEvent::Follow(event),
+                RelationshipEventKind::Unfollow => AccountActivityEvent::Unfollow(event),
+                RelationshipEventKind::Block => AccountActivityEvent::Block(event),
+                RelationshipEventKind::Unblock => AccountActivityEvent::Unblock(event),
+                RelationshipEventKind::Mute => AccountActivityEvent::Mute(event),
+                RelationshipEventKind::Unmute => AccountActivityEvent::Unmute(event),
+            }
+        }
+        _ => return None,
+    })
+}
+
+impl From<RawAccountActivity> for AccountActivity {
+    fn from(raw: RawAccountActivity) -> Self {
+        let mut events = Vec::new();
+        for (kind, value) in raw.events {
+            let Value::Array(list) = value else {
+                continue;
+            };
+            for event in list {
+                events.push(typed_event(&kind, &event).unwrap_or_else(|| {
+                    AccountActivityEvent::Other {
+                        kind: kind.clone(),
+                        event,
+                    }
+                }));
+            }
+        }
+        Self {
+            for_user_id: raw.for_user_id,
+            user_has_blocked: raw.user_has_blocked,
+            events,
+            users: raw.users,
+        }
+    }
+}
+
+impl From<AccountActivity> for RawAccountActivity {
+    fn from(activity: AccountActivity) -> Self {
+        let mut events = Map::new();
+        for event in activity.events {
+            let (key, event) = match event {
+                AccountActivityEvent::TweetCreate(event) => {
+                    ("tweet_create_events", serde_json::to_value(event))
+                }
+                AccountActivityEvent::TweetDelete(event) => {
+                    ("tweet_delete_events", serde_json::to_value(event))
+                }
+                AccountActivityEvent::Favorite(event) => {
+                    ("favorite_events", serde_json::to_value(event))
+                }
+                AccountActivityEvent::Follow(event) | AccountActivityEvent::Unfollow(event) => {
+                    ("follow_events", serde_json::to_value(event))
+                }
+                AccountActivityEvent::Block(event) | AccountActivityEvent::Unblock(event) => {
+                    ("block_events", serde_json::to_value(event))
+                }
+                AccountActivityEvent::Mute(event) | AccountActivityEvent::Unmute(event) => {
+                    ("mute_events", serde_json::to_value(event))
+                }
+                AccountActivityEvent::DirectMessage(event) => {
+                    ("direct_message_events", serde_json::to_value(event))
+                }
+                AccountActivityEvent::Other { kind, event } => {
+                    let list = events.entry(kind).or_insert_with(|| Value::Array(vec![]));
+                    if let Value::Array(list) = list {
+                        list.push(event);
+                    }
+                    continue;
+                }
+            };
+            let list = events.entry(key).or_insert_with(|| Value::Array(vec![]));
+            if let (Value::Array(list), Ok(event)) = (list, event) {
+                list.push(event);
+            }
+        }
+        Self {
+            for_user_id: activity.for_user_id,
+            user_has_blocked: activity.user_has_blocked,
+            users: activity.users,
+            events,
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::id::NumericId;
+use crate::id::{ComplianceJobId, NumericId};
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 use url::Url;
@@ -6,9 +6,14 @@ use url::Url;
 #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Copy)]
 #[serde(rename_all = "snake_case")]
 pub enum ComplianceJobStatus {
+    Created,
     InProgress,
     Failed,
     Complete,
+    Expired,
+    /// A status not known to this crate
+    #[serde(other)]
+    Unknown,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Copy)]
@@ -18,21 +23,61 @@ pub enum ComplianceJobKind {
     Users,
 }
 
+data_struct! {
+    #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+    pub struct ComplianceJob {
+        pub id: ComplianceJobId,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub name: Option<String>,
+        #[serde(rename = "type")]
+        pub kind: ComplianceJobKind,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub status: Option<ComplianceJobStatus>,
+        #[serde(with = "time::serde::rfc3339")]
+        pub created_at: OffsetDateTime,
+        pub upload_url: Url,
+        #[serde(with = "time::serde::rfc3339")]
+        pub upload_expires_at: OffsetDateTime,
+        pub download_url: Url,
+        #[serde(with = "time::serde::rfc3339")]
+        pub download_expires_at: OffsetDateTime,
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
-pub struct ComplianceJob {
-    pub id: NumericId,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub name: Option<String>,
-    #[serde(rename = "type")]
-    pub kind: ComplianceJobKind,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub status: Option<ComplianceJobStatus>,
-    #[serde(with = "time::serde::rfc3339")]
-    pub created_at: OffsetDateTime,
-    pub upload_url: Url,
-    #[serde(with = "time::serde::rfc3339")]
-    pub upload_expires_at: OffsetDateTime,
-    pub download_url: Url,
-    #[serde(with = "time::serde::rfc3339")]
-    pub download_expires_at: OffsetDateTime,
+#[serde(rename_all = "snake_case")]
+pub enum ComplianceActionKind {
+    Delete,
+    Protect,
+    Unprotect,
+    Suspend,
+    Unsuspend,
+    Deactivate,
+    Reactivate,
+    ScrubGeo,
+    Withheld,
+    #[serde(other)]
+    Unknown,
+}
+
+data_struct! {
+    /// A single line of a compliance job result file. `id` is a tweet or user ID depending
+    /// on the kind of job.
+    #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+    pub struct ComplianceAction {
+        pub id: NumericId,
+        pub action: ComplianceActionKind,
+        #[serde(with = "time::serde::rfc3339")]
+        pub created_at: OffsetDateTime,
+        #[serde(
+            default,
+            with = "time::serde::rfc3339::option",
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub redacted_at: Option<OffsetDateTime>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub reason: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub withheld_in_countries: Option<Vec<String>>,
+    }
 }
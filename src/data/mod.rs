@@ -1,3 +1,35 @@
+/// Declares a data type with an `extra` map of the fields not modeled by this crate, which is
+/// only filled with the `extra-fields` feature. With `deny-unknown-fields` such fields fail
+/// the deserialization instead, except in `partial` models of v1.1 payloads.
+macro_rules! data_struct {
+    (partial $(#[$attr:meta])* pub struct $name:ident { $($field:tt)* }) => {
+        $(#[$attr])*
+        pub struct $name {
+            $($field)*
+            #[cfg_attr(feature = "extra-fields", serde(flatten))]
+            #[cfg_attr(not(feature = "extra-fields"), serde(skip))]
+            pub extra: serde_json::Map<String, serde_json::Value>,
+        }
+    };
+    ($(#[$attr:meta])* pub struct $name:ident { $($field:tt)* }) => {
+        $(#[$attr])*
+        #[cfg_attr(feature = "deny-unknown-fields", serde(deny_unknown_fields))]
+        pub struct $name {
+            $($field)*
+            #[cfg_attr(
+                all(feature = "extra-fields", not(feature = "deny-unknown-fields")),
+                serde(flatten)
+            )]
+            #[cfg_attr(
+                any(not(feature = "extra-fields"), feature = "deny-unknown-fields"),
+                serde(skip)
+            )]
+            pub extra: serde_json::Map<String, serde_json::Value>,
+        }
+    };
+}
+
+mod account_activity;
 mod compliance_job;
 mod entity;
 mod expansions;
@@ -10,10 +42,12 @@ mod space;
 mod stream_rule;
 mod tweet;
 mod tweets_count;
+mod usage;
 mod user;
 mod verb;
 mod withheld;
 
+pub use account_activity::*;
 pub use compliance_job::*;
 pub use entity::*;
 pub use expansions::*;
@@ -26,6 +60,7 @@ pub use space::*;
 pub use stream_rule::*;
 pub use tweet::*;
 pub use tweets_count::*;
+pub use usage::*;
 pub use user::*;
 pub use verb::*;
 pub use withheld::*;
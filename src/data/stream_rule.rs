@@ -1,10 +1,12 @@
-use crate::id::NumericId;
+use crate::id::RuleId;
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize, Clone, Debug, Eq, PartialEq)]
-pub struct StreamRule {
-    pub id: NumericId,
-    pub value: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub tag: Option<String>,
+data_struct! {
+    #[derive(Deserialize, Serialize, Clone, Debug, Eq, PartialEq)]
+    pub struct StreamRule {
+        pub id: RuleId,
+        pub value: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub tag: Option<String>,
+    }
 }
@@ -1,72 +1,92 @@
 use super::entity::FullTextEntities;
 use super::withheld::Withheld;
-use super::GeoCoordinates;
-use crate::id::{NumericId, StringId};
+use super::{Expansions, GeoCoordinates};
+use crate::id::{MediaKey, NumericId, StringId, TweetId, UserId};
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
-#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
-pub struct TweetPublicMetrics {
-    pub retweet_count: usize,
-    pub reply_count: usize,
-    pub like_count: usize,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub quote_count: Option<usize>,
+data_struct! {
+    #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+    pub struct TweetPublicMetrics {
+        pub retweet_count: usize,
+        pub reply_count: usize,
+        pub like_count: usize,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub quote_count: Option<usize>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub bookmark_count: Option<usize>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub impression_count: Option<usize>,
+    }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
-pub struct TweetNonPublicMetrics {
-    pub impression_count: Option<usize>,
+data_struct! {
+    #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+    pub struct TweetNonPublicMetrics {
+        pub impression_count: Option<usize>,
+    }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
-pub struct TweetPromotedMetrics {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub impression_count: Option<usize>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub retweet_count: Option<usize>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub reply_count: Option<usize>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub like_count: Option<usize>,
+data_struct! {
+    #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+    pub struct TweetPromotedMetrics {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub impression_count: Option<usize>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub retweet_count: Option<usize>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub reply_count: Option<usize>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub like_count: Option<usize>,
+    }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
-pub struct TweetOrganicMetrics {
-    pub impression_count: usize,
-    pub retweet_count: usize,
-    pub reply_count: usize,
-    pub like_count: usize,
+data_struct! {
+    #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+    pub struct TweetOrganicMetrics {
+        pub impression_count: usize,
+        pub retweet_count: usize,
+        pub reply_count: usize,
+        pub like_count: usize,
+    }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
-pub struct Attachments {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub media_keys: Option<Vec<StringId>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub poll_ids: Option<Vec<NumericId>>,
+data_struct! {
+    #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+    pub struct Attachments {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub media_keys: Option<Vec<MediaKey>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub poll_ids: Option<Vec<NumericId>>,
+    }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
-pub struct ContextAnnotationDescription {
-    pub id: NumericId,
-    pub name: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub description: Option<String>,
+data_struct! {
+    #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+    pub struct ContextAnnotationDescription {
+        pub id: NumericId,
+        pub name: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub description: Option<String>,
+    }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
-pub struct ContextAnnotation {
-    pub domain: ContextAnnotationDescription,
-    pub entity: ContextAnnotationDescription,
+data_struct! {
+    #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+    pub struct ContextAnnotation {
+        pub domain: ContextAnnotationDescription,
+        pub entity: ContextAnnotationDescription,
+    }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
-#[cfg_attr(feature = "arbitrary_precision", derive(Eq))]
-pub struct TweetGeo {
-    pub place_id: Option<StringId>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub coordinates: Option<GeoCoordinates>,
+data_struct! {
+    #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+    #[cfg_attr(feature = "arbitrary_precision", derive(Eq))]
+    pub struct TweetGeo {
+        pub place_id: Option<StringId>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub coordinates: Option<GeoCoordinates>,
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
@@ -83,11 +103,13 @@ pub enum ReferencedTweetKind {
     Retweeted,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
-pub struct ReferencedTweet {
-    #[serde(rename = "type")]
-    pub kind: ReferencedTweetKind,
-    pub id: NumericId,
+data_struct! {
+    #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+    pub struct ReferencedTweet {
+        #[serde(rename = "type")]
+        pub kind: ReferencedTweetKind,
+        pub id: TweetId,
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
@@ -98,49 +120,149 @@ pub enum ReplySettings {
     Followers,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
-#[cfg_attr(feature = "arbitrary_precision", derive(Eq))]
-pub struct Tweet {
-    pub id: NumericId,
-    pub text: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub attachments: Option<Attachments>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub author_id: Option<NumericId>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub context_annotations: Option<Vec<ContextAnnotation>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub conversation_id: Option<NumericId>,
-    #[serde(
-        default,
-        with = "time::serde::rfc3339::option",
-        skip_serializing_if = "Option::is_none"
-    )]
-    pub created_at: Option<OffsetDateTime>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub entities: Option<FullTextEntities>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub geo: Option<TweetGeo>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub in_reply_to_user_id: Option<NumericId>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub lang: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub non_public_metrics: Option<TweetNonPublicMetrics>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub organic_metrics: Option<TweetOrganicMetrics>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub possibly_sensitive: Option<bool>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub promoted_metrics: Option<TweetPromotedMetrics>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub public_metrics: Option<TweetPublicMetrics>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub referenced_tweets: Option<Vec<ReferencedTweet>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub reply_settings: Option<ReplySettings>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub source: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub withheld: Option<Withheld>,
+data_struct! {
+    #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+    pub struct EditControls {
+        pub edits_remaining: usize,
+        pub is_edit_eligible: bool,
+        #[serde(with = "time::serde::rfc3339")]
+        pub editable_until: OffsetDateTime,
+    }
+}
+
+data_struct! {
+    #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+    #[cfg_attr(feature = "arbitrary_precision", derive(Eq))]
+    pub struct NoteTweet {
+        pub text: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub entities: Option<FullTextEntities>,
+    }
+}
+
+data_struct! {
+    #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+    #[cfg_attr(feature = "arbitrary_precision", derive(Eq))]
+    pub struct Tweet {
+        pub id: TweetId,
+        pub text: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub attachments: Option<Attachments>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub author_id: Option<UserId>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub context_annotations: Option<Vec<ContextAnnotation>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub conversation_id: Option<TweetId>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub edit_controls: Option<EditControls>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub edit_history_tweet_ids: Option<Vec<TweetId>>,
+        #[serde(
+            default,
+            with = "time::serde::rfc3339::option",
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub created_at: Option<OffsetDateTime>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub entities: Option<FullTextEntities>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub geo: Option<TweetGeo>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub in_reply_to_user_id: Option<UserId>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub lang: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub non_public_metrics: Option<TweetNonPublicMetrics>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub note_tweet: Option<NoteTweet>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub organic_metrics: Option<TweetOrganicMetrics>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub possibly_sensitive: Option<bool>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub promoted_metrics: Option<TweetPromotedMetrics>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub public_metrics: Option<TweetPublicMetrics>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub referenced_tweets: Option<Vec<ReferencedTweet>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub reply_settings: Option<ReplySettings>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub source: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub withheld: Option<Withheld>,
+    }
+}
+
+impl Tweet {
+    /// The creation time of the tweet, either from `created_at` if it was requested or
+    /// decoded from the tweet ID.
+    pub fn timestamp(&self) -> Option<OffsetDateTime> {
+        self.created_at.or_else(|| self.id.timestamp())
+    }
+    /// The untruncated text of the tweet. Long tweets have their `text` truncated with the
+    /// full text in `note_tweet`, which requires `TweetField::NoteTweet`.
+    pub fn full_text(&self) -> &str {
+        self.note_tweet
+            .as_ref()
+            .map(|note_tweet| note_tweet.text.as_str())
+            .unwrap_or(&self.text)
+    }
+    /// The entities matching [`Tweet::full_text`].
+    pub fn full_text_entities(&self) -> Option<&FullTextEntities> {
+        match self.note_tweet.as_ref() {
+            Some(note_tweet) => note_tweet.entities.as_ref(),
+            None => self.entities.as_ref(),
+        }
+    }
+    /// The ID of the first version of this tweet. Requires `TweetField::EditHistoryTweetIds`.
+    pub fn original_tweet_id(&self) -> Option<TweetId> {
+        self.edit_history_tweet_ids.as_ref()?.first().copied()
+    }
+    /// Whether this is the most recent version of the tweet. Requires
+    /// `TweetField::EditHistoryTweetIds`.
+    pub fn is_latest_version(&self) -> Option<bool> {
+        Some(self.edit_history_tweet_ids.as_ref()?.last() == Some(&self.id))
+    }
+    pub fn is_edited(&self) -> Option<bool> {
+        Some(self.edit_history_tweet_ids.as_ref()?.len() > 1)
+    }
+    /// Requires `TweetField::EditControls`.
+    pub fn edits_remaining(&self) -> Option<usize> {
+        Some(self.edit_controls.as_ref()?.edits_remaining)
+    }
+    /// Requires `TweetField::EditControls`.
+    pub fn edit_window_ends_at(&self) -> Option<OffsetDateTime> {
+        Some(self.edit_controls.as_ref()?.editable_until)
+    }
+    /// Whether the tweet can currently be edited. Requires `TweetField::EditControls`.
+    pub fn is_editable(&self) -> Option<bool> {
+        let controls = self.edit_controls.as_ref()?;
+        Some(
+            controls.is_edit_eligible
+                && controls.edits_remaining > 0
+                && controls.editable_until > OffsetDateTime::now_utc(),
+        )
+    }
+    /// All known versions of this tweet, oldest first. Previous versions are looked up in
+    /// `includes`, which are populated when requesting `TweetExpansion::EditHistoryTweetIds`.
+    /// Versions which are not available are skipped.
+    pub fn versions<'a>(&'a self, includes: Option<&'a Expansions>) -> Vec<&'a Tweet> {
+        let history = match self.edit_history_tweet_ids.as_ref() {
+            Some(history) => history,
+            None => return vec![self],
+        };
+        let included = includes.and_then(|includes| includes.tweets.as_deref());
+        history
+            .iter()
+            .filter_map(|id| {
+                if *id == self.id {
+                    Some(self)
+                } else {
+                    included?.iter().find(|tweet| tweet.id == *id)
+                }
+            })
+            .collect()
+    }
 }
@@ -1,11 +1,151 @@
+use crate::error::{Error, Result};
+use crate::query::Granularity;
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
-#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
-pub struct TweetsCount {
-    #[serde(with = "time::serde::rfc3339")]
+data_struct! {
+    #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+    pub struct TweetsCount {
+        #[serde(with = "time::serde::rfc3339")]
+        pub start: OffsetDateTime,
+        #[serde(with = "time::serde::rfc3339")]
+        pub end: OffsetDateTime,
+        pub tweet_count: usize,
+    }
+}
+
+impl TweetsCount {
+    fn new(start: OffsetDateTime, end: OffsetDateTime, tweet_count: usize) -> Self {
+        Self {
+            start,
+            end,
+            tweet_count,
+            extra: Default::default(),
+        }
+    }
+}
+
+/// Tweet counts from `start` to `end` in consecutive buckets of one granularity. The first
+/// and last bucket are cut short if the range does not start or end on a bucket boundary.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TweetsCountSeries {
+    pub granularity: Granularity,
     pub start: OffsetDateTime,
-    #[serde(with = "time::serde::rfc3339")]
     pub end: OffsetDateTime,
-    pub tweet_count: usize,
+    pub buckets: Vec<TweetsCount>,
+}
+
+impl TweetsCountSeries {
+    /// Sum `counts` into the buckets of `granularity` they start in, with zero buckets
+    /// where no counts fall. Counts outside of the range are left out.
+    pub fn new(
+        granularity: Granularity,
+        start: OffsetDateTime,
+        end: OffsetDateTime,
+        counts: impl IntoIterator<Item = TweetsCount>,
+    ) -> Self {
+        let mut buckets = Vec::new();
+        let mut bucket_start = start;
+        while bucket_start < end {
+            let bucket_end = (granularity.truncate(bucket_start) + granularity.duration()).min(end);
+            buckets.push(TweetsCount::new(bucket_start, bucket_end, 0));
+            bucket_start = bucket_end;
+        }
+        for count in counts {
+            if count.start < start || count.start >= end {
+                continue;
+            }
+            let index = buckets.partition_point(|bucket| bucket.end <= count.start);
+            buckets[index].tweet_count += count.tweet_count;
+        }
+        Self {
+            granularity,
+            start,
+            end,
+            buckets,
+        }
+    }
+
+    pub fn total(&self) -> usize {
+        self.buckets.iter().map(|bucket| bucket.tweet_count).sum()
+    }
+
+    /// The count of the bucket containing `time`
+    pub fn get(&self, time: OffsetDateTime) -> Option<usize> {
+        self.buckets
+            .iter()
+            .find(|bucket| bucket.start <= time && time < bucket.end)
+            .map(|bucket| bucket.tweet_count)
+    }
+
+    pub fn peak(&self) -> Option<&TweetsCount> {
+        self.buckets.iter().max_by_key(|bucket| bucket.tweet_count)
+    }
+
+    /// Sum the buckets into the coarser `granularity`. Fails if `granularity` is finer than
+    /// the one of the series.
+    pub fn rebucket(&self, granularity: Granularity) -> Result<Self> {
+        if granularity.duration() < self.granularity.duration() {
+            return Err(Error::custom(format!(
+                "cannot split {} buckets into {} buckets",
+                self.granularity, granularity
+            )));
+        }
+        Ok(Self::new(
+            granularity,
+            self.start,
+            self.end,
+            self.buckets.iter().cloned(),
+        ))
+    }
+}
+
+/// Several series side by side, e.g. the counts of competing queries, over the union of
+/// their ranges in the coarsest of their granularities
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TweetsCountComparison {
+    pub labels: Vec<String>,
+    pub series: Vec<TweetsCountSeries>,
+}
+
+impl TweetsCountComparison {
+    pub fn new(series: impl IntoIterator<Item = (impl ToString, TweetsCountSeries)>) -> Self {
+        let (labels, series): (Vec<_>, Vec<_>) = series
+            .into_iter()
+            .map(|(label, series)| (label.to_string(), series))
+            .unzip();
+        let granularity = series
+            .iter()
+            .map(|series| series.granularity)
+            .max_by_key(|granularity| granularity.duration());
+        let start = series.iter().map(|series| series.start).min();
+        let end = series.iter().map(|series| series.end).max();
+        let series = match (granularity, start, end) {
+            (Some(granularity), Some(start), Some(end)) => series
+                .into_iter()
+                .map(|series| TweetsCountSeries::new(granularity, start, end, series.buckets))
+                .collect(),
+            _ => Vec::new(),
+        };
+        Self { labels, series }
+    }
+
+    /// The start of each bucket and the counts of every series in it, in the order of
+    /// [`labels`](Self::labels)
+    pub fn rows(&self) -> impl Iterator<Item = (OffsetDateTime, Vec<usize>)> + '_ {
+        let buckets = self.series.first().map_or(0, |series| series.buckets.len());
+        (0..buckets).map(move |index| {
+            (
+                self.series[0].buckets[index].start,
+                self.series
+                    .iter()
+                    .map(|series| series.buckets[index].tweet_count)
+                    .collect(),
+            )
+        })
+    }
+
+    pub fn totals(&self) -> Vec<usize> {
+        self.series.iter().map(TweetsCountSeries::total).collect()
+    }
 }
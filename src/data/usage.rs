@@ -0,0 +1,62 @@
+use crate::id::NumericId;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+data_struct! {
+    #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+    pub struct DailyUsage {
+        #[serde(with = "time::serde::rfc3339")]
+        pub date: OffsetDateTime,
+        #[serde(with = "crate::utils::serde::string_u64")]
+        pub usage: u64,
+    }
+}
+
+data_struct! {
+    #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+    pub struct DailyProjectUsage {
+        pub project_id: NumericId,
+        pub usage: Vec<DailyUsage>,
+    }
+}
+
+data_struct! {
+    #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+    pub struct ClientAppUsage {
+        pub client_app_id: NumericId,
+        pub usage: Vec<DailyUsage>,
+        pub usage_result_count: usize,
+    }
+}
+
+data_struct! {
+    /// The Tweets consumed by a project against its monthly cap
+    #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+    pub struct TweetUsage {
+        /// The day of the month on which usage is reset
+        pub cap_reset_day: u8,
+        pub project_id: NumericId,
+        #[serde(with = "crate::utils::serde::string_u64")]
+        pub project_cap: u64,
+        #[serde(with = "crate::utils::serde::string_u64")]
+        pub project_usage: u64,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub daily_project_usage: Option<DailyProjectUsage>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub daily_client_app_usage: Option<Vec<ClientAppUsage>>,
+    }
+}
+
+impl TweetUsage {
+    pub fn remaining(&self) -> u64 {
+        self.project_cap.saturating_sub(self.project_usage)
+    }
+
+    /// The share of the cap used so far, from 0 to 1 and above once exceeded
+    pub fn fraction_used(&self) -> f64 {
+        if self.project_cap == 0 {
+            return 1.;
+        }
+        self.project_usage as f64 / self.project_cap as f64
+    }
+}
@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 macro_rules! verb {
     ($vis:vis struct $class:ident { $verb:ident }) => {
         #[derive(Deserialize, Serialize, Clone, Copy, Debug, Eq, PartialEq)]
+        #[cfg_attr(feature = "deny-unknown-fields", serde(deny_unknown_fields))]
         $vis struct $class {
             pub $verb: bool
         }
@@ -33,8 +34,40 @@ verb! { pub struct Retweeted { retweeted } }
 verb! { pub struct Liked { liked } }
 verb! { pub struct Bookmarked { bookmarked } }
 verb! { pub struct Hidden { hidden } }
-verb! { pub struct Following { following } }
 verb! { pub struct Blocking { blocking } }
 verb! { pub struct Muting { muting } }
 verb! { pub struct IsMember { is_member } }
 verb! { pub struct Pinned { pinned } }
+
+data_struct! {
+    /// `pending_follow` is set instead of `following` when following a protected account, until
+    /// the account accepts the request
+    #[derive(Deserialize, Serialize, Clone, Debug, Eq, PartialEq)]
+    pub struct Following {
+        pub following: bool,
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        pub pending_follow: bool,
+    }
+}
+
+impl Following {
+    pub fn as_bool(&self) -> bool {
+        self.following
+    }
+}
+
+impl From<Following> for bool {
+    fn from(following: Following) -> bool {
+        following.following
+    }
+}
+
+impl From<bool> for Following {
+    fn from(following: bool) -> Following {
+        Following {
+            following,
+            pending_follow: false,
+            extra: Default::default(),
+        }
+    }
+}
@@ -12,6 +12,18 @@ pub enum Error {
     Url(#[from] url::ParseError),
     #[error(transparent)]
     Json(#[from] serde_json::Error),
+    #[error("Compliance job {_0} failed")]
+    ComplianceJobFailed(crate::id::ComplianceJobId),
+    #[error("Compliance job {_0} expired")]
+    ComplianceJobExpired(crate::id::ComplianceJobId),
+    #[error("Compliance job {_0} has an unknown status")]
+    ComplianceJobUnknownStatus(crate::id::ComplianceJobId),
+    #[error("Timed out waiting for compliance job {_0}")]
+    ComplianceJobTimeout(crate::id::ComplianceJobId),
+    #[error("Not a recognized Twitter URL: {_0}")]
+    UnrecognizedUrl(String),
+    #[error("Tweet cap nearly reached: {usage} of {cap} Tweets used this month")]
+    QuotaExceeded { usage: u64, cap: u64 },
     #[error("Invalid Authorization header value: {_0}")]
     InvalidAuthorizationHeader(InvalidHeaderValue),
     #[cfg(feature = "oauth2")]
@@ -35,6 +47,17 @@ pub enum Error {
     #[cfg(feature = "oauth2")]
     #[error("No refresh token found. Try using the `offline.access` scope")]
     NoRefreshToken,
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[cfg(feature = "cassette")]
+    #[error("No recorded interaction matches {_0}")]
+    CassetteMiss(String),
+    #[cfg(feature = "webhook")]
+    #[error("Invalid webhook signature")]
+    InvalidWebhookSignature,
+    #[cfg(feature = "tower")]
+    #[error("Middleware error: {_0}")]
+    Middleware(crate::middleware::BoxError),
     #[error("Other: {_0}")]
     Custom(String),
 }
@@ -1,5 +1,7 @@
 mod numeric;
 mod string;
+mod typed;
 
 pub use numeric::*;
 pub use string::*;
+pub use typed::*;
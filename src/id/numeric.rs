@@ -3,6 +3,19 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::num::ParseIntError;
 use std::str::FromStr;
+use time::OffsetDateTime;
+
+/// Milliseconds since the Unix epoch at which Twitter snowflake timestamps start
+/// (2010-11-04T01:42:54.657Z).
+pub const SNOWFLAKE_EPOCH_MS: i128 = 1288834974657;
+
+/// The first snowflake ID issued. IDs below this are sequential and carry no timestamp.
+const FIRST_SNOWFLAKE: u64 = 29700859247;
+
+const TIMESTAMP_SHIFT: u32 = 22;
+const WORKER_SHIFT: u32 = 12;
+const WORKER_MASK: u64 = 0x3ff;
+const SEQUENCE_MASK: u64 = 0xfff;
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct NumericId(u64);
@@ -14,6 +27,43 @@ impl NumericId {
     pub fn as_u64(self) -> u64 {
         self.0
     }
+    /// The smallest snowflake ID that could have been generated at `time`. Useful as a
+    /// `since_id` or `until_id` bound.
+    pub fn from_time(time: OffsetDateTime) -> Self {
+        Self(Self::timestamp_bits(time) << TIMESTAMP_SHIFT)
+    }
+    /// The largest snowflake ID that could have been generated at `time`.
+    pub fn from_time_upper(time: OffsetDateTime) -> Self {
+        Self(
+            Self::timestamp_bits(time) << TIMESTAMP_SHIFT
+                | (WORKER_MASK << WORKER_SHIFT)
+                | SEQUENCE_MASK,
+        )
+    }
+    fn timestamp_bits(time: OffsetDateTime) -> u64 {
+        let ms = time.unix_timestamp_nanos() / 1_000_000 - SNOWFLAKE_EPOCH_MS;
+        ms.clamp(0, (u64::MAX >> TIMESTAMP_SHIFT) as i128) as u64
+    }
+    pub fn is_snowflake(self) -> bool {
+        self.0 >= FIRST_SNOWFLAKE
+    }
+    /// The time at which this ID was generated, with millisecond precision. Returns `None`
+    /// for pre-snowflake IDs.
+    pub fn timestamp(self) -> Option<OffsetDateTime> {
+        if !self.is_snowflake() {
+            return None;
+        }
+        let ms = (self.0 >> TIMESTAMP_SHIFT) as i128 + SNOWFLAKE_EPOCH_MS;
+        OffsetDateTime::from_unix_timestamp_nanos(ms * 1_000_000).ok()
+    }
+    pub fn worker(self) -> Option<u16> {
+        self.is_snowflake()
+            .then_some(((self.0 >> WORKER_SHIFT) & WORKER_MASK) as u16)
+    }
+    pub fn sequence(self) -> Option<u16> {
+        self.is_snowflake()
+            .then_some((self.0 & SEQUENCE_MASK) as u16)
+    }
 }
 
 impl From<NumericId> for u64 {
@@ -123,7 +173,7 @@ impl IntoNumericId for u64 {
     }
 }
 
-impl<'a> IntoNumericId for &'a u64 {
+impl IntoNumericId for &u64 {
     fn into_id(self) -> NumericId {
         NumericId(*self)
     }
@@ -105,13 +105,13 @@ impl IntoStringId for String {
     }
 }
 
-impl<'a> IntoStringId for &'a String {
+impl IntoStringId for &String {
     fn into_id(self) -> StringId {
         StringId(self.to_string())
     }
 }
 
-impl<'a> IntoStringId for &'a str {
+impl IntoStringId for &str {
     fn into_id(self) -> StringId {
         StringId(self.to_string())
     }
@@ -0,0 +1,217 @@
+use super::{NumericId, StringId};
+use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
+use std::fmt;
+use std::num::ParseIntError;
+use std::ops::Deref;
+use std::str::FromStr;
+
+macro_rules! numeric_id {
+    ($(#[$attr:meta])* $vis:vis struct $class:ident;) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+        #[serde(transparent)]
+        $vis struct $class(NumericId);
+
+        impl $class {
+            pub fn new(id: u64) -> Self {
+                Self(NumericId::new(id))
+            }
+            pub fn as_u64(self) -> u64 {
+                self.0.as_u64()
+            }
+            pub fn as_numeric_id(self) -> NumericId {
+                self.0
+            }
+        }
+
+        impl Deref for $class {
+            type Target = NumericId;
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl From<$class> for NumericId {
+            fn from(id: $class) -> Self {
+                id.0
+            }
+        }
+
+        impl From<$class> for u64 {
+            fn from(id: $class) -> Self {
+                id.0.as_u64()
+            }
+        }
+
+        impl From<NumericId> for $class {
+            fn from(id: NumericId) -> Self {
+                Self(id)
+            }
+        }
+
+        impl From<u64> for $class {
+            fn from(id: u64) -> Self {
+                Self(NumericId::new(id))
+            }
+        }
+
+        impl<'a> From<&'a u64> for $class {
+            fn from(id: &'a u64) -> Self {
+                Self(NumericId::new(*id))
+            }
+        }
+
+        impl<'a> From<&'a $class> for $class {
+            fn from(id: &'a $class) -> Self {
+                *id
+            }
+        }
+
+        impl FromStr for $class {
+            type Err = ParseIntError;
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Ok(Self(s.parse()?))
+            }
+        }
+
+        impl fmt::Display for $class {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.0, f)
+            }
+        }
+
+        impl PartialEq<u64> for $class {
+            fn eq(&self, other: &u64) -> bool {
+                self.0 == *other
+            }
+        }
+
+        impl PartialEq<&u64> for $class {
+            fn eq(&self, other: &&u64) -> bool {
+                self.0 == **other
+            }
+        }
+    };
+}
+
+macro_rules! string_id {
+    ($(#[$attr:meta])* $vis:vis struct $class:ident;) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+        #[serde(transparent)]
+        $vis struct $class(StringId);
+
+        impl $class {
+            pub fn new(id: impl ToString) -> Self {
+                Self(StringId::new(id.to_string()))
+            }
+            pub fn as_str(&self) -> &str {
+                self.0.as_str()
+            }
+            pub fn into_string(self) -> String {
+                self.0.into_string()
+            }
+        }
+
+        impl Deref for $class {
+            type Target = StringId;
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl From<$class> for StringId {
+            fn from(id: $class) -> Self {
+                id.0
+            }
+        }
+
+        impl From<$class> for String {
+            fn from(id: $class) -> Self {
+                id.0.into_string()
+            }
+        }
+
+        impl From<StringId> for $class {
+            fn from(id: StringId) -> Self {
+                Self(id)
+            }
+        }
+
+        impl From<String> for $class {
+            fn from(id: String) -> Self {
+                Self(StringId::new(id))
+            }
+        }
+
+        impl<'a> From<&'a String> for $class {
+            fn from(id: &'a String) -> Self {
+                Self(StringId::new(id.clone()))
+            }
+        }
+
+        impl<'a> From<&'a str> for $class {
+            fn from(id: &'a str) -> Self {
+                Self(StringId::new(id.to_string()))
+            }
+        }
+
+        impl<'a> From<&'a $class> for $class {
+            fn from(id: &'a $class) -> Self {
+                id.clone()
+            }
+        }
+
+        impl FromStr for $class {
+            type Err = Infallible;
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Ok(Self(s.parse()?))
+            }
+        }
+
+        impl fmt::Display for $class {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.0, f)
+            }
+        }
+
+        impl PartialEq<String> for $class {
+            fn eq(&self, other: &String) -> bool {
+                self.0 == *other
+            }
+        }
+
+        impl PartialEq<str> for $class {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == *other
+            }
+        }
+
+        impl PartialEq<&str> for $class {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+    };
+}
+
+numeric_id! {
+    /// The ID of a tweet. Distinct from the other ID types so that arguments cannot be
+    /// swapped by accident:
+    ///
+    /// ```compile_fail
+    /// # use twitter_v2::{TwitterApi, authorization::BearerToken};
+    /// # use twitter_v2::id::{TweetId, UserId};
+    /// # async fn like(api: TwitterApi<BearerToken>, tweet_id: TweetId, user_id: UserId) {
+    /// api.post_user_like(tweet_id, user_id).await;
+    /// # }
+    /// ```
+    pub struct TweetId;
+}
+numeric_id! { pub struct UserId; }
+numeric_id! { pub struct ListId; }
+numeric_id! { pub struct RuleId; }
+numeric_id! { pub struct ComplianceJobId; }
+string_id! { pub struct SpaceId; }
+string_id! { pub struct MediaKey; }
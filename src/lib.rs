@@ -10,6 +10,20 @@
 //! * **oauth2**: Included by default. See the examples for how to use.
 //! * **native-tls**: Use `native-tls` as TLS backend (default)
 //! * **rustls-tls**: Use `rustls` as TLS backend
+//! * **extra-fields**: Keep fields unknown to this crate in the `extra` map of every data type,
+//!   which is left empty otherwise
+//! * **deny-unknown-fields**: Fail deserialization of data types on unknown fields. Useful
+//!   for contract tests, takes precedence over `extra-fields`
+//! * **blocking**: A synchronous client in the `blocking` module
+//! * **cassette**: Record and replay HTTP interactions for offline tests, see the `cassette`
+//!   module
+//! * **testing**: An in-process mock of the API for tests, see the `testing` module
+//! * **tower**: Compose tower middleware around the HTTP client, see the `middleware`
+//!   module
+//! * **tracing**: Emit a `tracing` span per API call and events for streams, see the
+//!   `metrics` module
+//! * **webhook**: Receive Account Activity API events, see the `webhook` module, and manage
+//!   webhooks and subscriptions
 //!
 //! # Example
 //!
@@ -47,6 +61,10 @@
 //! # Ok(())
 //! # }
 //! ```
+//!
+//! Requested fields are `Option`s on the data types. To have them checked once when the
+//! response is parsed instead, declare a projection with [`select!`], see the [`selection`]
+//! module.
 
 #[cfg(not(any(feature = "rustls-tls", feature = "native-tls")))]
 compile_error!("Either `rustls-tls` or `native-tls` feature must be selected");
@@ -57,13 +75,30 @@ pub extern crate oauth2;
 pub mod api;
 pub mod api_result;
 pub mod authorization;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+pub mod cache;
+#[cfg(feature = "cassette")]
+pub mod cassette;
 pub mod data;
 pub mod error;
 pub mod id;
+pub mod links;
 pub mod meta;
+pub mod metrics;
+#[cfg(feature = "tower")]
+pub mod middleware;
 pub mod query;
+pub mod quota;
+pub mod render;
 pub mod requests;
+pub mod selection;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod transport;
 mod utils;
+#[cfg(feature = "webhook")]
+pub mod webhook;
 
 pub use self::{
     api::{TwitterApi, TwitterApiWithUserCtx},
@@ -73,6 +108,11 @@ pub use self::{
     error::{Error, Result},
 };
 
+#[doc(hidden)]
+pub mod __private {
+    pub use serde;
+}
+
 pub mod prelude {
     pub use crate::api_result::PaginableApiResponse;
     pub use crate::authorization::Authorization;
@@ -0,0 +1,241 @@
+use crate::data::{List, Space, Tweet, User};
+use crate::error::{Error, Result};
+use crate::id::{ListId, SpaceId, TweetId, UserId};
+use crate::utils::percent_encode;
+use std::fmt;
+use std::str::FromStr;
+use url::Url;
+
+const HOSTS: &[&str] = &[
+    "twitter.com",
+    "www.twitter.com",
+    "mobile.twitter.com",
+    "x.com",
+    "www.x.com",
+    "mobile.x.com",
+];
+
+// Top level paths which look like usernames but are not profiles
+const RESERVED: &[&str] = &[
+    "compose",
+    "explore",
+    "hashtag",
+    "home",
+    "i",
+    "intent",
+    "login",
+    "logout",
+    "messages",
+    "notifications",
+    "privacy",
+    "search",
+    "settings",
+    "share",
+    "signup",
+    "tos",
+];
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TwitterUrl {
+    Tweet {
+        username: Option<String>,
+        id: TweetId,
+    },
+    /// The hidden replies of a tweet (`/<username>/status/<id>/hidden`)
+    ModeratedTweets {
+        username: Option<String>,
+        id: TweetId,
+    },
+    User {
+        username: String,
+    },
+    UserId {
+        id: UserId,
+    },
+    List {
+        id: ListId,
+    },
+    Space {
+        id: SpaceId,
+    },
+}
+
+impl TwitterUrl {
+    pub fn parse(url: &str) -> Result<Self> {
+        let trimmed = url.trim();
+        let parsed = if trimmed.contains("://") {
+            Url::parse(trimmed)?
+        } else {
+            Url::parse(&format!("https://{trimmed}"))?
+        };
+        Self::from_url(&parsed).ok_or_else(|| Error::UnrecognizedUrl(url.to_string()))
+    }
+
+    pub fn from_url(url: &Url) -> Option<Self> {
+        if !matches!(url.scheme(), "http" | "https") {
+            return None;
+        }
+        let host = url.host_str()?.to_ascii_lowercase();
+        if !HOSTS.contains(&host.as_str()) {
+            return None;
+        }
+        let segments = url
+            .path_segments()?
+            .filter(|segment| !segment.is_empty())
+            .collect::<Vec<_>>();
+        match segments.as_slice() {
+            ["i", "web", "status", id, rest @ ..] | ["i", "status", id, rest @ ..] => {
+                let id = id.parse().ok()?;
+                if rest.first() == Some(&"hidden") {
+                    Some(Self::ModeratedTweets { username: None, id })
+                } else {
+                    Some(Self::Tweet { username: None, id })
+                }
+            }
+            ["i", "lists", id, ..] => Some(Self::List {
+                id: id.parse().ok()?,
+            }),
+            ["i", "spaces", id, ..] => Some(Self::Space { id: (*id).into() }),
+            ["i", "user", id, ..] => Some(Self::UserId {
+                id: id.parse().ok()?,
+            }),
+            ["intent", "user"] => {
+                let (_, value) = url.query_pairs().find(|(key, _)| key == "user_id")?;
+                Some(Self::UserId {
+                    id: value.parse().ok()?,
+                })
+            }
+            [username, "status" | "statuses", id, rest @ ..] => {
+                let username = if *username == "i" || !is_username(username) {
+                    None
+                } else {
+                    Some(username.to_string())
+                };
+                let id = id.parse().ok()?;
+                if rest.first() == Some(&"hidden") {
+                    Some(Self::ModeratedTweets { username, id })
+                } else {
+                    Some(Self::Tweet { username, id })
+                }
+            }
+            [username, ..] if is_username(username) && !is_reserved(username) => Some(Self::User {
+                username: username.to_string(),
+            }),
+            _ => None,
+        }
+    }
+
+    pub fn tweet_id(&self) -> Option<TweetId> {
+        match self {
+            Self::Tweet { id, .. } | Self::ModeratedTweets { id, .. } => Some(*id),
+            _ => None,
+        }
+    }
+
+    pub fn to_url(&self) -> Url {
+        let url = match self {
+            Self::Tweet {
+                username: Some(username),
+                id,
+            } => format!(
+                "https://twitter.com/{}/status/{id}",
+                percent_encode(username)
+            ),
+            Self::Tweet { username: None, id } => format!("https://twitter.com/i/web/status/{id}"),
+            Self::ModeratedTweets {
+                username: Some(username),
+                id,
+            } => format!(
+                "https://twitter.com/{}/status/{id}/hidden",
+                percent_encode(username)
+            ),
+            Self::ModeratedTweets { username: None, id } => {
+                format!("https://twitter.com/i/web/status/{id}/hidden")
+            }
+            Self::User { username } => format!("https://twitter.com/{}", percent_encode(username)),
+            Self::UserId { id } => format!("https://twitter.com/i/user/{id}"),
+            Self::List { id } => format!("https://twitter.com/i/lists/{id}"),
+            Self::Space { id } => format!(
+                "https://twitter.com/i/spaces/{}",
+                percent_encode(&id.as_str())
+            ),
+        };
+        Url::parse(&url).unwrap()
+    }
+}
+
+impl FromStr for TwitterUrl {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for TwitterUrl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.to_url(), f)
+    }
+}
+
+impl From<TwitterUrl> for Url {
+    fn from(url: TwitterUrl) -> Self {
+        url.to_url()
+    }
+}
+
+fn is_username(username: &str) -> bool {
+    !username.is_empty()
+        && username.len() <= 15
+        && username
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn is_reserved(username: &str) -> bool {
+    RESERVED
+        .iter()
+        .any(|reserved| reserved.eq_ignore_ascii_case(username))
+}
+
+impl Tweet {
+    /// Link to the tweet. As tweets don't carry the author's username, this uses the
+    /// `/i/web/status/<id>` form which redirects to the canonical URL.
+    pub fn url(&self) -> Url {
+        TwitterUrl::Tweet {
+            username: None,
+            id: self.id,
+        }
+        .to_url()
+    }
+}
+
+impl User {
+    pub fn profile_url(&self) -> Url {
+        TwitterUrl::User {
+            username: self.username.clone(),
+        }
+        .to_url()
+    }
+    pub fn tweet_url(&self, id: impl Into<TweetId>) -> Url {
+        TwitterUrl::Tweet {
+            username: Some(self.username.clone()),
+            id: id.into(),
+        }
+        .to_url()
+    }
+}
+
+impl Space {
+    pub fn url(&self) -> Url {
+        TwitterUrl::Space {
+            id: self.id.clone(),
+        }
+        .to_url()
+    }
+}
+
+impl List {
+    pub fn url(&self) -> Url {
+        TwitterUrl::List { id: self.id }.to_url()
+    }
+}
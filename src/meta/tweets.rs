@@ -1,13 +1,14 @@
 use super::pagination::PaginationMeta;
+use crate::id::TweetId;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct TweetsMeta {
     pub result_count: usize,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub newest_id: Option<String>,
+    pub newest_id: Option<TweetId>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub oldest_id: Option<String>,
+    pub oldest_id: Option<TweetId>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub next_token: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -1,6 +1,18 @@
+use super::pagination::PaginationMeta;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct TweetsCountsMeta {
-    total_tweet_count: usize,
+    pub total_tweet_count: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_token: Option<String>,
+}
+
+impl PaginationMeta for TweetsCountsMeta {
+    fn next_token(&self) -> Option<&str> {
+        self.next_token.as_deref()
+    }
+    fn previous_token(&self) -> Option<&str> {
+        None
+    }
 }
@@ -0,0 +1,113 @@
+//! Hooks to observe the requests made by a [`TwitterApi`](crate::TwitterApi).
+//!
+//! A [`Metrics`] implementation installed with
+//! [`TwitterApi::with_metrics`](crate::TwitterApi::with_metrics) is called once per API
+//! call with an [`ApiCall`] describing it. [`EndpointCounters`] is a ready-made
+//! implementation counting requests and errors per endpoint.
+//!
+//! With the `tracing` feature every API call additionally runs in a `twitter_api` span
+//! recording the same information. URLs, headers and bodies are never recorded, so no
+//! credentials end up in logs.
+
+use crate::error::Error;
+use crate::transport::RateLimit;
+use reqwest::{Method, StatusCode};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+pub trait Metrics: Send + Sync + 'static {
+    fn record(&self, call: &ApiCall<'_>);
+}
+
+#[derive(Debug)]
+pub struct ApiCall<'a> {
+    pub method: &'a Method,
+    /// The path of the endpoint relative to the API base URL with IDs replaced by `:id`,
+    /// e.g. `users/:id/following`
+    pub endpoint: &'a str,
+    /// `None` if no response was received
+    pub status: Option<StatusCode>,
+    pub rate_limit: Option<RateLimit>,
+    /// Time until the response headers were received
+    pub latency: Duration,
+    /// The transport error if no response was received
+    pub error: Option<&'a Error>,
+}
+
+impl<'a> ApiCall<'a> {
+    pub fn is_error(&self) -> bool {
+        self.error.is_some()
+            || !self
+                .status
+                .is_some_and(|status| status.is_success() || status == StatusCode::NOT_MODIFIED)
+    }
+
+    #[cfg(feature = "tracing")]
+    pub(crate) fn trace(&self, span: &tracing::Span) {
+        if let Some(status) = self.status {
+            span.record("status", status.as_u16());
+        }
+        if let Some(rate_limit) = self.rate_limit {
+            span.record("rate_limit_remaining", rate_limit.remaining);
+        }
+        span.record("latency_ms", self.latency.as_millis() as u64);
+        span.in_scope(|| match (self.status, self.error) {
+            (_, Some(err)) => tracing::warn!(error = %err, "request failed"),
+            (Some(status), _) if !status.is_success() => {
+                tracing::warn!("request returned an error status")
+            }
+            _ => tracing::debug!("request completed"),
+        });
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct EndpointCount {
+    pub requests: u64,
+    pub errors: u64,
+}
+
+/// Counts requests and errors per method and endpoint, e.g. `GET tweets/:id`
+#[derive(Debug, Default)]
+pub struct EndpointCounters {
+    counts: Mutex<BTreeMap<String, EndpointCount>>,
+}
+
+impl EndpointCounters {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, method: &Method, endpoint: &str) -> EndpointCount {
+        self.counts
+            .lock()
+            .unwrap()
+            .get(&format!("{method} {endpoint}"))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn counts(&self) -> BTreeMap<String, EndpointCount> {
+        self.counts.lock().unwrap().clone()
+    }
+}
+
+impl Metrics for EndpointCounters {
+    fn record(&self, call: &ApiCall<'_>) {
+        let mut counts = self.counts.lock().unwrap();
+        let count = counts
+            .entry(format!("{} {}", call.method, call.endpoint))
+            .or_default();
+        count.requests += 1;
+        if call.is_error() {
+            count.errors += 1;
+        }
+    }
+}
+
+impl<M: Metrics> Metrics for std::sync::Arc<M> {
+    fn record(&self, call: &ApiCall<'_>) {
+        (**self).record(call)
+    }
+}
@@ -0,0 +1,417 @@
+//! [`tower`] integration, enabled with the `tower` feature.
+//!
+//! [`ReqwestTransport`] is a `Service<reqwest::Request>`, so standard and custom tower
+//! layers can be stacked on top of it and the result installed with
+//! [`TwitterApi::with_service`](crate::TwitterApi::with_service). Requests reach the stack
+//! fully built and authorized. Two layers aware of Twitter's rate limit headers are
+//! provided here: [`RateLimitLayer`] holds back requests to endpoints whose limit is
+//! exhausted and [`RetryPolicy`] retries rate limited and failed requests. An
+//! [`AuthorizeLayer`] beneath the retries signs every attempt anew.
+//!
+//! ```
+//! use std::time::Duration;
+//! use tower::retry::RetryLayer;
+//! use tower::ServiceBuilder;
+//! use twitter_v2::authorization::BearerToken;
+//! use twitter_v2::middleware::{AuthorizeLayer, RateLimitLayer, RetryPolicy};
+//! use twitter_v2::transport::ReqwestTransport;
+//! use twitter_v2::TwitterApi;
+//!
+//! let auth = BearerToken::new("");
+//! let service = ServiceBuilder::new()
+//!     .layer(RetryLayer::new(RetryPolicy::new(3)))
+//!     .layer(AuthorizeLayer::new(auth.clone()))
+//!     .layer(RateLimitLayer::new().max_wait(Duration::from_secs(60)))
+//!     .service(ReqwestTransport::default());
+//! let api = TwitterApi::new(auth).with_service(service);
+//! ```
+
+use crate::api_result::ApiError;
+use crate::authorization::Authorization;
+use crate::error::{Error, Result};
+use crate::transport::{RateLimit, ReqwestTransport, TransportResponse, TwitterTransport};
+use crate::utils::{endpoint_template, trace_event};
+use async_trait::async_trait;
+use futures::future::BoxFuture;
+use reqwest::header::AUTHORIZATION;
+use reqwest::{Method, Request, StatusCode};
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+use time::OffsetDateTime;
+use tower::{Layer, Service, ServiceExt};
+
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+impl Service<Request> for ReqwestTransport {
+    type Response = TransportResponse;
+    type Error = Error;
+    type Future = BoxFuture<'static, Result<TransportResponse>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let client = self.client().clone();
+        Box::pin(async move { Ok(client.execute(request).await?.into()) })
+    }
+}
+
+/// Adapts a tower service to a [`TwitterTransport`]. Errors of the service which are not
+/// an [`Error`] are returned as [`Error::Middleware`].
+#[derive(Debug)]
+pub struct ServiceTransport<S> {
+    service: Mutex<S>,
+}
+
+impl<S> ServiceTransport<S> {
+    pub fn new(service: S) -> Self {
+        Self {
+            service: Mutex::new(service),
+        }
+    }
+}
+
+#[async_trait]
+impl<S> TwitterTransport for ServiceTransport<S>
+where
+    S: Service<Request, Response = TransportResponse> + Clone + Send + 'static,
+    S::Error: Into<BoxError>,
+    S::Future: Send,
+{
+    async fn execute(&self, request: Request) -> Result<TransportResponse> {
+        let service = self.service.lock().unwrap().clone();
+        service
+            .oneshot(request)
+            .await
+            .map_err(|err| into_error(err.into()))
+    }
+}
+
+fn into_error(err: BoxError) -> Error {
+    match err.downcast::<Error>() {
+        Ok(err) => *err,
+        Err(err) => Error::Middleware(err),
+    }
+}
+
+/// Tracks the `x-rate-limit-*` headers per endpoint and delays requests to endpoints whose
+/// limit is exhausted until it resets. Requests that would have to wait longer than
+/// `max_wait` fail right away with a `429 Too Many Requests` [`ApiError`].
+#[derive(Clone, Debug)]
+pub struct RateLimitLayer {
+    limits: Arc<Mutex<HashMap<String, RateLimit>>>,
+    max_wait: Duration,
+}
+
+impl RateLimitLayer {
+    pub fn new() -> Self {
+        Self {
+            limits: Default::default(),
+            max_wait: Duration::from_secs(15 * 60),
+        }
+    }
+
+    /// Defaults to 15 minutes, the length of Twitter's rate limit windows
+    pub fn max_wait(mut self, max_wait: Duration) -> Self {
+        self.max_wait = max_wait;
+        self
+    }
+
+    /// The last known rate limit of `method` on `path`, relative to the API base URL.
+    /// IDs in the path are ignored, e.g. `tweets/20/liking_users` and
+    /// `tweets/21/liking_users` share a limit.
+    pub fn rate_limit(&self, method: &str, path: &str) -> Option<RateLimit> {
+        self.limits
+            .lock()
+            .unwrap()
+            .get(&endpoint_key(method, path))
+            .copied()
+    }
+}
+
+impl Default for RateLimitLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimitService<S>;
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimitService {
+            inner,
+            limits: self.limits.clone(),
+            max_wait: self.max_wait,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RateLimitService<S> {
+    inner: S,
+    limits: Arc<Mutex<HashMap<String, RateLimit>>>,
+    max_wait: Duration,
+}
+
+impl<S> Service<Request> for RateLimitService<S>
+where
+    S: Service<Request, Response = TransportResponse> + Clone + Send + 'static,
+    S::Error: Into<BoxError>,
+    S::Future: Send,
+{
+    type Response = TransportResponse;
+    type Error = BoxError;
+    type Future = BoxFuture<'static, Result<TransportResponse, BoxError>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), BoxError>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        // the readied service is the one to call, leave a fresh clone in its place
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let limits = self.limits.clone();
+        let max_wait = self.max_wait;
+        let key = endpoint_key(request.method().as_str(), request.url().path());
+        Box::pin(async move {
+            let exhausted = limits
+                .lock()
+                .unwrap()
+                .get(&key)
+                .copied()
+                .filter(RateLimit::is_exhausted);
+            if let Some(limit) = exhausted {
+                let wait = until(limit.reset);
+                if wait > max_wait {
+                    return Err(Box::new(Error::Api(rate_limited(&limit))) as BoxError);
+                }
+                tokio::time::sleep(wait).await;
+            }
+            let response = inner.call(request).await.map_err(Into::into)?;
+            if let Some(limit) = response.rate_limit() {
+                limits.lock().unwrap().insert(key, limit);
+            }
+            Ok(response)
+        })
+    }
+}
+
+/// Sets the `Authorization` header of every request passing through it. Stacked beneath a
+/// [`RetryLayer`](tower::retry::RetryLayer), retries are signed anew instead of reusing the
+/// signature, and with it the OAuth1 nonce, of the first attempt.
+#[derive(Debug)]
+pub struct AuthorizeLayer<A> {
+    auth: Arc<A>,
+}
+
+impl<A> AuthorizeLayer<A> {
+    pub fn new(auth: A) -> Self {
+        Self {
+            auth: Arc::new(auth),
+        }
+    }
+}
+
+impl<A> Clone for AuthorizeLayer<A> {
+    fn clone(&self) -> Self {
+        Self {
+            auth: self.auth.clone(),
+        }
+    }
+}
+
+impl<S, A> Layer<S> for AuthorizeLayer<A> {
+    type Service = AuthorizeService<S, A>;
+    fn layer(&self, inner: S) -> Self::Service {
+        AuthorizeService {
+            inner,
+            auth: self.auth.clone(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct AuthorizeService<S, A> {
+    inner: S,
+    auth: Arc<A>,
+}
+
+impl<S: Clone, A> Clone for AuthorizeService<S, A> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            auth: self.auth.clone(),
+        }
+    }
+}
+
+impl<S, A> Service<Request> for AuthorizeService<S, A>
+where
+    S: Service<Request, Response = TransportResponse> + Clone + Send + 'static,
+    S::Error: Into<BoxError>,
+    S::Future: Send,
+    A: Authorization + Send + Sync + 'static,
+{
+    type Response = TransportResponse;
+    type Error = BoxError;
+    type Future = BoxFuture<'static, Result<TransportResponse, BoxError>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), BoxError>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, mut request: Request) -> Self::Future {
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let auth = self.auth.clone();
+        Box::pin(async move {
+            let authorization = auth.header(&request).await?;
+            request.headers_mut().insert(AUTHORIZATION, authorization);
+            inner.call(request).await.map_err(Into::into)
+        })
+    }
+}
+
+/// A [`tower::retry::Policy`] retrying rate limited requests once their limit resets, and
+/// server errors and connection failures with exponential backoff.
+///
+/// Only `GET`, `HEAD` and `OPTIONS` requests are retried unless [`RetryPolicy::retry_writes`]
+/// is set. Retries are sent as the first attempt was, so stack an [`AuthorizeLayer`]
+/// beneath the retries to sign each of them anew.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    attempt: usize,
+    attempts: usize,
+    backoff: Duration,
+    max_wait: Duration,
+    retry_writes: bool,
+}
+
+impl RetryPolicy {
+    /// Retry up to `attempts` times
+    pub fn new(attempts: usize) -> Self {
+        Self {
+            attempt: 0,
+            attempts,
+            backoff: Duration::from_secs(1),
+            max_wait: Duration::from_secs(15 * 60),
+            retry_writes: false,
+        }
+    }
+
+    /// Also retry `POST`, `PUT`, `PATCH` and `DELETE` requests. A write whose response was
+    /// lost may then be applied twice. Defaults to `false`.
+    pub fn retry_writes(mut self, retry_writes: bool) -> Self {
+        self.retry_writes = retry_writes;
+        self
+    }
+
+    fn retries(&self, method: &Method) -> bool {
+        self.retry_writes || matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+    }
+
+    /// The delay before the first retry of a failed request, doubled with every attempt.
+    /// Defaults to 1 second.
+    pub fn backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Give up on rate limited requests that would have to wait longer than `max_wait`.
+    /// Defaults to 15 minutes.
+    pub fn max_wait(mut self, max_wait: Duration) -> Self {
+        self.max_wait = max_wait;
+        self
+    }
+
+    fn wait_for<E: Any>(&self, result: Result<&TransportResponse, &E>) -> Option<Duration> {
+        match result {
+            Ok(response) if response.status == StatusCode::TOO_MANY_REQUESTS => {
+                match response.rate_limit() {
+                    Some(limit) => Some(until(limit.reset)).filter(|wait| *wait <= self.max_wait),
+                    None => Some(self.backoff),
+                }
+            }
+            Ok(response) if response.status.is_server_error() => Some(self.backoff),
+            Ok(_) => None,
+            Err(err) => {
+                let err = err as &dyn Any;
+                let err = err.downcast_ref::<Error>().or_else(|| {
+                    err.downcast_ref::<BoxError>()
+                        .and_then(|err| err.downcast_ref::<Error>())
+                });
+                match err {
+                    Some(Error::Request(err)) if err.is_connect() || err.is_timeout() => {
+                        Some(self.backoff)
+                    }
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
+impl<E: Any> tower::retry::Policy<Request, TransportResponse, E> for RetryPolicy {
+    type Future = BoxFuture<'static, Self>;
+
+    fn retry(&self, req: &Request, result: Result<&TransportResponse, &E>) -> Option<Self::Future> {
+        if self.attempt >= self.attempts || !self.retries(req.method()) {
+            return None;
+        }
+        let wait = self.wait_for(result)?;
+        trace_event!(
+            tracing::Level::INFO,
+            retry_attempt = self.attempt + 1,
+            wait_ms = wait.as_millis() as u64,
+            "retrying request"
+        );
+        let next = Self {
+            attempt: self.attempt + 1,
+            attempts: self.attempts,
+            backoff: self.backoff * 2,
+            max_wait: self.max_wait,
+            retry_writes: self.retry_writes,
+        };
+        Some(Box::pin(async move {
+            tokio::time::sleep(wait).await;
+            next
+        }))
+    }
+
+    fn clone_request(&self, req: &Request) -> Option<Request> {
+        if !self.retries(req.method()) {
+            return None;
+        }
+        req.try_clone()
+    }
+}
+
+fn endpoint_key(method: &str, path: &str) -> String {
+    format!(
+        "{} {}",
+        method.to_ascii_uppercase(),
+        endpoint_template(path)
+    )
+}
+
+fn until(time: OffsetDateTime) -> Duration {
+    (time - OffsetDateTime::now_utc())
+        .try_into()
+        .unwrap_or_default()
+}
+
+fn rate_limited(limit: &RateLimit) -> ApiError {
+    ApiError {
+        title: "Too Many Requests".to_string(),
+        kind: "about:blank".to_string(),
+        status: StatusCode::TOO_MANY_REQUESTS,
+        detail: format!("Rate limit exhausted until {}", limit.reset),
+        errors: Vec::new(),
+        rate_limit_reset: Some(limit.reset),
+    }
+}
@@ -1,4 +1,11 @@
-use super::get_req_builder;
+use super::{get_req_builder, Granularity, UrlQueryExt};
+use crate::authorization::Authorization;
+use crate::data::{TweetsCount, TweetsCountSeries};
+use crate::error::{Error, Result};
+use crate::meta::TweetsCountsMeta;
+use reqwest::Method;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
 
 get_req_builder! {
 pub struct GetTweetsRequestBuilder {
@@ -54,8 +61,59 @@ pub struct GetTweetsCountsRequestBuilder {
     end_time,
     since_id,
     until_id,
-    granularity
-}
+    granularity,
+    next_token
+}
+}
+
+impl<A> GetTweetsCountsRequestBuilder<A, Vec<TweetsCount>, TweetsCountsMeta>
+where
+    A: Authorization,
+{
+    /// Fetch every page of counts and collect them into a series without gaps. The range
+    /// defaults to the one covered by the counts if `start_time` or `end_time` is not set.
+    pub async fn series(&self) -> Result<TweetsCountSeries> {
+        let param = |name: &str| {
+            self.url
+                .query_pairs()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.into_owned())
+        };
+        let granularity = match param("granularity") {
+            Some(granularity) => granularity.parse().map_err(Error::custom)?,
+            None => Granularity::Hour,
+        };
+        let time = |name: &str| {
+            param(name)
+                .map(|time| OffsetDateTime::parse(&time, &Rfc3339).map_err(Error::custom))
+                .transpose()
+        };
+        let (start_time, end_time) = (time("start_time")?, time("end_time")?);
+
+        let mut counts = Vec::new();
+        let mut url = self.url.clone();
+        loop {
+            let response = self
+                .client
+                .send::<Vec<TweetsCount>, TweetsCountsMeta>(
+                    self.client.request(Method::GET, url.clone()),
+                )
+                .await?;
+            let next_token = response.meta().and_then(|meta| meta.next_token.clone());
+            counts.extend(response.into_data().unwrap_or_default());
+            match next_token {
+                Some(token) => url.replace_query_val("next_token", token),
+                None => break,
+            }
+        }
+        let start = start_time
+            .or_else(|| counts.iter().map(|count| count.start).min())
+            .unwrap_or_else(OffsetDateTime::now_utc);
+        let end = end_time
+            .or_else(|| counts.iter().map(|count| count.end).max())
+            .unwrap_or(start);
+        Ok(TweetsCountSeries::new(granularity, start, end, counts))
+    }
 }
 
 get_req_builder! {
@@ -173,3 +231,10 @@ pub struct GetComplianceJobsRequestBuilder {
     compliance_job_status
 }
 }
+
+get_req_builder! {
+pub struct GetUsageTweetsRequestBuilder {
+    days,
+    usage_fields
+}
+}
@@ -1,6 +1,6 @@
-use strum::Display;
+use strum::{Display, EnumIter};
 
-#[derive(Copy, Clone, Debug, Display)]
+#[derive(Copy, Clone, Debug, Display, EnumIter, Eq, PartialEq)]
 #[strum(serialize_all = "snake_case")]
 pub enum TweetExpansion {
     AuthorId,
@@ -17,15 +17,16 @@ pub enum TweetExpansion {
     EntitiesMentionsUsername,
     #[strum(serialize = "referenced_tweets.id.author_id")]
     ReferencedTweetsIdAuthorId,
+    EditHistoryTweetIds,
 }
 
-#[derive(Copy, Clone, Debug, Display)]
+#[derive(Copy, Clone, Debug, Display, EnumIter, Eq, PartialEq)]
 #[strum(serialize_all = "snake_case")]
 pub enum UserExpansion {
     PinnedTweetId,
 }
 
-#[derive(Copy, Clone, Debug, Display)]
+#[derive(Copy, Clone, Debug, Display, EnumIter, Eq, PartialEq)]
 #[strum(serialize_all = "snake_case")]
 pub enum SpaceExpansion {
     InvitedUserIds,
@@ -34,13 +35,13 @@ pub enum SpaceExpansion {
     HostIds,
 }
 
-#[derive(Copy, Clone, Debug, Display)]
+#[derive(Copy, Clone, Debug, Display, EnumIter, Eq, PartialEq)]
 #[strum(serialize_all = "snake_case")]
 pub enum LimitedTweetExpansion {
     AuthorId,
 }
 
-#[derive(Copy, Clone, Debug, Display)]
+#[derive(Copy, Clone, Debug, Display, EnumIter, Eq, PartialEq)]
 #[strum(serialize_all = "snake_case")]
 pub enum ListExpansion {
     OwnerId,
@@ -1,6 +1,6 @@
-use strum::Display;
+use strum::{Display, EnumIter};
 
-#[derive(Copy, Clone, Debug, Display)]
+#[derive(Copy, Clone, Debug, Display, EnumIter, Eq, PartialEq)]
 #[strum(serialize_all = "snake_case")]
 pub enum MediaField {
     DurationMs,
@@ -18,7 +18,7 @@ pub enum MediaField {
     Variants
 }
 
-#[derive(Copy, Clone, Debug, Display)]
+#[derive(Copy, Clone, Debug, Display, EnumIter, Eq, PartialEq)]
 #[strum(serialize_all = "snake_case")]
 pub enum PlaceField {
     ContainedWithin,
@@ -31,7 +31,7 @@ pub enum PlaceField {
     PlaceType,
 }
 
-#[derive(Copy, Clone, Debug, Display)]
+#[derive(Copy, Clone, Debug, Display, EnumIter, Eq, PartialEq)]
 #[strum(serialize_all = "snake_case")]
 pub enum PollField {
     DurationMinutes,
@@ -41,7 +41,7 @@ pub enum PollField {
     VotingStatus,
 }
 
-#[derive(Copy, Clone, Debug, Display)]
+#[derive(Copy, Clone, Debug, Display, EnumIter, Eq, PartialEq)]
 #[strum(serialize_all = "snake_case")]
 pub enum TweetField {
     Attachments,
@@ -49,12 +49,15 @@ pub enum TweetField {
     ContextAnnotations,
     ConversationId,
     CreatedAt,
+    EditControls,
+    EditHistoryTweetIds,
     Entities,
     Geo,
     Id,
     InReplyToUserId,
     Lang,
     NonPublicMetrics,
+    NoteTweet,
     PublicMetrics,
     OrganicMetrics,
     PromotedMetrics,
@@ -66,7 +69,7 @@ pub enum TweetField {
     Withheld,
 }
 
-#[derive(Copy, Clone, Debug, Display)]
+#[derive(Copy, Clone, Debug, Display, EnumIter, Eq, PartialEq)]
 #[strum(serialize_all = "snake_case")]
 pub enum UserField {
     CreatedAt,
@@ -85,7 +88,7 @@ pub enum UserField {
     Withheld,
 }
 
-#[derive(Copy, Clone, Debug, Display)]
+#[derive(Copy, Clone, Debug, Display, EnumIter, Eq, PartialEq)]
 #[strum(serialize_all = "snake_case")]
 pub enum SpaceField {
     HostIds,
@@ -107,7 +110,7 @@ pub enum SpaceField {
     IsTicketed,
 }
 
-#[derive(Copy, Clone, Debug, Display)]
+#[derive(Copy, Clone, Debug, Display, EnumIter, Eq, PartialEq)]
 #[strum(serialize_all = "snake_case")]
 pub enum TopicField {
     Id,
@@ -115,7 +118,7 @@ pub enum TopicField {
     Description,
 }
 
-#[derive(Copy, Clone, Debug, Display)]
+#[derive(Copy, Clone, Debug, Display, EnumIter, Eq, PartialEq)]
 #[strum(serialize_all = "snake_case")]
 pub enum ListField {
     CreatedAt,
@@ -125,3 +128,10 @@ pub enum ListField {
     Description,
     OwnerId,
 }
+
+#[derive(Copy, Clone, Debug, Display, EnumIter, Eq, PartialEq)]
+#[strum(serialize_all = "snake_case")]
+pub enum UsageField {
+    DailyClientAppUsage,
+    DailyProjectUsage,
+}
@@ -1,9 +1,27 @@
-use strum::Display;
+use strum::{Display, EnumString};
+use time::{Duration, OffsetDateTime};
 
-#[derive(Copy, Clone, Debug, Display)]
+#[derive(Copy, Clone, Debug, Display, EnumString, Eq, PartialEq, Hash)]
 #[strum(serialize_all = "snake_case")]
 pub enum Granularity {
     Minute,
     Hour,
     Day,
 }
+
+impl Granularity {
+    pub fn duration(&self) -> Duration {
+        match self {
+            Self::Minute => Duration::MINUTE,
+            Self::Hour => Duration::HOUR,
+            Self::Day => Duration::DAY,
+        }
+    }
+
+    /// The start of the bucket containing `time`, with days starting at midnight UTC
+    pub fn truncate(&self, time: OffsetDateTime) -> OffsetDateTime {
+        let seconds = time.unix_timestamp();
+        let bucket = self.duration().whole_seconds();
+        OffsetDateTime::from_unix_timestamp(seconds - seconds.rem_euclid(bucket)).unwrap_or(time)
+    }
+}
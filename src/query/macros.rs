@@ -2,10 +2,11 @@ macro_rules! get_req_builder_arg {
     (ids) => {
         pub fn ids(
             &mut self,
-            ids: impl IntoIterator<Item = impl $crate::id::IntoNumericId>,
+            ids: impl IntoIterator<Item = impl Into<$crate::id::RuleId>>,
         ) -> &mut Self {
             use $crate::query::UrlQueryExt;
-            self.url.append_query_seq("ids", ids);
+            self.url
+                .append_query_seq("ids", ids.into_iter().map(Into::<$crate::id::RuleId>::into));
             self
         }
     };
@@ -89,6 +90,16 @@ macro_rules! get_req_builder_arg {
             self
         }
     };
+    (usage_fields) => {
+        pub fn usage_fields(
+            &mut self,
+            fields: impl IntoIterator<Item = $crate::query::UsageField>,
+        ) -> &mut Self {
+            use $crate::query::UrlQueryExt;
+            self.url.append_query_seq("usage.fields", fields);
+            self
+        }
+    };
     (tweet_expansions) => {
         pub fn expansions(
             &mut self,
@@ -164,17 +175,24 @@ macro_rules! get_req_builder_arg {
             self
         }
     };
+    (next_token) => {
+        pub fn next_token(&mut self, next_token: &str) -> &mut Self {
+            use $crate::query::UrlQueryExt;
+            self.url.append_query_val("next_token", next_token);
+            self
+        }
+    };
     (since_id) => {
-        pub fn since_id(&mut self, since_id: impl $crate::id::IntoNumericId) -> &mut Self {
+        pub fn since_id(&mut self, since_id: impl Into<$crate::id::TweetId>) -> &mut Self {
             use $crate::query::UrlQueryExt;
-            self.url.append_query_val("since_id", since_id);
+            self.url.append_query_val("since_id", since_id.into());
             self
         }
     };
     (until_id) => {
-        pub fn until_id(&mut self, until_id: impl $crate::id::IntoNumericId) -> &mut Self {
+        pub fn until_id(&mut self, until_id: impl Into<$crate::id::TweetId>) -> &mut Self {
             use $crate::query::UrlQueryExt;
-            self.url.append_query_val("until_id", until_id);
+            self.url.append_query_val("until_id", until_id.into());
             self
         }
     };
@@ -214,6 +232,14 @@ macro_rules! get_req_builder_arg {
             self
         }
     };
+    (days) => {
+        /// The number of days to report usage for, from 1 to 90
+        pub fn days(&mut self, days: usize) -> &mut Self {
+            use $crate::query::UrlQueryExt;
+            self.url.append_query_val("days", days);
+            self
+        }
+    };
     (space_state) => {
         pub fn state(&mut self, state: $crate::query::SpaceStateQuery) -> &mut Self {
             use $crate::query::UrlQueryExt;
@@ -230,6 +256,94 @@ macro_rules! get_req_builder_arg {
     };
 }
 
+macro_rules! get_req_builder_preset {
+    ($self:ident, $preset:ident, media_fields) => {{
+        use $crate::query::UrlQueryExt;
+        $self
+            .url
+            .merge_query_seq("media.fields", $crate::query::MediaField::preset($preset));
+    }};
+    ($self:ident, $preset:ident, place_fields) => {{
+        use $crate::query::UrlQueryExt;
+        $self
+            .url
+            .merge_query_seq("place.fields", $crate::query::PlaceField::preset($preset));
+    }};
+    ($self:ident, $preset:ident, poll_fields) => {{
+        use $crate::query::UrlQueryExt;
+        $self
+            .url
+            .merge_query_seq("poll.fields", $crate::query::PollField::preset($preset));
+    }};
+    ($self:ident, $preset:ident, user_fields) => {{
+        use $crate::query::UrlQueryExt;
+        $self
+            .url
+            .merge_query_seq("user.fields", $crate::query::UserField::preset($preset));
+    }};
+    ($self:ident, $preset:ident, tweet_fields) => {{
+        use $crate::query::UrlQueryExt;
+        $self
+            .url
+            .merge_query_seq("tweet.fields", $crate::query::TweetField::preset($preset));
+    }};
+    ($self:ident, $preset:ident, space_fields) => {{
+        use $crate::query::UrlQueryExt;
+        $self
+            .url
+            .merge_query_seq("space.fields", $crate::query::SpaceField::preset($preset));
+    }};
+    ($self:ident, $preset:ident, topic_fields) => {{
+        use $crate::query::UrlQueryExt;
+        $self
+            .url
+            .merge_query_seq("topic.fields", $crate::query::TopicField::preset($preset));
+    }};
+    ($self:ident, $preset:ident, list_fields) => {{
+        use $crate::query::UrlQueryExt;
+        $self
+            .url
+            .merge_query_seq("list.fields", $crate::query::ListField::preset($preset));
+    }};
+    ($self:ident, $preset:ident, usage_fields) => {{
+        use $crate::query::UrlQueryExt;
+        $self
+            .url
+            .merge_query_seq("usage.fields", $crate::query::UsageField::preset($preset));
+    }};
+    ($self:ident, $preset:ident, tweet_expansions) => {{
+        use $crate::query::UrlQueryExt;
+        $self
+            .url
+            .merge_query_seq("expansions", $crate::query::TweetExpansion::preset($preset));
+    }};
+    ($self:ident, $preset:ident, limited_tweet_expansions) => {{
+        use $crate::query::UrlQueryExt;
+        $self
+            .url
+            .merge_query_seq("expansions", $crate::query::LimitedTweetExpansion::preset($preset));
+    }};
+    ($self:ident, $preset:ident, user_expansions) => {{
+        use $crate::query::UrlQueryExt;
+        $self
+            .url
+            .merge_query_seq("expansions", $crate::query::UserExpansion::preset($preset));
+    }};
+    ($self:ident, $preset:ident, space_expansions) => {{
+        use $crate::query::UrlQueryExt;
+        $self
+            .url
+            .merge_query_seq("expansions", $crate::query::SpaceExpansion::preset($preset));
+    }};
+    ($self:ident, $preset:ident, list_expansions) => {{
+        use $crate::query::UrlQueryExt;
+        $self
+            .url
+            .merge_query_seq("expansions", $crate::query::ListExpansion::preset($preset));
+    }};
+    ($self:ident, $preset:ident, $other:tt) => {};
+}
+
 macro_rules! get_req_builder_verb {
     (send) => {
         pub async fn send(&self) -> $crate::ApiResult<A, T, M> {
@@ -251,15 +365,75 @@ macro_rules! get_req_builder_verb {
     };
 }
 
+#[cfg(feature = "blocking")]
+macro_rules! get_req_builder_blocking_verb {
+    (send) => {
+        pub fn send(&self) -> $crate::ApiResult<A, T, M> {
+            self.client
+                .send(self.client.request(reqwest::Method::GET, self.url.clone()))
+        }
+        /// Iterate over all pages of the response, following `next_token`
+        pub fn pages(&self) -> $crate::blocking::Pages<A, T, M>
+        where
+            M: $crate::meta::PaginationMeta,
+        {
+            $crate::blocking::Pages::new(&self.client, self.url.clone())
+        }
+    };
+    (stream) => {
+        pub fn stream(&self) -> $crate::Result<$crate::blocking::StreamIter<$crate::ApiPayload<T, M>>>
+        where
+            T: Send + 'static,
+            M: Send + 'static,
+        {
+            self.client
+                .stream(self.client.request(reqwest::Method::GET, self.url.clone()))
+        }
+    };
+}
+
 macro_rules! get_req_builder {
     ($vis:vis struct $class:ident { $($optional_arg:tt),* }) => {
         get_req_builder!{#[send] $vis struct $class { $($optional_arg),* }}
     };
     (#[$verb:tt] $vis:vis struct $class:ident { $($optional_arg:tt),* }) => {
-        $vis struct $class<A, T, M> {
-            client: $crate::TwitterApi<A>,
+        $vis struct $class<A, T, M, C = $crate::TwitterApi<A>> {
+            client: C,
             url: url::Url,
-            return_ty: std::marker::PhantomData<(T, M)>
+            return_ty: std::marker::PhantomData<(A, T, M)>
+        }
+
+        impl<A, T, M, C> $class<A, T, M, C> {
+            $($crate::query::get_req_builder_arg! { $optional_arg })*
+
+            /// Request the fields and expansions of `preset` which this endpoint accepts, in
+            /// addition to those already requested
+            #[allow(unused_variables)]
+            pub fn with_preset(&mut self, preset: $crate::query::FieldPreset) -> &mut Self {
+                $($crate::query::get_req_builder_preset! { self, preset, $optional_arg })*
+                self
+            }
+
+            /// Request every field and expansion this endpoint accepts with any
+            /// authorization, see [`FieldPreset::FullArchive`]($crate::query::FieldPreset::FullArchive)
+            pub fn with_all_fields(&mut self) -> &mut Self {
+                self.with_preset($crate::query::FieldPreset::FullArchive)
+            }
+
+            /// Request the fields of the [`Selection`]($crate::selection::Selection) `S` and
+            /// return them as non-optional fields of `S`
+            pub fn select<S>(&self) -> $class<A, T::Output, M, C>
+            where
+                S: $crate::selection::Selection,
+                T: $crate::selection::Project<S>,
+                C: Clone,
+            {
+                use $crate::query::UrlQueryExt;
+                use $crate::selection::Selectable;
+                let mut url = self.url.clone();
+                url.merge_query_seq(S::Source::FIELDS_KEY, S::fields());
+                $class { client: self.client.clone(), url, return_ty: Default::default() }
+            }
         }
 
         impl<A, T, M> $class<A, T, M>
@@ -271,11 +445,33 @@ macro_rules! get_req_builder {
             pub(crate) fn new(client: &$crate::TwitterApi<A>, url: url::Url) -> Self {
                 Self { client: client.clone(), url, return_ty: Default::default() }
             }
-            $($crate::query::get_req_builder_arg! { $optional_arg })*
             $crate::query::get_req_builder_verb! { $verb }
         }
 
-        impl<A, T, M> Clone for $class<A, T, M> {
+        #[cfg(feature = "blocking")]
+        impl<A, T, M> $class<A, T, M> {
+            pub(crate) fn into_blocking(
+                self,
+                client: &$crate::blocking::TwitterApi<A>,
+            ) -> $class<A, T, M, $crate::blocking::TwitterApi<A>> {
+                $class { client: client.clone(), url: self.url, return_ty: Default::default() }
+            }
+        }
+
+        #[cfg(feature = "blocking")]
+        impl<A, T, M> $class<A, T, M, $crate::blocking::TwitterApi<A>>
+        where
+            A: $crate::authorization::Authorization,
+            T: serde::de::DeserializeOwned,
+            M: serde::de::DeserializeOwned
+        {
+            $crate::query::get_req_builder_blocking_verb! { $verb }
+        }
+
+        impl<A, T, M, C> Clone for $class<A, T, M, C>
+        where
+            C: Clone
+        {
             fn clone(&self) -> Self {
                 Self {
                     client: self.client.clone(),
@@ -289,4 +485,7 @@ macro_rules! get_req_builder {
 
 pub(crate) use get_req_builder;
 pub(crate) use get_req_builder_arg;
+pub(crate) use get_req_builder_preset;
 pub(crate) use get_req_builder_verb;
+#[cfg(feature = "blocking")]
+pub(crate) use get_req_builder_blocking_verb;
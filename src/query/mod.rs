@@ -5,6 +5,7 @@ mod expansions;
 mod fields;
 mod granularity;
 mod macros;
+mod preset;
 mod sort_order;
 mod space_state;
 mod to_query;
@@ -16,6 +17,7 @@ pub use expansions::*;
 pub use fields::*;
 pub use granularity::*;
 pub(crate) use macros::*;
+pub use preset::*;
 pub use sort_order::*;
 pub use space_state::*;
 pub(crate) use to_query::*;
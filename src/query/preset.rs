@@ -0,0 +1,127 @@
+use super::{
+    LimitedTweetExpansion, ListExpansion, ListField, MediaField, PlaceField, PollField,
+    SpaceExpansion, SpaceField, TopicField, TweetExpansion, TweetField, UsageField, UserExpansion,
+    UserField,
+};
+use strum::IntoEnumIterator;
+
+/// A set of fields and expansions to request, applied with `with_preset` on the request
+/// builders. Builders only apply the fields and expansions their endpoint accepts.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum FieldPreset {
+    /// The identifying fields of each object and no expansions
+    Minimal,
+    /// [`Minimal`](Self::Minimal) and the public engagement metrics
+    PublicMetrics,
+    /// Every field and expansion available with any authorization, i.e. all but the
+    /// non-public, organic and promoted metrics
+    FullArchive,
+    /// Every field and expansion. The private metrics are only returned for Tweets and
+    /// media of the authenticated user, and requesting them for others fails.
+    Owner,
+}
+
+macro_rules! field_presets {
+    ($($ty:ident {
+        minimal: [$($minimal:ident),*],
+        public_metrics: [$($metrics:ident),*],
+        private: [$($private:ident),*] $(,)?
+    })*) => {
+        $(
+            impl $ty {
+                /// Every variant, including those added in later versions
+                pub fn all() -> impl Iterator<Item = Self> {
+                    Self::iter()
+                }
+
+                pub fn preset(preset: FieldPreset) -> Vec<Self> {
+                    match preset {
+                        FieldPreset::Minimal => vec![$(Self::$minimal),*],
+                        FieldPreset::PublicMetrics => vec![$(Self::$minimal,)* $(Self::$metrics),*],
+                        FieldPreset::FullArchive => Self::all()
+                            .filter(|field| ![$(Self::$private),*].contains(field))
+                            .collect(),
+                        FieldPreset::Owner => Self::all().collect(),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+field_presets! {
+    TweetField {
+        minimal: [Id, Text, AuthorId, CreatedAt],
+        public_metrics: [PublicMetrics],
+        private: [NonPublicMetrics, OrganicMetrics, PromotedMetrics],
+    }
+    UserField {
+        minimal: [Id, Name, Username],
+        public_metrics: [PublicMetrics],
+        private: [],
+    }
+    MediaField {
+        minimal: [MediaKey, Type],
+        public_metrics: [PublicMetrics],
+        private: [NonPublicMetrics, OrganicMetrics, PromotedMetrics],
+    }
+    PlaceField {
+        minimal: [Id, FullName],
+        public_metrics: [],
+        private: [],
+    }
+    PollField {
+        minimal: [Id, Options],
+        public_metrics: [],
+        private: [],
+    }
+    SpaceField {
+        minimal: [Id, State],
+        public_metrics: [ParticipantCount, SubscriberCount],
+        private: [],
+    }
+    TopicField {
+        minimal: [Id, Name],
+        public_metrics: [],
+        private: [],
+    }
+    ListField {
+        minimal: [],
+        public_metrics: [FollowerCount, MemberCount],
+        private: [],
+    }
+    UsageField {
+        minimal: [],
+        public_metrics: [],
+        private: [],
+    }
+}
+
+macro_rules! expansion_presets {
+    ($($ty:ident)*) => {
+        $(
+            impl $ty {
+                /// Every variant, including those added in later versions
+                pub fn all() -> impl Iterator<Item = Self> {
+                    Self::iter()
+                }
+
+                /// No expansions for the minimal and metrics presets, all of them otherwise
+                pub fn preset(preset: FieldPreset) -> Vec<Self> {
+                    match preset {
+                        FieldPreset::Minimal | FieldPreset::PublicMetrics => Vec::new(),
+                        FieldPreset::FullArchive | FieldPreset::Owner => Self::all().collect(),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+expansion_presets! {
+    TweetExpansion
+    UserExpansion
+    SpaceExpansion
+    LimitedTweetExpansion
+    ListExpansion
+}
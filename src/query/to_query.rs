@@ -12,25 +12,6 @@ where
         .join(",")
 }
 
-pub(crate) trait ToQuery {
-    fn to_query(self, query: &str) -> Vec<(&str, String)>;
-}
-
-impl<T> ToQuery for T
-where
-    T: IntoIterator,
-    T::Item: ToString,
-{
-    fn to_query(self, key: &str) -> Vec<(&str, String)> {
-        let value = to_query_value(self);
-        if value.is_empty() {
-            vec![]
-        } else {
-            vec![(key, value)]
-        }
-    }
-}
-
 pub trait UrlQueryExt {
     fn append_query_val<T>(&mut self, key: &str, value: T)
     where
@@ -48,6 +29,11 @@ pub trait UrlQueryExt {
             self.append_query_val(key, value)
         }
     }
+    /// Add the values missing from the comma separated list under `key`
+    fn merge_query_seq<T>(&mut self, key: &str, value: T)
+    where
+        T: IntoIterator,
+        T::Item: ToString;
     fn append_query_time(&mut self, key: &str, time: time::OffsetDateTime) {
         self.append_query_val(
             key,
@@ -84,4 +70,26 @@ impl UrlQueryExt for Url {
             self.append_query_val(key, value)
         }
     }
+    fn merge_query_seq<T>(&mut self, key: &str, value: T)
+    where
+        T: IntoIterator,
+        T::Item: ToString,
+    {
+        let existing = self
+            .query_pairs()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.split(',').map(str::to_string).collect::<Vec<_>>());
+        match existing {
+            Some(mut values) => {
+                for value in value {
+                    let value = value.to_string();
+                    if !values.contains(&value) {
+                        values.push(value);
+                    }
+                }
+                self.replace_query_val(key, values.join(","))
+            }
+            None => self.append_query_seq(key, value),
+        }
+    }
 }
@@ -0,0 +1,238 @@
+//! Guarding the monthly Tweet cap of a project.
+//!
+//! A [`QuotaGuard`] installed with
+//! [`TwitterApi::with_quota_guard`](crate::TwitterApi::with_quota_guard) checks the usage of
+//! the project reported by `usage/tweets` before each request to an endpoint consuming
+//! Tweets, such as the search and timeline endpoints. Once the usage reaches the threshold,
+//! by default 90% of the cap, those requests are refused with [`Error::QuotaExceeded`] or
+//! only logged, depending on the [`QuotaAction`]. Other endpoints are never affected.
+//!
+//! The usage is refreshed every [`refresh_interval`](QuotaGuard::refresh_interval). In
+//! between, each guarded request is assumed to consume its `max_results`, so the estimate
+//! errs on the side of caution.
+//!
+//! ```
+//! use std::sync::Arc;
+//! use twitter_v2::authorization::BearerToken;
+//! use twitter_v2::quota::{QuotaAction, QuotaGuard};
+//! use twitter_v2::TwitterApi;
+//!
+//! let guard = Arc::new(QuotaGuard::new().threshold(0.8).action(QuotaAction::Refuse));
+//! let api = TwitterApi::new(BearerToken::new("")).with_quota_guard(guard.clone());
+//! ```
+
+use crate::api_result::{ApiPayload, ApiResponseExt};
+use crate::authorization::Authorization;
+use crate::data::TweetUsage;
+use crate::error::{Error, Result};
+use crate::utils::{endpoint_template, matches_endpoint, trace_event};
+use crate::TwitterApi;
+use reqwest::{Method, Request};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// The endpoints guarded by default
+const TWEET_ENDPOINTS: &[&str] = &[
+    "tweets/search/all",
+    "tweets/search/recent",
+    "tweets/search/stream",
+    "tweets/sample/stream",
+    "users/:id/tweets",
+    "users/:id/mentions",
+    "users/:id/liked_tweets",
+    "lists/:id/tweets",
+];
+
+/// The number of Tweets assumed to be returned by a request without `max_results`
+const DEFAULT_RESULTS: u64 = 10;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum QuotaAction {
+    /// Emit a `tracing` warning event and send the request anyway
+    Warn,
+    /// Fail the request with [`Error::QuotaExceeded`]
+    Refuse,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct QuotaStatus {
+    /// The Tweets consumed this month, including the estimate since the last refresh
+    pub usage: u64,
+    pub cap: u64,
+}
+
+impl QuotaStatus {
+    pub fn fraction_used(&self) -> f64 {
+        if self.cap == 0 {
+            return 1.;
+        }
+        self.usage as f64 / self.cap as f64
+    }
+}
+
+#[derive(Debug)]
+pub struct QuotaGuard {
+    threshold: f64,
+    action: QuotaAction,
+    endpoints: Vec<String>,
+    refresh_interval: Duration,
+    state: Mutex<QuotaState>,
+    /// Held while fetching the usage, so that concurrent requests fetch it only once
+    refreshing: tokio::sync::Mutex<()>,
+}
+
+#[derive(Debug, Default)]
+struct QuotaState {
+    status: Option<QuotaStatus>,
+    refreshed_at: Option<Instant>,
+}
+
+impl QuotaGuard {
+    pub fn new() -> Self {
+        Self {
+            threshold: 0.9,
+            action: QuotaAction::Refuse,
+            endpoints: TWEET_ENDPOINTS.iter().map(|e| e.to_string()).collect(),
+            refresh_interval: Duration::from_secs(15 * 60),
+            state: Default::default(),
+            refreshing: Default::default(),
+        }
+    }
+
+    /// The share of the cap from which requests are refused or logged. Defaults to 0.9.
+    pub fn threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// What to do with requests once the threshold is reached. Defaults to
+    /// [`QuotaAction::Refuse`].
+    pub fn action(mut self, action: QuotaAction) -> Self {
+        self.action = action;
+        self
+    }
+
+    /// Guard `endpoints` instead of the search, stream and timeline endpoints. Patterns are
+    /// paths relative to the API base URL in which segments starting with `:` match any
+    /// value, e.g. `users/:id/tweets`.
+    pub fn endpoints(mut self, endpoints: impl IntoIterator<Item = impl ToString>) -> Self {
+        self.endpoints = endpoints.into_iter().map(|e| e.to_string()).collect();
+        self
+    }
+
+    /// How often to fetch the usage of the project. Defaults to 15 minutes.
+    pub fn refresh_interval(mut self, refresh_interval: Duration) -> Self {
+        self.refresh_interval = refresh_interval;
+        self
+    }
+
+    /// The last known usage of the project, `None` until it has been fetched
+    pub fn status(&self) -> Option<QuotaStatus> {
+        self.state.lock().unwrap().status
+    }
+
+    /// Replace the estimated usage with the one reported by `usage/tweets`
+    pub fn update(&self, usage: &TweetUsage) {
+        let mut state = self.state.lock().unwrap();
+        state.status = Some(QuotaStatus {
+            usage: usage.project_usage,
+            cap: usage.project_cap,
+        });
+        state.refreshed_at = Some(Instant::now());
+    }
+
+    fn is_stale(&self) -> bool {
+        match self.state.lock().unwrap().refreshed_at {
+            Some(refreshed_at) => refreshed_at.elapsed() >= self.refresh_interval,
+            None => true,
+        }
+    }
+
+    async fn refresh_usage<A: Authorization>(&self, api: &TwitterApi<A>) {
+        match fetch_usage(api).await {
+            Ok(usage) => self.update(&usage),
+            Err(_err) => {
+                trace_event!(tracing::Level::WARN, error = %_err, "failed to fetch tweet usage");
+                // keep the estimate and retry after the next interval
+                self.state.lock().unwrap().refreshed_at = Some(Instant::now());
+            }
+        }
+    }
+
+    pub(crate) async fn check<A: Authorization>(
+        &self,
+        api: &TwitterApi<A>,
+        req: &Request,
+    ) -> Result<()> {
+        let endpoint = endpoint_template(req.url().path());
+        if !self
+            .endpoints
+            .iter()
+            .any(|pattern| matches_endpoint(pattern, &endpoint))
+        {
+            return Ok(());
+        }
+        if self.is_stale() {
+            let _refreshing = self.refreshing.lock().await;
+            // another request may have refreshed the usage while this one waited
+            if self.is_stale() {
+                self.refresh_usage(api).await;
+            }
+        }
+
+        let mut state = self.state.lock().unwrap();
+        let Some(status) = state.status.as_mut() else {
+            return Ok(());
+        };
+        if status.fraction_used() >= self.threshold {
+            match self.action {
+                QuotaAction::Refuse => {
+                    return Err(Error::QuotaExceeded {
+                        usage: status.usage,
+                        cap: status.cap,
+                    })
+                }
+                QuotaAction::Warn => {
+                    trace_event!(
+                        tracing::Level::WARN,
+                        endpoint = %endpoint,
+                        usage = status.usage,
+                        cap = status.cap,
+                        "tweet cap nearly reached"
+                    );
+                }
+            }
+        }
+        status.usage += req
+            .url()
+            .query_pairs()
+            .find(|(key, _)| key == "max_results")
+            .and_then(|(_, value)| value.parse().ok())
+            .unwrap_or(DEFAULT_RESULTS);
+        Ok(())
+    }
+}
+
+impl Default for QuotaGuard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Fetch the usage without going through the guard, the metrics hook or the cache
+async fn fetch_usage<A: Authorization>(api: &TwitterApi<A>) -> Result<TweetUsage> {
+    let req = api
+        .request(Method::GET, api.url("usage/tweets")?)
+        .query(&[("days", "1")])
+        .build()?;
+    let payload = api
+        .dispatch(req, true)
+        .await?
+        .api_error_for_status()
+        .await?
+        .json::<ApiPayload<TweetUsage, ()>>()
+        .await?;
+    payload
+        .data
+        .ok_or_else(|| Error::custom("usage/tweets returned no data"))
+}
@@ -0,0 +1,318 @@
+use crate::data::{
+    AnnotationEntity, CashtagEntity, FullTextEntities, HashtagEntity, MentionEntity, Tweet,
+    UrlEntity,
+};
+use crate::utils::percent_encode;
+use std::fmt;
+use std::ops::Range;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum RenderFormat {
+    Html,
+    Markdown,
+    Plain,
+}
+
+impl RenderFormat {
+    /// Escape literal text so that it is safe to embed in the output format.
+    pub fn escape(self, text: &str) -> String {
+        match self {
+            RenderFormat::Html => escape_html(text),
+            RenderFormat::Markdown => escape_markdown(text),
+            RenderFormat::Plain => text.to_string(),
+        }
+    }
+    /// Render a link with already unescaped `text` pointing to `href`.
+    pub fn link(self, text: &str, href: &str) -> String {
+        match self {
+            RenderFormat::Html => format!(
+                "<a href=\"{}\">{}</a>",
+                escape_html(href),
+                escape_html(text)
+            ),
+            RenderFormat::Markdown => format!(
+                "[{}]({})",
+                escape_markdown(text),
+                href.replace('(', "%28").replace(')', "%29")
+            ),
+            RenderFormat::Plain => text.to_string(),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub enum EntityKind<'a> {
+    Url(&'a UrlEntity),
+    Hashtag(&'a HashtagEntity),
+    Cashtag(&'a CashtagEntity),
+    Mention(&'a MentionEntity),
+    Annotation(&'a AnnotationEntity),
+}
+
+/// An entity resolved against the text it was found in. `start` and `end` are
+/// offsets in Unicode code points, `text` is the slice of the source they cover.
+#[derive(Copy, Clone, Debug)]
+pub struct EntitySpan<'a> {
+    pub start: usize,
+    pub end: usize,
+    pub text: &'a str,
+    pub kind: EntityKind<'a>,
+}
+
+type EntityCallback<'a> = Box<dyn Fn(RenderFormat, &EntitySpan<'_>) -> Option<String> + 'a>;
+
+pub struct TextRenderer<'a> {
+    text: &'a str,
+    entities: Option<&'a FullTextEntities>,
+    strip_media_urls: bool,
+    callback: Option<EntityCallback<'a>>,
+}
+
+impl<'a> TextRenderer<'a> {
+    pub fn new(text: &'a str, entities: Option<&'a FullTextEntities>) -> Self {
+        Self {
+            text,
+            entities,
+            strip_media_urls: true,
+            callback: None,
+        }
+    }
+    /// Remove trailing media URLs (`https://t.co/...` links pointing at the
+    /// attached photos or videos). Enabled by default.
+    pub fn strip_media_urls(&mut self, strip_media_urls: bool) -> &mut Self {
+        self.strip_media_urls = strip_media_urls;
+        self
+    }
+    /// Override how entities are rendered. Returning `None` falls back to the
+    /// default rendering. The returned string is inserted as is, use
+    /// [`RenderFormat::escape`] for any literal text.
+    pub fn on_entity(
+        &mut self,
+        callback: impl Fn(RenderFormat, &EntitySpan<'_>) -> Option<String> + 'a,
+    ) -> &mut Self {
+        self.callback = Some(Box::new(callback));
+        self
+    }
+    pub fn to_html(&self) -> String {
+        self.render(RenderFormat::Html)
+    }
+    pub fn to_markdown(&self) -> String {
+        self.render(RenderFormat::Markdown)
+    }
+    pub fn to_plain(&self) -> String {
+        self.render(RenderFormat::Plain)
+    }
+    pub fn spans(&self) -> Vec<EntitySpan<'a>> {
+        self.resolve().into_iter().map(|(_, span)| span).collect()
+    }
+    pub fn render(&self, format: RenderFormat) -> String {
+        let spans = self.resolve();
+        let mut spans = spans.as_slice();
+        let mut text = self.text;
+        if self.strip_media_urls {
+            while let Some(((range, span), rest)) = spans.split_last() {
+                let trailing = text.get(range.end..).unwrap_or_default();
+                if !is_media_url(span) || !trailing.trim().is_empty() {
+                    break;
+                }
+                text = self.text[..range.start].trim_end();
+                spans = rest;
+            }
+        }
+
+        let mut out = String::with_capacity(text.len());
+        let mut cursor = 0;
+        for (range, span) in spans {
+            out.push_str(&format.escape(&unescape_html(&text[cursor..range.start])));
+            if let Some(rendered) = self
+                .callback
+                .as_ref()
+                .and_then(|callback| callback(format, span))
+            {
+                out.push_str(&rendered);
+            } else {
+                out.push_str(&render_entity(format, span));
+            }
+            cursor = range.end;
+        }
+        out.push_str(&format.escape(&unescape_html(&text[cursor..])));
+        out
+    }
+    fn resolve(&self) -> Vec<(Range<usize>, EntitySpan<'a>)> {
+        let offsets = self
+            .text
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain(std::iter::once(self.text.len()))
+            .collect::<Vec<_>>();
+        let mut candidates = Vec::new();
+        if let Some(entities) = self.entities {
+            for url in entities.urls.iter().flatten() {
+                if let (Some(start), Some(end)) = (url.start, url.end) {
+                    candidates.push((start, end, EntityKind::Url(url)));
+                }
+            }
+            for hashtag in entities.hashtags.iter().flatten() {
+                candidates.push((hashtag.start, hashtag.end, EntityKind::Hashtag(hashtag)));
+            }
+            for cashtag in entities.cashtags.iter().flatten() {
+                candidates.push((cashtag.start, cashtag.end, EntityKind::Cashtag(cashtag)));
+            }
+            for mention in entities.mentions.iter().flatten() {
+                candidates.push((mention.start, mention.end, EntityKind::Mention(mention)));
+            }
+            // annotation offsets are inclusive
+            for annotation in entities.annotations.iter().flatten() {
+                if annotation.start >= 0 && annotation.end >= annotation.start {
+                    candidates.push((
+                        annotation.start as usize,
+                        annotation.end as usize + 1,
+                        EntityKind::Annotation(annotation),
+                    ));
+                }
+            }
+        }
+        // links win over annotations when they overlap
+        candidates.sort_by_key(|(start, end, kind)| {
+            (*start, matches!(kind, EntityKind::Annotation(_)), *end)
+        });
+        let mut spans: Vec<(Range<usize>, EntitySpan<'a>)> = Vec::with_capacity(candidates.len());
+        for (start, end, kind) in candidates {
+            if start >= end || end >= offsets.len() {
+                continue;
+            }
+            if let Some((_, last)) = spans.last() {
+                if last.end > start {
+                    continue;
+                }
+            }
+            let range = offsets[start]..offsets[end];
+            let text = &self.text[range.clone()];
+            spans.push((
+                range,
+                EntitySpan {
+                    start,
+                    end,
+                    text,
+                    kind,
+                },
+            ));
+        }
+        spans
+    }
+}
+
+impl<'a> fmt::Debug for TextRenderer<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TextRenderer")
+            .field("text", &self.text)
+            .field("entities", &self.entities)
+            .field("strip_media_urls", &self.strip_media_urls)
+            .finish()
+    }
+}
+
+fn is_media_url(span: &EntitySpan<'_>) -> bool {
+    match span.kind {
+        EntityKind::Url(url) => {
+            url.media_key.is_some()
+                || url
+                    .display_url
+                    .as_deref()
+                    .map(|display_url| display_url.starts_with("pic.twitter.com/"))
+                    .unwrap_or(false)
+        }
+        _ => false,
+    }
+}
+
+fn render_entity(format: RenderFormat, span: &EntitySpan<'_>) -> String {
+    let text = unescape_html(span.text);
+    match span.kind {
+        EntityKind::Url(url) => {
+            let href = url.expanded_url.as_deref().unwrap_or(&url.url);
+            let display = url.display_url.as_deref().unwrap_or(href);
+            match format {
+                RenderFormat::Plain => href.to_string(),
+                _ => format.link(display, href),
+            }
+        }
+        EntityKind::Hashtag(hashtag) => format.link(
+            &text,
+            &format!(
+                "https://twitter.com/hashtag/{}",
+                percent_encode(&hashtag.tag)
+            ),
+        ),
+        EntityKind::Cashtag(cashtag) => format.link(
+            &text,
+            &format!(
+                "https://twitter.com/search?q=%24{}",
+                percent_encode(&cashtag.tag)
+            ),
+        ),
+        EntityKind::Mention(mention) => format.link(
+            &text,
+            &format!("https://twitter.com/{}", percent_encode(&mention.username)),
+        ),
+        EntityKind::Annotation(_) => format.escape(&text),
+    }
+}
+
+// Tweet text comes with `&`, `<` and `>` already HTML-escaped
+fn unescape_html(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            '\n' => out.push_str("<br>"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn escape_markdown(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(
+            c,
+            '\\' | '`'
+                | '*'
+                | '_'
+                | '{'
+                | '}'
+                | '['
+                | ']'
+                | '('
+                | ')'
+                | '#'
+                | '!'
+                | '<'
+                | '>'
+                | '|'
+                | '~'
+        ) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+impl Tweet {
+    /// Render the [full text](Tweet::full_text) of the tweet.
+    pub fn render(&self) -> TextRenderer<'_> {
+        TextRenderer::new(self.full_text(), self.full_text_entities())
+    }
+}
@@ -0,0 +1,519 @@
+use crate::api::TwitterApi;
+use crate::api_result::ApiPayload;
+use crate::authorization::Authorization;
+use crate::data::Tweet;
+use crate::error::Result;
+use crate::id::TweetId;
+use crate::meta::TweetsMeta;
+use crate::query::{GetTweetsSearchRequestBuilder, Granularity};
+use async_trait::async_trait;
+use futures::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use time::{Duration, OffsetDateTime};
+
+/// The longest range `tweets/counts/all` is asked about at once
+const COUNTS_CHUNK: Duration = Duration::days(30);
+
+type Configure<A> =
+    dyn Fn(&mut GetTweetsSearchRequestBuilder<A, Vec<Tweet>, TweetsMeta>) + Send + Sync;
+
+/// A time range of a backfill and its progress
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct BackfillWindow {
+    #[serde(with = "time::serde::rfc3339")]
+    pub start_time: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub end_time: OffsetDateTime,
+    /// The token of the next page to fetch, `None` if no page has been fetched yet or the
+    /// window is complete
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_token: Option<String>,
+    #[serde(default)]
+    pub complete: bool,
+}
+
+impl BackfillWindow {
+    pub fn new(start_time: OffsetDateTime, end_time: OffsetDateTime) -> Self {
+        Self {
+            start_time,
+            end_time,
+            next_token: None,
+            complete: false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, Eq, PartialEq)]
+pub struct BackfillCheckpoint {
+    pub windows: Vec<BackfillWindow>,
+}
+
+impl BackfillCheckpoint {
+    pub fn is_complete(&self) -> bool {
+        self.windows.iter().all(|window| window.complete)
+    }
+}
+
+/// Storage of the progress of a [`Backfill`]
+#[async_trait]
+pub trait CheckpointStore: Send + Sync + 'static {
+    async fn load(&self, key: &str) -> Result<Option<BackfillCheckpoint>>;
+    async fn save(&self, key: &str, checkpoint: &BackfillCheckpoint) -> Result<()>;
+}
+
+/// A [`CheckpointStore`] which does not survive the process, the default
+#[derive(Debug, Default)]
+pub struct MemoryCheckpointStore {
+    checkpoints: Mutex<HashMap<String, BackfillCheckpoint>>,
+}
+
+impl MemoryCheckpointStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl CheckpointStore for MemoryCheckpointStore {
+    async fn load(&self, key: &str) -> Result<Option<BackfillCheckpoint>> {
+        Ok(self.checkpoints.lock().unwrap().get(key).cloned())
+    }
+    async fn save(&self, key: &str, checkpoint: &BackfillCheckpoint) -> Result<()> {
+        self.checkpoints
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), checkpoint.clone());
+        Ok(())
+    }
+}
+
+/// A [`CheckpointStore`] keeping all checkpoints in a JSON file, which is replaced
+/// atomically on every save
+#[derive(Debug)]
+pub struct FileCheckpointStore {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl FileCheckpointStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    fn read(&self) -> Result<HashMap<String, BackfillCheckpoint>> {
+        match std::fs::read(&self.path) {
+            Ok(contents) => Ok(serde_json::from_slice(&contents)?),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(HashMap::new()),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+#[async_trait]
+impl CheckpointStore for FileCheckpointStore {
+    async fn load(&self, key: &str) -> Result<Option<BackfillCheckpoint>> {
+        let _lock = self.lock.lock().unwrap();
+        Ok(self.read()?.remove(key))
+    }
+    async fn save(&self, key: &str, checkpoint: &BackfillCheckpoint) -> Result<()> {
+        let _lock = self.lock.lock().unwrap();
+        let mut checkpoints = self.read()?;
+        checkpoints.insert(key.to_string(), checkpoint.clone());
+        let tmp = self.path.with_extension("tmp");
+        std::fs::write(&tmp, serde_json::to_vec_pretty(&checkpoints)?)?;
+        std::fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+}
+
+/// Collects all Tweets matching a query in a time range from the full-archive search,
+/// surviving restarts.
+///
+/// The range is split into windows, either of a fixed duration or holding about
+/// [`tweets_per_window`](Self::tweets_per_window) Tweets according to
+/// `tweets/counts/all`. Windows are fetched concurrently, while requests are spaced by
+/// [`min_interval`](Self::min_interval) to stay within the rate limit of the endpoint.
+///
+/// The progress of each window is saved to a [`CheckpointStore`] and a later run with the
+/// same query and range continues where the last one stopped. A page is checkpointed once
+/// the next item of the stream is requested, so after a crash pages may be delivered
+/// again but are never skipped. Tweets already delivered during a run are left out of
+/// later pages.
+///
+/// ```no_run
+/// use futures::prelude::*;
+/// use time::macros::datetime;
+/// use twitter_v2::authorization::BearerToken;
+/// use twitter_v2::requests::FileCheckpointStore;
+/// use twitter_v2::TwitterApi;
+///
+/// # #[tokio::main]
+/// # async fn main() -> twitter_v2::Result<()> {
+/// let api = TwitterApi::new(BearerToken::new(""));
+/// let mut backfill = api.backfill(
+///     "from:TwitterDev",
+///     datetime!(2020-01-01 0:00 UTC),
+///     datetime!(2021-01-01 0:00 UTC),
+/// );
+/// backfill
+///     .tweets_per_window(10_000)
+///     .checkpoint(FileCheckpointStore::new("backfill.json"));
+/// let mut pages = backfill.run().await?;
+/// while let Some(page) = pages.try_next().await? {
+///     println!("{} tweets", page.data.map_or(0, |tweets| tweets.len()));
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct Backfill<A> {
+    client: TwitterApi<A>,
+    query: String,
+    start_time: OffsetDateTime,
+    end_time: OffsetDateTime,
+    window: Duration,
+    tweets_per_window: Option<usize>,
+    concurrency: usize,
+    min_interval: std::time::Duration,
+    max_results: usize,
+    configure: Option<Arc<Configure<A>>>,
+    store: Arc<dyn CheckpointStore>,
+    key: Option<String>,
+}
+
+impl<A> Backfill<A>
+where
+    A: Authorization + Send + Sync + 'static,
+{
+    pub(crate) fn new(
+        client: &TwitterApi<A>,
+        query: impl ToString,
+        start_time: OffsetDateTime,
+        end_time: OffsetDateTime,
+    ) -> Self {
+        Self {
+            // a run must not miss Tweets because of a cached search page
+            client: client.clone().without_cache(),
+            query: query.to_string(),
+            start_time,
+            end_time,
+            window: Duration::days(1),
+            tweets_per_window: None,
+            concurrency: 4,
+            min_interval: std::time::Duration::from_secs(1),
+            max_results: 500,
+            configure: None,
+            store: Arc::new(MemoryCheckpointStore::new()),
+            key: None,
+        }
+    }
+    /// The duration of each window when not sized by Tweet counts. Defaults to 1 day.
+    pub fn window(&mut self, window: Duration) -> &mut Self {
+        self.window = window;
+        self
+    }
+    /// Size windows to hold about `tweets_per_window` Tweets each, according to the hourly
+    /// counts of `tweets/counts/all`
+    pub fn tweets_per_window(&mut self, tweets_per_window: usize) -> &mut Self {
+        self.tweets_per_window = Some(tweets_per_window);
+        self
+    }
+    /// The number of windows fetched at the same time. Defaults to 4.
+    pub fn concurrency(&mut self, concurrency: usize) -> &mut Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+    /// The minimum time between two requests of the backfill. Defaults to 1 second, the
+    /// rate limit of the full-archive search.
+    pub fn min_interval(&mut self, min_interval: std::time::Duration) -> &mut Self {
+        self.min_interval = min_interval;
+        self
+    }
+    /// The page size, from 10 to 500. Defaults to 500.
+    pub fn max_results(&mut self, max_results: usize) -> &mut Self {
+        self.max_results = max_results;
+        self
+    }
+    /// Set fields, expansions and other options of every search request
+    pub fn configure(
+        &mut self,
+        configure: impl Fn(&mut GetTweetsSearchRequestBuilder<A, Vec<Tweet>, TweetsMeta>)
+            + Send
+            + Sync
+            + 'static,
+    ) -> &mut Self {
+        self.configure = Some(Arc::new(configure));
+        self
+    }
+    /// Save progress to `store`. Progress is only kept in memory by default.
+    pub fn checkpoint(&mut self, store: impl CheckpointStore) -> &mut Self {
+        self.store = Arc::new(store);
+        self
+    }
+    /// The key of the checkpoint in the store. Defaults to the query and the time range.
+    pub fn key(&mut self, key: impl ToString) -> &mut Self {
+        self.key = Some(key.to_string());
+        self
+    }
+
+    fn checkpoint_key(&self) -> String {
+        self.key.clone().unwrap_or_else(|| {
+            format!(
+                "{} {}..{}",
+                self.query,
+                self.start_time.unix_timestamp(),
+                self.end_time.unix_timestamp()
+            )
+        })
+    }
+
+    /// Split the time range into windows without fetching any Tweets
+    pub async fn plan(&self) -> Result<Vec<BackfillWindow>> {
+        self.plan_throttled(&Throttle::new(self.min_interval)).await
+    }
+
+    async fn plan_throttled(&self, throttle: &Throttle) -> Result<Vec<BackfillWindow>> {
+        let Some(tweets_per_window) = self.tweets_per_window else {
+            let mut windows = Vec::new();
+            let mut start = self.start_time;
+            while start < self.end_time {
+                let end = (start + self.window.max(Duration::SECOND)).min(self.end_time);
+                windows.push(BackfillWindow::new(start, end));
+                start = end;
+            }
+            return Ok(windows);
+        };
+
+        let mut windows = Vec::new();
+        let mut window_start = self.start_time;
+        let mut tweets = 0;
+        let mut chunk_start = self.start_time;
+        while chunk_start < self.end_time {
+            let chunk_end = (chunk_start + COUNTS_CHUNK).min(self.end_time);
+            let mut counts = Vec::new();
+            let mut next_token = None;
+            loop {
+                throttle.wait().await;
+                let mut request = self.client.get_tweets_counts_all(&self.query);
+                request
+                    .start_time(chunk_start)
+                    .end_time(chunk_end)
+                    .granularity(Granularity::Hour);
+                if let Some(next_token) = next_token.as_deref() {
+                    request.next_token(next_token);
+                }
+                let response = request.send().await?;
+                next_token = response.meta().and_then(|meta| meta.next_token.clone());
+                counts.extend(response.into_data().unwrap_or_default());
+                if next_token.is_none() {
+                    break;
+                }
+            }
+            counts.sort_by_key(|count| count.start);
+            for count in counts {
+                tweets += count.tweet_count;
+                if tweets >= tweets_per_window && count.end > window_start {
+                    let end = count.end.min(self.end_time);
+                    windows.push(BackfillWindow::new(window_start, end));
+                    window_start = end;
+                    tweets = 0;
+                }
+            }
+            chunk_start = chunk_end;
+        }
+        if window_start < self.end_time {
+            windows.push(BackfillWindow::new(window_start, self.end_time));
+        }
+        Ok(windows)
+    }
+
+    /// Load the checkpoint of this backfill, `None` if it has not been started
+    pub async fn progress(&self) -> Result<Option<BackfillCheckpoint>> {
+        self.store.load(&self.checkpoint_key()).await
+    }
+
+    /// Stream the pages of all windows not completed yet, starting each window from its
+    /// checkpoint
+    pub async fn run(
+        &self,
+    ) -> Result<impl Stream<Item = Result<ApiPayload<Vec<Tweet>, TweetsMeta>>> + '_> {
+        let key = self.checkpoint_key();
+        let throttle = Throttle::new(self.min_interval);
+        let checkpoint = match self.store.load(&key).await? {
+            Some(checkpoint) => checkpoint,
+            None => {
+                let checkpoint = BackfillCheckpoint {
+                    windows: self.plan_throttled(&throttle).await?,
+                };
+                self.store.save(&key, &checkpoint).await?;
+                checkpoint
+            }
+        };
+        let pending = checkpoint
+            .windows
+            .iter()
+            .enumerate()
+            .filter(|(_, window)| !window.complete)
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        let run = Arc::new(Run {
+            key,
+            throttle,
+            checkpoint: Mutex::new(checkpoint),
+            save: tokio::sync::Mutex::new(()),
+            seen: Mutex::new(HashSet::new()),
+        });
+        Ok(stream::iter(pending)
+            .map(move |index| self.window_pages(run.clone(), index).boxed())
+            .flatten_unordered(self.concurrency))
+    }
+
+    fn window_pages(
+        &self,
+        run: Arc<Run>,
+        index: usize,
+    ) -> impl Stream<Item = Result<ApiPayload<Vec<Tweet>, TweetsMeta>>> + Send + '_ {
+        // the progress made by the last page, saved once the page has been consumed
+        stream::unfold(Some(None), move |progress| {
+            let run = run.clone();
+            async move {
+                let progress: Option<WindowProgress> = progress?;
+                if let Some(progress) = progress {
+                    if let Err(err) = self.save_progress(&run, index, progress).await {
+                        return Some((Err(err), None));
+                    }
+                }
+                let window = run.checkpoint.lock().unwrap().windows[index].clone();
+                if window.complete {
+                    return None;
+                }
+                match self.fetch_page(&run, &window).await {
+                    Ok(payload) => {
+                        let next_token = payload.meta.as_ref().and_then(|m| m.next_token.clone());
+                        let progress = WindowProgress {
+                            complete: next_token.is_none(),
+                            next_token,
+                        };
+                        Some((Ok(payload), Some(Some(progress))))
+                    }
+                    Err(err) => Some((Err(err), None)),
+                }
+            }
+        })
+    }
+
+    async fn fetch_page(
+        &self,
+        run: &Run,
+        window: &BackfillWindow,
+    ) -> Result<ApiPayload<Vec<Tweet>, TweetsMeta>> {
+        let mut builder = self.client.get_tweets_search_all(&self.query);
+        builder
+            .start_time(window.start_time)
+            .end_time(window.end_time)
+            .max_results(self.max_results);
+        if let Some(configure) = self.configure.as_ref() {
+            configure(&mut builder);
+        }
+        if let Some(token) = window.next_token.as_deref() {
+            builder.pagination_token(token);
+        }
+        run.throttle.wait().await;
+        let mut payload = builder.send().await?.into_payload();
+        if let Some(tweets) = payload.data.as_mut() {
+            let mut seen = run.seen.lock().unwrap();
+            tweets.retain(|tweet| seen.insert(tweet.id));
+        }
+        Ok(payload)
+    }
+
+    async fn save_progress(&self, run: &Run, index: usize, progress: WindowProgress) -> Result<()> {
+        // saves are serialized so that a stale snapshot never overwrites a newer one
+        let _save = run.save.lock().await;
+        let checkpoint = {
+            let mut checkpoint = run.checkpoint.lock().unwrap();
+            let window = &mut checkpoint.windows[index];
+            window.next_token = progress.next_token;
+            window.complete = progress.complete;
+            checkpoint.clone()
+        };
+        self.store.save(&run.key, &checkpoint).await
+    }
+}
+
+impl<A> Clone for Backfill<A> {
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client.clone(),
+            query: self.query.clone(),
+            start_time: self.start_time,
+            end_time: self.end_time,
+            window: self.window,
+            tweets_per_window: self.tweets_per_window,
+            concurrency: self.concurrency,
+            min_interval: self.min_interval,
+            max_results: self.max_results,
+            configure: self.configure.clone(),
+            store: self.store.clone(),
+            key: self.key.clone(),
+        }
+    }
+}
+
+impl<A> fmt::Debug for Backfill<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Backfill")
+            .field("query", &self.query)
+            .field("start_time", &self.start_time)
+            .field("end_time", &self.end_time)
+            .field("window", &self.window)
+            .field("tweets_per_window", &self.tweets_per_window)
+            .field("concurrency", &self.concurrency)
+            .field("min_interval", &self.min_interval)
+            .field("max_results", &self.max_results)
+            .finish_non_exhaustive()
+    }
+}
+
+struct Run {
+    key: String,
+    throttle: Throttle,
+    checkpoint: Mutex<BackfillCheckpoint>,
+    save: tokio::sync::Mutex<()>,
+    seen: Mutex<HashSet<TweetId>>,
+}
+
+struct WindowProgress {
+    next_token: Option<String>,
+    complete: bool,
+}
+
+/// Spaces out the requests of all windows
+struct Throttle {
+    interval: std::time::Duration,
+    next: tokio::sync::Mutex<Option<Instant>>,
+}
+
+impl Throttle {
+    fn new(interval: std::time::Duration) -> Self {
+        Self {
+            interval,
+            next: tokio::sync::Mutex::new(None),
+        }
+    }
+
+    async fn wait(&self) {
+        let mut next = self.next.lock().await;
+        if let Some(at) = *next {
+            tokio::time::sleep_until(at.into()).await;
+        }
+        *next = Some(Instant::now() + self.interval);
+    }
+}
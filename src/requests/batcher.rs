@@ -0,0 +1,406 @@
+use crate::api::TwitterApi;
+use crate::api_result::{ApiError, ApiPayload};
+use crate::authorization::Authorization;
+use crate::data::{Expansions, Tweet, User};
+use crate::error::{Error, Result};
+use crate::id::{TweetId, UserId};
+use crate::query::{
+    MediaField, PlaceField, PollField, TweetExpansion, TweetField, UrlQueryExt, UserExpansion,
+    UserField,
+};
+use futures::future::{self, BoxFuture, Shared};
+use futures::prelude::*;
+use reqwest::Method;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::oneshot;
+
+/// The most IDs the `users` and `tweets` lookup endpoints accept at once
+const MAX_BATCH_SIZE: usize = 100;
+
+type Params = BTreeMap<&'static str, BTreeSet<String>>;
+type BatchFuture = Shared<BoxFuture<'static, Result<Arc<BatchPayload>, Arc<Error>>>>;
+
+/// Coalesces single user and tweet lookups into batched `get_users` and `get_tweets`
+/// calls, in the manner of a DataLoader.
+///
+/// Lookups issued within [`window`](Self::window) of the first one are sent as a single
+/// request asking for the union of their fields and expansions, and every caller receives
+/// the part of the response concerning its ID. Lookups of an ID which is part of a batch
+/// in flight with all the requested fields share its response.
+///
+/// ```no_run
+/// use twitter_v2::authorization::BearerToken;
+/// use twitter_v2::query::UserField;
+/// use twitter_v2::TwitterApi;
+///
+/// # #[tokio::main]
+/// # async fn main() -> twitter_v2::Result<()> {
+/// let batcher = TwitterApi::new(BearerToken::new("")).batcher();
+/// let jack = batcher.get_user(12);
+/// let mut dev = batcher.get_user(2244994945);
+/// dev.user_fields([UserField::CreatedAt]);
+/// let (jack, dev) = futures::try_join!(jack.send(), dev.send())?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Batcher<A> {
+    client: TwitterApi<A>,
+    window: Duration,
+    max_batch_size: usize,
+    state: Arc<Mutex<BatcherState>>,
+}
+
+#[derive(Default)]
+struct BatcherState {
+    open: HashMap<&'static str, OpenBatch>,
+    in_flight: Vec<InFlightBatch>,
+    next_batch: u64,
+}
+
+struct OpenBatch {
+    batch: u64,
+    contents: Arc<Mutex<BatchContents>>,
+    future: BatchFuture,
+    /// Dropping the sender wakes the batch up before its window ends
+    _flush: oneshot::Sender<()>,
+}
+
+struct InFlightBatch {
+    batch: u64,
+    endpoint: &'static str,
+    contents: Arc<Mutex<BatchContents>>,
+    future: BatchFuture,
+}
+
+impl BatcherState {
+    /// Close the open batch of `endpoint` to further lookups and send it right away
+    fn send_open(&mut self, endpoint: &'static str) {
+        if let Some(open) = self.open.remove(endpoint) {
+            self.in_flight.push(InFlightBatch {
+                batch: open.batch,
+                endpoint,
+                contents: open.contents,
+                future: open.future,
+            });
+        }
+    }
+}
+
+#[derive(Default)]
+struct BatchContents {
+    ids: Vec<u64>,
+    params: Params,
+}
+
+impl BatchContents {
+    fn covers(&self, id: u64, params: &Params) -> bool {
+        self.ids.contains(&id)
+            && params.iter().all(|(key, values)| {
+                self.params
+                    .get(key)
+                    .map_or(values.is_empty(), |batched| batched.is_superset(values))
+            })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct BatchPayload {
+    data: Option<Vec<Value>>,
+    includes: Option<Expansions>,
+    errors: Option<Vec<Value>>,
+}
+
+impl<A> Batcher<A>
+where
+    A: Authorization + Send + Sync + 'static,
+{
+    pub(crate) fn new(client: &TwitterApi<A>) -> Self {
+        Self {
+            client: client.clone(),
+            window: Duration::from_millis(10),
+            max_batch_size: MAX_BATCH_SIZE,
+            state: Default::default(),
+        }
+    }
+
+    /// How long to collect lookups before sending a batch. Defaults to 10 milliseconds.
+    pub fn window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Send a batch as soon as it holds `max_batch_size` IDs. Defaults to and is capped at
+    /// 100.
+    pub fn max_batch_size(mut self, max_batch_size: usize) -> Self {
+        self.max_batch_size = max_batch_size.clamp(1, MAX_BATCH_SIZE);
+        self
+    }
+
+    pub fn get_user(&self, id: impl Into<UserId>) -> BatchLookup<'_, A, User> {
+        BatchLookup::new(self, "users", id.into().as_u64())
+    }
+
+    pub fn get_tweet(&self, id: impl Into<TweetId>) -> BatchLookup<'_, A, Tweet> {
+        BatchLookup::new(self, "tweets", id.into().as_u64())
+    }
+
+    fn load(&self, endpoint: &'static str, id: u64, params: Params) -> BatchFuture {
+        let mut state = self.state.lock().unwrap();
+        if let Some(in_flight) = state.in_flight.iter().find(|batch| {
+            batch.endpoint == endpoint && batch.contents.lock().unwrap().covers(id, &params)
+        }) {
+            return in_flight.future.clone();
+        }
+        if let Some(open) = state.open.get_mut(endpoint) {
+            let future = open.future.clone();
+            let is_full = {
+                let mut contents = open.contents.lock().unwrap();
+                if !contents.ids.contains(&id) {
+                    contents.ids.push(id);
+                }
+                for (key, values) in params {
+                    contents.params.entry(key).or_default().extend(values);
+                }
+                contents.ids.len() >= self.max_batch_size
+            };
+            if is_full {
+                state.send_open(endpoint);
+            }
+            return future;
+        }
+
+        let batch = state.next_batch;
+        state.next_batch += 1;
+        let contents = Arc::new(Mutex::new(BatchContents {
+            ids: vec![id],
+            params,
+        }));
+        let (flush, flushed) = oneshot::channel();
+        let future = self
+            .run_batch(batch, endpoint, contents.clone(), flushed)
+            .boxed()
+            .shared();
+        state.open.insert(
+            endpoint,
+            OpenBatch {
+                batch,
+                contents,
+                future: future.clone(),
+                _flush: flush,
+            },
+        );
+        if self.max_batch_size <= 1 {
+            state.send_open(endpoint);
+        }
+        future
+    }
+
+    fn run_batch(
+        &self,
+        batch: u64,
+        endpoint: &'static str,
+        contents: Arc<Mutex<BatchContents>>,
+        flushed: oneshot::Receiver<()>,
+    ) -> impl Future<Output = Result<Arc<BatchPayload>, Arc<Error>>> + Send + 'static {
+        let client = self.client.clone();
+        let state = self.state.clone();
+        let window = self.window;
+        async move {
+            let _ = future::select(Box::pin(tokio::time::sleep(window)), flushed).await;
+            let result = async {
+                let url = {
+                    let mut state = state.lock().unwrap();
+                    if state
+                        .open
+                        .get(endpoint)
+                        .is_some_and(|open| open.batch == batch)
+                    {
+                        state.send_open(endpoint);
+                    }
+                    let contents = contents.lock().unwrap();
+                    let mut url = client.url(endpoint)?;
+                    url.append_query_seq("ids", contents.ids.iter());
+                    for (key, values) in contents.params.iter() {
+                        url.append_query_seq(key, values);
+                    }
+                    url
+                };
+                let response = client
+                    .execute(client.request(Method::GET, url).build()?)
+                    .await?;
+                Ok(Arc::new(response.json::<BatchPayload>().await?))
+            }
+            .await
+            .map_err(Arc::new);
+            state
+                .lock()
+                .unwrap()
+                .in_flight
+                .retain(|in_flight| in_flight.batch != batch);
+            result
+        }
+    }
+}
+
+impl<A> Clone for Batcher<A> {
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client.clone(),
+            window: self.window,
+            max_batch_size: self.max_batch_size,
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<A> fmt::Debug for Batcher<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Batcher")
+            .field("window", &self.window)
+            .field("max_batch_size", &self.max_batch_size)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A single lookup queued with a [`Batcher`]
+pub struct BatchLookup<'a, A, T> {
+    batcher: &'a Batcher<A>,
+    endpoint: &'static str,
+    id: u64,
+    params: Params,
+    return_ty: PhantomData<T>,
+}
+
+impl<'a, A, T> BatchLookup<'a, A, T>
+where
+    A: Authorization + Send + Sync + 'static,
+    T: DeserializeOwned,
+{
+    fn new(batcher: &'a Batcher<A>, endpoint: &'static str, id: u64) -> Self {
+        Self {
+            batcher,
+            endpoint,
+            id,
+            params: Params::new(),
+            return_ty: PhantomData,
+        }
+    }
+
+    fn param(&mut self, key: &'static str, values: impl IntoIterator<Item = impl ToString>) {
+        self.params
+            .entry(key)
+            .or_default()
+            .extend(values.into_iter().map(|value| value.to_string()));
+    }
+
+    pub fn tweet_fields(&mut self, fields: impl IntoIterator<Item = TweetField>) -> &mut Self {
+        self.param("tweet.fields", fields);
+        self
+    }
+
+    pub fn user_fields(&mut self, fields: impl IntoIterator<Item = UserField>) -> &mut Self {
+        self.param("user.fields", fields);
+        self
+    }
+
+    /// The lookup's `data` is `None` if the resource does not exist, in which case
+    /// `errors` holds the error reported for it
+    pub async fn send(&self) -> Result<ApiPayload<T, ()>> {
+        let payload = self
+            .batcher
+            .load(self.endpoint, self.id, self.params.clone())
+            .await
+            .map_err(|err| clone_error(&err))?;
+        let id = self.id.to_string();
+        let data = payload
+            .data
+            .iter()
+            .flatten()
+            .find(|item| item["id"].as_str() == Some(id.as_str()))
+            .map(|item| serde_json::from_value(item.clone()))
+            .transpose()?;
+        let errors = payload
+            .errors
+            .iter()
+            .flatten()
+            .filter(|err| {
+                err["resource_id"].as_str() == Some(id.as_str())
+                    || err["value"].as_str() == Some(id.as_str())
+            })
+            .map(|err| serde_json::from_value::<ApiError>(err.clone()))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(ApiPayload {
+            data,
+            meta: None,
+            includes: payload.includes.clone(),
+            errors: if errors.is_empty() {
+                None
+            } else {
+                Some(errors)
+            },
+        })
+    }
+}
+
+impl<'a, A> BatchLookup<'a, A, User>
+where
+    A: Authorization + Send + Sync + 'static,
+{
+    pub fn expansions(&mut self, expansions: impl IntoIterator<Item = UserExpansion>) -> &mut Self {
+        self.param("expansions", expansions);
+        self
+    }
+}
+
+impl<'a, A> BatchLookup<'a, A, Tweet>
+where
+    A: Authorization + Send + Sync + 'static,
+{
+    pub fn expansions(
+        &mut self,
+        expansions: impl IntoIterator<Item = TweetExpansion>,
+    ) -> &mut Self {
+        self.param("expansions", expansions);
+        self
+    }
+
+    pub fn media_fields(&mut self, fields: impl IntoIterator<Item = MediaField>) -> &mut Self {
+        self.param("media.fields", fields);
+        self
+    }
+
+    pub fn place_fields(&mut self, fields: impl IntoIterator<Item = PlaceField>) -> &mut Self {
+        self.param("place.fields", fields);
+        self
+    }
+
+    pub fn poll_fields(&mut self, fields: impl IntoIterator<Item = PollField>) -> &mut Self {
+        self.param("poll.fields", fields);
+        self
+    }
+}
+
+impl<'a, A, T> fmt::Debug for BatchLookup<'a, A, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BatchLookup")
+            .field("endpoint", &self.endpoint)
+            .field("id", &self.id)
+            .field("params", &self.params)
+            .finish()
+    }
+}
+
+/// Every caller of a batch receives its error. Only API errors keep their type.
+fn clone_error(err: &Error) -> Error {
+    match err {
+        Error::Api(err) => Error::Api(err.clone()),
+        err => Error::custom(err),
+    }
+}
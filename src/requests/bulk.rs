@@ -0,0 +1,395 @@
+use super::follow_graph::fetch_all_users;
+use crate::api::TwitterApi;
+use crate::api_result::{ApiError, ApiPayload, ApiResponse};
+use crate::authorization::Authorization;
+use crate::error::{Error, Result};
+use crate::id::{ListId, UserId};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::time::Duration;
+use time::OffsetDateTime;
+
+/// How often a rate limited target is retried before giving up
+const RATE_LIMIT_RETRIES: usize = 3;
+
+/// The window of the write limits of the relationship endpoints
+const LIMIT_WINDOW: Duration = Duration::from_secs(15 * 60);
+
+/// A change of relationship applied to many users. The ID is the acting user, or the list
+/// for list memberships.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkAction {
+    Follow(UserId),
+    Unfollow(UserId),
+    Block(UserId),
+    Unblock(UserId),
+    Mute(UserId),
+    Unmute(UserId),
+    AddListMember(ListId),
+    RemoveListMember(ListId),
+}
+
+impl BulkAction {
+    /// The documented number of requests allowed per 15 minute window
+    pub fn write_limit(&self) -> usize {
+        match self {
+            Self::AddListMember(_) | Self::RemoveListMember(_) => 300,
+            _ => 50,
+        }
+    }
+
+    /// Whether the action creates the relationship rather than removing it
+    fn creates(&self) -> bool {
+        matches!(
+            self,
+            Self::Follow(_) | Self::Block(_) | Self::Mute(_) | Self::AddListMember(_)
+        )
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkOutcome {
+    Applied,
+    /// The target was known to be in the desired state, no request was sent
+    AlreadyApplied,
+    /// The target is a protected account which has to accept the follow request
+    Pending,
+    NotFound,
+    /// The action was not sent because of a dry run
+    WouldApply,
+    Failed(String),
+}
+
+impl BulkOutcome {
+    /// Whether the target is left out when the report is passed to another run
+    pub fn is_settled(&self) -> bool {
+        !matches!(self, Self::WouldApply | Self::Failed(_))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct BulkEntry {
+    pub target: UserId,
+    pub outcome: BulkOutcome,
+}
+
+/// The outcome of every target of a [`BulkRelationship`] run, in the order they were
+/// processed
+#[derive(Clone, Debug, Default, Serialize, Deserialize, Eq, PartialEq)]
+pub struct BulkReport {
+    pub entries: Vec<BulkEntry>,
+    /// When the requests of the current write limit window were sent, so that a resumed
+    /// run stays within the limit
+    #[serde(
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        with = "crate::utils::serde::vec_rfc3339"
+    )]
+    sent: Vec<OffsetDateTime>,
+}
+
+impl BulkReport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The latest outcome of `target`
+    pub fn outcome(&self, target: impl Into<UserId>) -> Option<&BulkOutcome> {
+        let target = target.into();
+        self.entries
+            .iter()
+            .rev()
+            .find(|entry| entry.target == target)
+            .map(|entry| &entry.outcome)
+    }
+
+    pub fn count(&self, outcome: &BulkOutcome) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| &entry.outcome == outcome)
+            .count()
+    }
+
+    fn record(&mut self, target: UserId, outcome: BulkOutcome) {
+        self.entries.retain(|entry| entry.target != target);
+        self.entries.push(BulkEntry { target, outcome });
+    }
+}
+
+/// Applies a [`BulkAction`] to many users, one request at a time and within the write limit
+/// of the endpoint.
+///
+/// Each target gets an entry in a [`BulkReport`]. Failures concerning a single target are
+/// recorded and the run moves on, while other errors end the run. Passing the same report
+/// to a later run resumes it: settled targets are skipped, failed ones are retried and the
+/// requests sent recently still count towards the write limit.
+///
+/// ```no_run
+/// use twitter_v2::authorization::BearerToken;
+/// use twitter_v2::requests::{BulkAction, BulkOutcome, BulkReport};
+/// use twitter_v2::TwitterApi;
+///
+/// # #[tokio::main]
+/// # async fn main() -> twitter_v2::Result<()> {
+/// let api = TwitterApi::new(BearerToken::new(""));
+/// let mut report = BulkReport::new();
+/// api.bulk(BulkAction::AddListMember(1234.into()))
+///     .run([12, 2244994945], &mut report)
+///     .await?;
+/// println!("{} added", report.count(&BulkOutcome::Applied));
+/// # Ok(())
+/// # }
+/// ```
+pub struct BulkRelationship<A> {
+    client: TwitterApi<A>,
+    action: BulkAction,
+    dry_run: bool,
+    limit: usize,
+    window: Duration,
+    rate_limit_wait: Duration,
+    existing: HashSet<UserId>,
+    fetch_existing: bool,
+}
+
+impl<A> BulkRelationship<A>
+where
+    A: Authorization,
+{
+    pub(crate) fn new(client: &TwitterApi<A>, action: BulkAction) -> Self {
+        Self {
+            // a run acts on the current state of the relationships
+            client: client.clone().without_cache(),
+            action,
+            dry_run: false,
+            limit: action.write_limit(),
+            window: LIMIT_WINDOW,
+            rate_limit_wait: LIMIT_WINDOW,
+            existing: HashSet::new(),
+            fetch_existing: false,
+        }
+    }
+    /// Record what would be done without sending any request
+    pub fn dry_run(&mut self, dry_run: bool) -> &mut Self {
+        self.dry_run = dry_run;
+        self
+    }
+    /// Send at most `limit` requests per `window`. Defaults to the documented write limit
+    /// of the endpoint per 15 minutes.
+    pub fn limit(&mut self, limit: usize, window: Duration) -> &mut Self {
+        self.limit = limit.max(1);
+        self.window = window;
+        self
+    }
+    /// How long to wait before retrying a rate limited target if the response does not tell
+    /// when the limit resets. Defaults to 15 minutes.
+    pub fn rate_limit_wait(&mut self, rate_limit_wait: Duration) -> &mut Self {
+        self.rate_limit_wait = rate_limit_wait;
+        self
+    }
+    /// Users known to be in the desired state already, e.g. the accounts of a
+    /// [`FollowSnapshot`](super::FollowSnapshot) or the current members of a list
+    pub fn existing(&mut self, existing: impl IntoIterator<Item = impl Into<UserId>>) -> &mut Self {
+        self.existing = existing.into_iter().map(Into::into).collect();
+        self
+    }
+    /// Look up which targets are in the desired state already before the run: the accounts
+    /// followed, blocked or muted by the acting user, or the members of the list. Takes one
+    /// request per 1000 accounts or 100 members.
+    pub fn fetch_existing(&mut self, fetch_existing: bool) -> &mut Self {
+        self.fetch_existing = fetch_existing;
+        self
+    }
+
+    /// Apply the action to every target not yet settled in `report`, recording the outcomes
+    pub async fn run(
+        &self,
+        targets: impl IntoIterator<Item = impl Into<UserId>>,
+        report: &mut BulkReport,
+    ) -> Result<()> {
+        let settled = report
+            .entries
+            .iter()
+            .filter(|entry| entry.outcome.is_settled())
+            .map(|entry| entry.target)
+            .collect::<HashSet<_>>();
+        let related = if self.fetch_existing {
+            Some(self.fetch_related().await?)
+        } else {
+            None
+        };
+        for target in targets.into_iter().map(Into::into) {
+            if settled.contains(&target) {
+                continue;
+            }
+            let existing = self.existing.contains(&target)
+                || matches!(&related, Some(related) if related.contains(&target) == self.action.creates());
+            let outcome = if existing {
+                BulkOutcome::AlreadyApplied
+            } else if self.dry_run {
+                BulkOutcome::WouldApply
+            } else {
+                self.send(target, &mut report.sent).await?
+            };
+            report.record(target, outcome);
+        }
+        Ok(())
+    }
+
+    /// The users related to the acting user or list by the action
+    async fn fetch_related(&self) -> Result<HashSet<UserId>> {
+        let users = fetch_all_users(
+            |pagination_token| {
+                let mut req = match self.action {
+                    BulkAction::Follow(id) | BulkAction::Unfollow(id) => {
+                        self.client.get_user_following(id)
+                    }
+                    BulkAction::Block(id) | BulkAction::Unblock(id) => {
+                        self.client.get_user_blocking(id)
+                    }
+                    BulkAction::Mute(id) | BulkAction::Unmute(id) => {
+                        self.client.get_user_muting(id)
+                    }
+                    BulkAction::AddListMember(id) | BulkAction::RemoveListMember(id) => {
+                        self.client.get_list_members(id)
+                    }
+                };
+                req.max_results(match self.action {
+                    BulkAction::AddListMember(_) | BulkAction::RemoveListMember(_) => 100,
+                    _ => 1000,
+                });
+                if let Some(token) = pagination_token {
+                    req.pagination_token(token);
+                }
+                req
+            },
+            Duration::ZERO,
+            self.rate_limit_wait,
+        )
+        .await?;
+        Ok(users.into_iter().map(|user| user.id).collect())
+    }
+
+    /// Wait until another request fits into the write limit and count it
+    async fn reserve(&self, sent: &mut Vec<OffsetDateTime>) {
+        loop {
+            let now = OffsetDateTime::now_utc();
+            sent.retain(|at| *at + self.window > now);
+            if sent.len() < self.limit {
+                sent.push(now);
+                return;
+            }
+            let wait = sent[sent.len() - self.limit] + self.window - now;
+            tokio::time::sleep(wait.try_into().unwrap_or_default()).await;
+        }
+    }
+
+    async fn send(&self, target: UserId, sent: &mut Vec<OffsetDateTime>) -> Result<BulkOutcome> {
+        let mut retries = 0;
+        loop {
+            self.reserve(sent).await;
+            match self.apply(target).await {
+                Err(Error::Api(err))
+                    if err.status == StatusCode::TOO_MANY_REQUESTS
+                        && retries < RATE_LIMIT_RETRIES =>
+                {
+                    retries += 1;
+                    let wait = match err.rate_limit_reset {
+                        Some(reset) => (reset - OffsetDateTime::now_utc())
+                            .try_into()
+                            .unwrap_or_default(),
+                        None => self.rate_limit_wait,
+                    };
+                    tokio::time::sleep(wait).await;
+                }
+                Err(Error::Api(err)) if is_not_found(&err) => return Ok(BulkOutcome::NotFound),
+                Err(Error::Api(err)) if err.status.is_client_error() => {
+                    return Ok(BulkOutcome::Failed(err.to_string()))
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn apply(&self, target: UserId) -> Result<BulkOutcome> {
+        let client = &self.client;
+        Ok(match self.action {
+            BulkAction::Follow(id) => {
+                outcome(client.post_user_following(id, target).await?, |following| {
+                    if following.pending_follow {
+                        BulkOutcome::Pending
+                    } else {
+                        applied(following.following)
+                    }
+                })
+            }
+            BulkAction::Unfollow(id) => outcome(
+                client.delete_user_following(id, target).await?,
+                |following| applied(!following.following),
+            ),
+            BulkAction::Block(id) => {
+                outcome(client.post_user_blocking(id, target).await?, |blocking| {
+                    applied(blocking.blocking)
+                })
+            }
+            BulkAction::Unblock(id) => {
+                outcome(client.delete_user_blocking(id, target).await?, |blocking| {
+                    applied(!blocking.blocking)
+                })
+            }
+            BulkAction::Mute(id) => outcome(client.post_user_muting(id, target).await?, |muting| {
+                applied(muting.muting)
+            }),
+            BulkAction::Unmute(id) => {
+                outcome(client.delete_user_muting(id, target).await?, |muting| {
+                    applied(!muting.muting)
+                })
+            }
+            BulkAction::AddListMember(id) => {
+                outcome(client.post_list_member(id, target).await?, |member| {
+                    applied(member.is_member)
+                })
+            }
+            BulkAction::RemoveListMember(id) => {
+                outcome(client.delete_list_member(id, target).await?, |member| {
+                    applied(!member.is_member)
+                })
+            }
+        })
+    }
+}
+
+fn applied(applied: bool) -> BulkOutcome {
+    if applied {
+        BulkOutcome::Applied
+    } else {
+        BulkOutcome::Failed("the relationship did not change".to_string())
+    }
+}
+
+fn is_not_found(err: &ApiError) -> bool {
+    err.status == StatusCode::NOT_FOUND || err.kind.ends_with("/resource-not-found")
+}
+
+/// The outcome of a response, whose errors concern the target if there is no data
+fn outcome<A, T>(
+    response: ApiResponse<A, T, ()>,
+    state: impl FnOnce(T) -> BulkOutcome,
+) -> BulkOutcome {
+    let ApiPayload { data, errors, .. } = response.into_payload();
+    if let Some(data) = data {
+        return state(data);
+    }
+    let errors = errors.unwrap_or_default();
+    if errors.iter().any(is_not_found) {
+        BulkOutcome::NotFound
+    } else {
+        BulkOutcome::Failed(
+            errors
+                .first()
+                .map_or_else(|| "no data returned".to_string(), ToString::to_string),
+        )
+    }
+}
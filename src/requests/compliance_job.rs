@@ -4,6 +4,7 @@ use crate::authorization::Authorization;
 use crate::data::{ComplianceJob, ComplianceJobKind};
 use reqwest::Method;
 use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
 use url::Url;
 
 #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
@@ -18,10 +19,22 @@ pub struct DraftComplianceJob {
 }
 
 #[derive(Debug)]
-pub struct ComplianceJobBuilder<A> {
-    client: TwitterApi<A>,
+pub struct ComplianceJobBuilder<A, C = TwitterApi<A>> {
+    client: C,
     url: Url,
     job: DraftComplianceJob,
+    auth_ty: PhantomData<A>,
+}
+
+impl<A, C> ComplianceJobBuilder<A, C> {
+    pub fn text(&mut self, name: impl ToString) -> &mut Self {
+        self.job.name = Some(name.to_string());
+        self
+    }
+    pub fn resumable(&mut self, resumable: bool) -> &mut Self {
+        self.job.resumable = Some(resumable);
+        self
+    }
 }
 
 impl<A> ComplianceJobBuilder<A>
@@ -37,16 +50,9 @@ where
                 name: None,
                 resumable: None,
             },
+            auth_ty: PhantomData,
         }
     }
-    pub fn text(&mut self, name: impl ToString) -> &mut Self {
-        self.job.name = Some(name.to_string());
-        self
-    }
-    pub fn resumable(&mut self, resumable: bool) -> &mut Self {
-        self.job.resumable = Some(resumable);
-        self
-    }
     pub async fn send(&self) -> ApiResult<A, ComplianceJob, ()> {
         self.client
             .send(
@@ -58,12 +64,45 @@ where
     }
 }
 
-impl<A> Clone for ComplianceJobBuilder<A> {
+#[cfg(feature = "blocking")]
+impl<A> ComplianceJobBuilder<A> {
+    pub(crate) fn into_blocking(
+        self,
+        client: &crate::blocking::TwitterApi<A>,
+    ) -> ComplianceJobBuilder<A, crate::blocking::TwitterApi<A>> {
+        ComplianceJobBuilder {
+            client: client.clone(),
+            url: self.url,
+            job: self.job,
+            auth_ty: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl<A> ComplianceJobBuilder<A, crate::blocking::TwitterApi<A>>
+where
+    A: Authorization,
+{
+    pub fn send(&self) -> ApiResult<A, ComplianceJob, ()> {
+        self.client.send(
+            self.client
+                .request(Method::POST, self.url.clone())
+                .json(&self.job),
+        )
+    }
+}
+
+impl<A, C> Clone for ComplianceJobBuilder<A, C>
+where
+    C: Clone,
+{
     fn clone(&self) -> Self {
         Self {
             client: self.client.clone(),
             url: self.url.clone(),
             job: self.job.clone(),
+            auth_ty: PhantomData,
         }
     }
 }
@@ -0,0 +1,189 @@
+use crate::api::TwitterApi;
+use crate::api_result::ApiResponse;
+use crate::authorization::Authorization;
+use crate::data::{ComplianceAction, ComplianceJob, ComplianceJobKind, ComplianceJobStatus};
+use crate::error::{Error, Result};
+use crate::id::{ComplianceJobId, NumericId};
+use crate::utils::JsonStream;
+use futures::prelude::*;
+use reqwest::header::CONTENT_TYPE;
+use reqwest::{Body, Method};
+use std::time::{Duration, Instant};
+
+/// Drives a batch compliance job from start to finish: creates the job, uploads the IDs
+/// to check, waits for Twitter to process them and streams back the resulting
+/// [`ComplianceAction`]s.
+#[derive(Debug)]
+pub struct ComplianceJobRunner<A> {
+    client: TwitterApi<A>,
+    kind: ComplianceJobKind,
+    name: Option<String>,
+    resumable: Option<bool>,
+    poll_interval: Duration,
+    max_poll_interval: Duration,
+    timeout: Option<Duration>,
+}
+
+impl<A> ComplianceJobRunner<A>
+where
+    A: Authorization,
+{
+    pub(crate) fn new(client: &TwitterApi<A>, kind: ComplianceJobKind) -> Self {
+        Self {
+            client: client.clone(),
+            kind,
+            name: None,
+            resumable: None,
+            poll_interval: Duration::from_secs(15),
+            max_poll_interval: Duration::from_secs(120),
+            timeout: None,
+        }
+    }
+    pub fn name(&mut self, name: impl ToString) -> &mut Self {
+        self.name = Some(name.to_string());
+        self
+    }
+    pub fn resumable(&mut self, resumable: bool) -> &mut Self {
+        self.resumable = Some(resumable);
+        self
+    }
+    /// Delay before the first status check. Doubles after every check that finds the
+    /// job still running, up to [`max_poll_interval`](Self::max_poll_interval).
+    pub fn poll_interval(&mut self, poll_interval: Duration) -> &mut Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+    pub fn max_poll_interval(&mut self, max_poll_interval: Duration) -> &mut Self {
+        self.max_poll_interval = max_poll_interval;
+        self
+    }
+    /// Give up waiting for the job after `timeout`. Unbounded by default.
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub async fn create(&self) -> Result<ComplianceJob> {
+        let mut builder = self.client.post_compliance_job(self.kind);
+        if let Some(name) = self.name.as_ref() {
+            builder.text(name);
+        }
+        if let Some(resumable) = self.resumable {
+            builder.resumable(resumable);
+        }
+        into_job(builder.send().await?)
+    }
+
+    /// Upload the IDs to check to the job's signed upload URL, one per line.
+    pub async fn upload(
+        &self,
+        job: &ComplianceJob,
+        ids: impl IntoIterator<Item = impl Into<NumericId>>,
+    ) -> Result<()> {
+        let body = ids.into_iter().fold(String::new(), |mut body, id| {
+            body.push_str(&id.into().to_string());
+            body.push('\n');
+            body
+        });
+        self.put_upload(job, body.into()).await
+    }
+
+    /// Like [`upload`](Self::upload) but streams the IDs instead of buffering them.
+    pub async fn upload_stream<S>(&self, job: &ComplianceJob, ids: S) -> Result<()>
+    where
+        S: Stream<Item = NumericId> + Send + 'static,
+    {
+        let lines = ids.map(|id| Ok::<_, std::convert::Infallible>(format!("{id}\n")));
+        self.put_upload(job, Body::wrap_stream(lines)).await
+    }
+
+    async fn put_upload(&self, job: &ComplianceJob, body: Body) -> Result<()> {
+        let req = self
+            .client
+            .request(Method::PUT, job.upload_url.clone())
+            .header(CONTENT_TYPE, "text/plain")
+            .body(body)
+            .build()?;
+        self.client.execute_presigned(req).await?;
+        Ok(())
+    }
+
+    /// Poll the job with exponential backoff until it is complete.
+    pub async fn wait(&self, id: impl Into<ComplianceJobId>) -> Result<ComplianceJob> {
+        let id = id.into();
+        let started = Instant::now();
+        let mut interval = self.poll_interval;
+        loop {
+            let job = into_job(self.client.get_compliance_job(id).await?)?;
+            match job.status {
+                Some(ComplianceJobStatus::Complete) => return Ok(job),
+                Some(ComplianceJobStatus::Failed) => return Err(Error::ComplianceJobFailed(id)),
+                Some(ComplianceJobStatus::Expired) => return Err(Error::ComplianceJobExpired(id)),
+                Some(ComplianceJobStatus::Unknown) => {
+                    return Err(Error::ComplianceJobUnknownStatus(id))
+                }
+                _ => {}
+            }
+            if let Some(timeout) = self.timeout {
+                let elapsed = started.elapsed();
+                if elapsed >= timeout {
+                    return Err(Error::ComplianceJobTimeout(id));
+                }
+                interval = interval.min(timeout - elapsed);
+            }
+            tokio::time::sleep(interval).await;
+            interval = (interval * 2).min(self.max_poll_interval);
+        }
+    }
+
+    /// Stream the result file of a completed job.
+    pub async fn download(
+        &self,
+        job: &ComplianceJob,
+    ) -> Result<impl Stream<Item = Result<ComplianceAction>>> {
+        let req = self
+            .client
+            .request(Method::GET, job.download_url.clone())
+            .build()?;
+        Ok(JsonStream::new(
+            self.client.execute_presigned(req).await?.bytes_stream(),
+        ))
+    }
+
+    /// Create a job, upload `ids`, wait for it to complete and stream the results.
+    pub async fn run(
+        &self,
+        ids: impl IntoIterator<Item = impl Into<NumericId>>,
+    ) -> Result<impl Stream<Item = Result<ComplianceAction>>> {
+        let job = self.create().await?;
+        self.upload(&job, ids).await?;
+        let job = self.wait(job.id).await?;
+        self.download(&job).await
+    }
+}
+
+impl<A> Clone for ComplianceJobRunner<A> {
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client.clone(),
+            kind: self.kind,
+            name: self.name.clone(),
+            resumable: self.resumable,
+            poll_interval: self.poll_interval,
+            max_poll_interval: self.max_poll_interval,
+            timeout: self.timeout,
+        }
+    }
+}
+
+fn into_job<A>(response: ApiResponse<A, ComplianceJob, ()>) -> Result<ComplianceJob> {
+    let payload = response.into_payload();
+    match payload.data {
+        Some(job) => Ok(job),
+        None => Err(payload
+            .errors
+            .and_then(|errors| errors.into_iter().next())
+            .map(Error::Api)
+            .unwrap_or_else(|| Error::custom("No compliance job in response"))),
+    }
+}
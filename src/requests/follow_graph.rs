@@ -0,0 +1,250 @@
+use crate::api::TwitterApi;
+use crate::authorization::Authorization;
+use crate::data::User;
+use crate::error::{Error, Result};
+use crate::id::UserId;
+use crate::meta::ResultCountMeta;
+use crate::query::GetRelatedUsersRequestBuilder;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use time::OffsetDateTime;
+
+/// How often a rate limited page is retried before giving up
+const RATE_LIMIT_RETRIES: usize = 3;
+
+type Configure<A> =
+    dyn Fn(&mut GetRelatedUsersRequestBuilder<A, Vec<User>, ResultCountMeta>) + Send + Sync;
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum FollowDirection {
+    /// The accounts following the user
+    Followers,
+    /// The accounts the user follows
+    Following,
+}
+
+/// All followers or followings of a user at one point in time
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct FollowSnapshot {
+    pub user_id: UserId,
+    pub direction: FollowDirection,
+    #[serde(with = "time::serde::rfc3339")]
+    pub taken_at: OffsetDateTime,
+    pub users: Vec<User>,
+}
+
+impl FollowSnapshot {
+    pub fn ids(&self) -> HashSet<UserId> {
+        self.users.iter().map(|user| user.id).collect()
+    }
+
+    pub fn contains(&self, id: impl Into<UserId>) -> bool {
+        let id = id.into();
+        self.users.iter().any(|user| user.id == id)
+    }
+
+    /// The accounts gained and lost between this snapshot and a `later` one of the same user
+    /// and direction
+    pub fn diff(&self, later: &FollowSnapshot) -> Result<FollowDiff> {
+        if self.user_id != later.user_id || self.direction != later.direction {
+            return Err(Error::custom(format!(
+                "cannot diff {:?} of {} with {:?} of {}",
+                self.direction, self.user_id, later.direction, later.user_id
+            )));
+        }
+        let (before, after) = (self.ids(), later.ids());
+        Ok(FollowDiff {
+            user_id: self.user_id,
+            direction: self.direction,
+            since: self.taken_at,
+            until: later.taken_at,
+            gained: later
+                .users
+                .iter()
+                .filter(|user| !before.contains(&user.id))
+                .cloned()
+                .collect(),
+            lost: self
+                .users
+                .iter()
+                .filter(|user| !after.contains(&user.id))
+                .cloned()
+                .collect(),
+        })
+    }
+}
+
+/// The changes to the followers or followings of a user between two snapshots. Accounts
+/// were gained or lost at some point between `since` and `until`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct FollowDiff {
+    pub user_id: UserId,
+    pub direction: FollowDirection,
+    #[serde(with = "time::serde::rfc3339")]
+    pub since: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub until: OffsetDateTime,
+    pub gained: Vec<User>,
+    pub lost: Vec<User>,
+}
+
+impl FollowDiff {
+    pub fn is_empty(&self) -> bool {
+        self.gained.is_empty() && self.lost.is_empty()
+    }
+}
+
+/// Takes complete snapshots of the followers or followings of a user.
+///
+/// Pages are requested [`min_interval`](Self::min_interval) apart, and a rate limited page
+/// is retried after [`rate_limit_wait`](Self::rate_limit_wait).
+///
+/// ```no_run
+/// use twitter_v2::authorization::BearerToken;
+/// use twitter_v2::requests::{FollowDirection, FollowSnapshot};
+/// use twitter_v2::TwitterApi;
+///
+/// # #[tokio::main]
+/// # async fn main() -> twitter_v2::Result<()> {
+/// let api = TwitterApi::new(BearerToken::new(""));
+/// let yesterday: FollowSnapshot =
+///     serde_json::from_str(&std::fs::read_to_string("followers.json")?)?;
+/// let today = api
+///     .follow_graph(2244994945, FollowDirection::Followers)
+///     .snapshot()
+///     .await?;
+/// let diff = yesterday.diff(&today)?;
+/// println!("+{} -{}", diff.gained.len(), diff.lost.len());
+/// std::fs::write("followers.json", serde_json::to_string(&today)?)?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct FollowGraph<A> {
+    client: TwitterApi<A>,
+    user_id: UserId,
+    direction: FollowDirection,
+    min_interval: Duration,
+    rate_limit_wait: Duration,
+    max_results: usize,
+    configure: Option<Arc<Configure<A>>>,
+}
+
+impl<A> FollowGraph<A>
+where
+    A: Authorization,
+{
+    pub(crate) fn new(
+        client: &TwitterApi<A>,
+        user_id: impl Into<UserId>,
+        direction: FollowDirection,
+    ) -> Self {
+        Self {
+            // the graph must reflect the current follows
+            client: client.clone().without_cache(),
+            user_id: user_id.into(),
+            direction,
+            min_interval: Duration::from_secs(60),
+            rate_limit_wait: Duration::from_secs(15 * 60),
+            max_results: 1000,
+            configure: None,
+        }
+    }
+    /// The minimum time between two pages. Defaults to 1 minute, the rate limit of the
+    /// follows endpoints.
+    pub fn min_interval(&mut self, min_interval: Duration) -> &mut Self {
+        self.min_interval = min_interval;
+        self
+    }
+    /// How long to wait before retrying a rate limited page. Defaults to 15 minutes.
+    pub fn rate_limit_wait(&mut self, rate_limit_wait: Duration) -> &mut Self {
+        self.rate_limit_wait = rate_limit_wait;
+        self
+    }
+    /// The page size, from 1 to 1000. Defaults to 1000.
+    pub fn max_results(&mut self, max_results: usize) -> &mut Self {
+        self.max_results = max_results;
+        self
+    }
+    /// Set fields and expansions of every request
+    pub fn configure(
+        &mut self,
+        configure: impl Fn(&mut GetRelatedUsersRequestBuilder<A, Vec<User>, ResultCountMeta>)
+            + Send
+            + Sync
+            + 'static,
+    ) -> &mut Self {
+        self.configure = Some(Arc::new(configure));
+        self
+    }
+
+    fn page_request(
+        &self,
+        pagination_token: Option<&str>,
+    ) -> GetRelatedUsersRequestBuilder<A, Vec<User>, ResultCountMeta> {
+        let mut req = match self.direction {
+            FollowDirection::Followers => self.client.get_user_followers(self.user_id),
+            FollowDirection::Following => self.client.get_user_following(self.user_id),
+        };
+        req.max_results(self.max_results);
+        if let Some(configure) = &self.configure {
+            configure(&mut req);
+        }
+        if let Some(token) = pagination_token {
+            req.pagination_token(token);
+        }
+        req
+    }
+
+    /// Fetch every page of followers or followings
+    pub async fn snapshot(&self) -> Result<FollowSnapshot> {
+        let taken_at = OffsetDateTime::now_utc();
+        let users = fetch_all_users(
+            |pagination_token| self.page_request(pagination_token),
+            self.min_interval,
+            self.rate_limit_wait,
+        )
+        .await?;
+        Ok(FollowSnapshot {
+            user_id: self.user_id,
+            direction: self.direction,
+            taken_at,
+            users,
+        })
+    }
+}
+
+/// Fetch every page of a users endpoint, `min_interval` apart and retrying rate limited
+/// pages after `rate_limit_wait`
+pub(crate) async fn fetch_all_users<A: Authorization>(
+    page_request: impl Fn(Option<&str>) -> GetRelatedUsersRequestBuilder<A, Vec<User>, ResultCountMeta>,
+    min_interval: Duration,
+    rate_limit_wait: Duration,
+) -> Result<Vec<User>> {
+    let mut users = Vec::new();
+    let mut pagination_token = None;
+    loop {
+        let mut retries = 0;
+        let response = loop {
+            match page_request(pagination_token.as_deref()).send().await {
+                Err(Error::Api(err))
+                    if err.status == StatusCode::TOO_MANY_REQUESTS
+                        && retries < RATE_LIMIT_RETRIES =>
+                {
+                    retries += 1;
+                    tokio::time::sleep(rate_limit_wait).await;
+                }
+                result => break result?,
+            }
+        };
+        pagination_token = response.meta().and_then(|meta| meta.next_token.clone());
+        users.extend(response.into_data().unwrap_or_default());
+        if pagination_token.is_none() {
+            return Ok(users);
+        }
+        tokio::time::sleep(min_interval).await;
+    }
+}
@@ -1,26 +1,30 @@
 use serde::{Deserialize, Serialize};
 
 macro_rules! id_req {
-    ($pub:vis struct $class:ident { $field:ident }) => {
+    ($pub:vis struct $class:ident { $field:ident: $id:ty }) => {
         #[derive(Serialize, Deserialize, Clone, Copy, Debug)]
         pub struct $class {
-            $field: $crate::id::NumericId,
+            $field: $id,
         }
 
-        impl<T> From<T> for $class
-        where
-            T: $crate::id::IntoNumericId,
-        {
-            fn from(id: T) -> Self {
-                Self {
-                    $field: id.into_id(),
-                }
+        impl From<$id> for $class {
+            fn from(id: $id) -> Self {
+                Self { $field: id }
             }
         }
     };
 }
 
-id_req! { pub struct TweetId { tweet_id } }
-id_req! { pub struct TargetUserId { target_user_id } }
-id_req! { pub struct UserId { user_id } }
-id_req! { pub struct ListId { list_id } }
+id_req! { pub struct TweetIdRequest { tweet_id: crate::id::TweetId } }
+id_req! { pub struct TargetUserIdRequest { target_user_id: crate::id::UserId } }
+id_req! { pub struct UserIdRequest { user_id: crate::id::UserId } }
+id_req! { pub struct ListIdRequest { list_id: crate::id::ListId } }
+
+#[deprecated(note = "renamed to `TweetIdRequest`, `TweetId` is the ID itself")]
+pub type TweetId = TweetIdRequest;
+#[deprecated(note = "renamed to `TargetUserIdRequest`")]
+pub type TargetUserId = TargetUserIdRequest;
+#[deprecated(note = "renamed to `UserIdRequest`, `UserId` is the ID itself")]
+pub type UserId = UserIdRequest;
+#[deprecated(note = "renamed to `ListIdRequest`, `ListId` is the ID itself")]
+pub type ListId = ListIdRequest;
@@ -18,12 +18,27 @@ struct DraftList {
 }
 
 #[derive(Debug)]
-pub struct ListBuilder<A, T> {
-    client: TwitterApi<A>,
+pub struct ListBuilder<A, T, C = TwitterApi<A>> {
+    client: C,
     url: Url,
     method: Method,
     list: DraftList,
-    return_ty: PhantomData<T>,
+    return_ty: PhantomData<(A, T)>,
+}
+
+impl<A, T, C> ListBuilder<A, T, C> {
+    pub fn name(&mut self, name: impl ToString) -> &mut Self {
+        self.list.name = Some(name.to_string());
+        self
+    }
+    pub fn description(&mut self, description: impl ToString) -> &mut Self {
+        self.list.description = Some(description.to_string());
+        self
+    }
+    pub fn private(&mut self, private: bool) -> &mut Self {
+        self.list.private = Some(private);
+        self
+    }
 }
 
 impl<A, T> ListBuilder<A, T>
@@ -40,18 +55,6 @@ where
             return_ty: Default::default(),
         }
     }
-    pub fn name(&mut self, name: impl ToString) -> &mut Self {
-        self.list.name = Some(name.to_string());
-        self
-    }
-    pub fn description(&mut self, description: impl ToString) -> &mut Self {
-        self.list.description = Some(description.to_string());
-        self
-    }
-    pub fn private(&mut self, private: bool) -> &mut Self {
-        self.list.private = Some(private);
-        self
-    }
     pub async fn send(&self) -> ApiResult<A, T, ()> {
         self.client
             .send(
@@ -63,7 +66,41 @@ where
     }
 }
 
-impl<A, T> Clone for ListBuilder<A, T> {
+#[cfg(feature = "blocking")]
+impl<A, T> ListBuilder<A, T> {
+    pub(crate) fn into_blocking(
+        self,
+        client: &crate::blocking::TwitterApi<A>,
+    ) -> ListBuilder<A, T, crate::blocking::TwitterApi<A>> {
+        ListBuilder {
+            client: client.clone(),
+            url: self.url,
+            method: self.method,
+            list: self.list,
+            return_ty: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl<A, T> ListBuilder<A, T, crate::blocking::TwitterApi<A>>
+where
+    A: Authorization,
+    T: DeserializeOwned,
+{
+    pub fn send(&self) -> ApiResult<A, T, ()> {
+        self.client.send(
+            self.client
+                .request(self.method.clone(), self.url.clone())
+                .json(&self.list),
+        )
+    }
+}
+
+impl<A, T, C> Clone for ListBuilder<A, T, C>
+where
+    C: Clone,
+{
     fn clone(&self) -> Self {
         Self {
             client: self.client.clone(),
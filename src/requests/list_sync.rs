@@ -0,0 +1,151 @@
+use super::follow_graph::fetch_all_users;
+use super::{BulkAction, BulkOutcome, BulkReport};
+use crate::api::TwitterApi;
+use crate::authorization::Authorization;
+use crate::error::{Error, Result};
+use crate::id::{ListId, UserId};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+/// The most members a list can have
+pub const MAX_LIST_MEMBERS: usize = 5000;
+
+/// The changes made by a [`ListMemberSync`]
+#[derive(Clone, Debug, Default, Serialize, Deserialize, Eq, PartialEq)]
+pub struct ListSyncReport {
+    pub added: BulkReport,
+    pub removed: BulkReport,
+    /// The desired members which already were members
+    pub unchanged: Vec<UserId>,
+}
+
+impl ListSyncReport {
+    /// Whether every add and remove went through, or would have in a dry run
+    pub fn is_complete(&self) -> bool {
+        self.added
+            .entries
+            .iter()
+            .chain(&self.removed.entries)
+            .all(|entry| !matches!(entry.outcome, BulkOutcome::Failed(_)))
+    }
+}
+
+/// Makes the members of a list match a desired set of users.
+///
+/// The current members are paged through first. Members not in the set are then removed
+/// before the missing ones are added, so a list never grows beyond [`MAX_LIST_MEMBERS`]
+/// during the sync. Adds and removes run like a [`BulkRelationship`](super::BulkRelationship)
+/// and a sync which stopped early is completed by running it again with the same report.
+///
+/// ```no_run
+/// use twitter_v2::authorization::BearerToken;
+/// use twitter_v2::requests::ListSyncReport;
+/// use twitter_v2::TwitterApi;
+///
+/// # #[tokio::main]
+/// # async fn main() -> twitter_v2::Result<()> {
+/// let api = TwitterApi::new(BearerToken::new(""));
+/// let mut report = ListSyncReport::default();
+/// api.sync_list_members(1234, [12, 2244994945])
+///     .run(&mut report)
+///     .await?;
+/// println!(
+///     "+{} -{}",
+///     report.added.entries.len(),
+///     report.removed.entries.len()
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub struct ListMemberSync<A> {
+    client: TwitterApi<A>,
+    list_id: ListId,
+    desired: BTreeSet<UserId>,
+    dry_run: bool,
+    min_interval: Duration,
+    rate_limit_wait: Duration,
+}
+
+impl<A> ListMemberSync<A>
+where
+    A: Authorization,
+{
+    pub(crate) fn new(
+        client: &TwitterApi<A>,
+        list_id: impl Into<ListId>,
+        desired: impl IntoIterator<Item = impl Into<UserId>>,
+    ) -> Self {
+        Self {
+            // the diff is taken against the current members of the list
+            client: client.clone().without_cache(),
+            list_id: list_id.into(),
+            desired: desired.into_iter().map(Into::into).collect(),
+            dry_run: false,
+            min_interval: Duration::from_secs(1),
+            rate_limit_wait: Duration::from_secs(15 * 60),
+        }
+    }
+    /// Compute the changes without applying them. The current members are still fetched.
+    pub fn dry_run(&mut self, dry_run: bool) -> &mut Self {
+        self.dry_run = dry_run;
+        self
+    }
+    /// The minimum time between two pages of members. Defaults to 1 second, the rate limit
+    /// of `lists/:id/members`.
+    pub fn min_interval(&mut self, min_interval: Duration) -> &mut Self {
+        self.min_interval = min_interval;
+        self
+    }
+    /// How long to wait before retrying a rate limited request. Defaults to 15 minutes.
+    pub fn rate_limit_wait(&mut self, rate_limit_wait: Duration) -> &mut Self {
+        self.rate_limit_wait = rate_limit_wait;
+        self
+    }
+
+    /// Sync the members, recording the changes in `report` even if the run fails
+    pub async fn run(&self, report: &mut ListSyncReport) -> Result<()> {
+        if self.desired.len() > MAX_LIST_MEMBERS {
+            return Err(Error::custom(format!(
+                "{} desired members exceed the limit of {MAX_LIST_MEMBERS} per list",
+                self.desired.len()
+            )));
+        }
+        let current = fetch_all_users(
+            |pagination_token| {
+                let mut req = self.client.get_list_members(self.list_id);
+                req.max_results(100);
+                if let Some(token) = pagination_token {
+                    req.pagination_token(token);
+                }
+                req
+            },
+            self.min_interval,
+            self.rate_limit_wait,
+        )
+        .await?
+        .into_iter()
+        .map(|user| user.id)
+        .collect::<BTreeSet<_>>();
+
+        report.unchanged = self.desired.intersection(&current).copied().collect();
+        self.client
+            .bulk(BulkAction::RemoveListMember(self.list_id))
+            .dry_run(self.dry_run)
+            .rate_limit_wait(self.rate_limit_wait)
+            .run(
+                current.difference(&self.desired).copied(),
+                &mut report.removed,
+            )
+            .await?;
+        self.client
+            .bulk(BulkAction::AddListMember(self.list_id))
+            .dry_run(self.dry_run)
+            .rate_limit_wait(self.rate_limit_wait)
+            .run(
+                self.desired.difference(&current).copied(),
+                &mut report.added,
+            )
+            .await
+    }
+}
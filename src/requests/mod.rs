@@ -1,11 +1,23 @@
+mod backfill;
+mod batcher;
+mod bulk;
 mod compliance_job;
+mod compliance_job_runner;
+mod follow_graph;
 mod id_req;
 mod list;
+mod list_sync;
 mod stream_rule;
 mod tweet;
 
+pub use backfill::*;
+pub use batcher::*;
+pub use bulk::*;
 pub use compliance_job::*;
-pub(crate) use id_req::*;
+pub use compliance_job_runner::*;
+pub use follow_graph::*;
+pub use id_req::*;
 pub use list::*;
+pub use list_sync::*;
 pub use stream_rule::*;
 pub use tweet::*;
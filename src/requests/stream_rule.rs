@@ -2,11 +2,12 @@ use crate::api::TwitterApi;
 use crate::api_result::ApiResult;
 use crate::authorization::Authorization;
 use crate::data::StreamRule;
-use crate::id::{IntoNumericId, NumericId};
+use crate::id::RuleId;
 use crate::meta::StreamRuleMeta;
 use crate::query::UrlQueryExt;
 use reqwest::Method;
 use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
 use url::Url;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -16,7 +17,7 @@ struct DraftStreamRuleAdd {
 }
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 struct DraftStreamRuleDelete {
-    ids: Vec<NumericId>,
+    ids: Vec<RuleId>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
@@ -26,22 +27,13 @@ struct DraftStreamRule {
 }
 
 #[derive(Debug)]
-pub struct StreamRuleBuilder<A> {
-    client: TwitterApi<A>,
+pub struct StreamRuleBuilder<A, C = TwitterApi<A>> {
+    client: C,
     url: Url,
     stream_rule: DraftStreamRule,
+    auth_ty: PhantomData<A>,
 }
-impl<A> StreamRuleBuilder<A>
-where
-    A: Authorization,
-{
-    pub(crate) fn new(client: &TwitterApi<A>, url: Url) -> Self {
-        Self {
-            client: client.clone(),
-            url,
-            stream_rule: Default::default(),
-        }
-    }
+impl<A, C> StreamRuleBuilder<A, C> {
     pub fn dry_run(&mut self) -> &mut Self {
         self.url.append_query_val("dry_run", true);
         self
@@ -74,22 +66,36 @@ where
         }
         self
     }
-    pub fn delete_id(&mut self, id: impl IntoNumericId) -> &mut Self {
+    pub fn delete_id(&mut self, id: impl Into<RuleId>) -> &mut Self {
         self.delete_ids([id]);
         self
     }
-    pub fn delete_ids(&mut self, ids: impl IntoIterator<Item = impl IntoNumericId>) -> &mut Self {
+    pub fn delete_ids(&mut self, ids: impl IntoIterator<Item = impl Into<RuleId>>) -> &mut Self {
         if let Some(delete) = self.stream_rule.delete.as_mut() {
             for id in ids {
-                delete.ids.push(id.into_id())
+                delete.ids.push(id.into())
             }
         } else {
             self.stream_rule.delete = Some(DraftStreamRuleDelete {
-                ids: ids.into_iter().map(|id| id.into_id()).collect(),
+                ids: ids.into_iter().map(Into::into).collect(),
             });
         }
         self
     }
+}
+
+impl<A> StreamRuleBuilder<A>
+where
+    A: Authorization,
+{
+    pub(crate) fn new(client: &TwitterApi<A>, url: Url) -> Self {
+        Self {
+            client: client.clone(),
+            url,
+            stream_rule: Default::default(),
+            auth_ty: PhantomData,
+        }
+    }
     pub async fn send(&self) -> ApiResult<A, Vec<StreamRule>, StreamRuleMeta> {
         self.client
             .send(
@@ -101,12 +107,45 @@ where
     }
 }
 
-impl<A> Clone for StreamRuleBuilder<A> {
+#[cfg(feature = "blocking")]
+impl<A> StreamRuleBuilder<A> {
+    pub(crate) fn into_blocking(
+        self,
+        client: &crate::blocking::TwitterApi<A>,
+    ) -> StreamRuleBuilder<A, crate::blocking::TwitterApi<A>> {
+        StreamRuleBuilder {
+            client: client.clone(),
+            url: self.url,
+            stream_rule: self.stream_rule,
+            auth_ty: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl<A> StreamRuleBuilder<A, crate::blocking::TwitterApi<A>>
+where
+    A: Authorization,
+{
+    pub fn send(&self) -> ApiResult<A, Vec<StreamRule>, StreamRuleMeta> {
+        self.client.send(
+            self.client
+                .request(Method::POST, self.url.clone())
+                .json(&self.stream_rule),
+        )
+    }
+}
+
+impl<A, C> Clone for StreamRuleBuilder<A, C>
+where
+    C: Clone,
+{
     fn clone(&self) -> Self {
         Self {
             client: self.client.clone(),
             url: self.url.clone(),
             stream_rule: self.stream_rule.clone(),
+            auth_ty: PhantomData,
         }
     }
 }
@@ -2,9 +2,10 @@ use crate::api::TwitterApi;
 use crate::api_result::ApiResult;
 use crate::authorization::Authorization;
 use crate::data::{ReplySettings, Tweet};
-use crate::id::{IntoNumericId, IntoStringId, StringId};
+use crate::id::{IntoNumericId, IntoStringId, StringId, TweetId, UserId};
 use reqwest::Method;
 use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
 use std::time::Duration;
 use url::Url;
 
@@ -33,11 +34,18 @@ struct DraftTweetReply {
     pub in_reply_to_tweet_id: Option<String>,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+struct DraftTweetEditOptions {
+    pub previous_post_id: String,
+}
+
 #[derive(Clone, Default, Debug, Serialize, Deserialize, Eq, PartialEq)]
 struct DraftTweet {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub direct_message_deep_link: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub edit_options: Option<DraftTweetEditOptions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub for_super_followers_only: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub geo: Option<DraftTweetGeo>,
@@ -56,23 +64,14 @@ struct DraftTweet {
 }
 
 #[derive(Debug)]
-pub struct TweetBuilder<A> {
-    client: TwitterApi<A>,
+pub struct TweetBuilder<A, C = TwitterApi<A>> {
+    client: C,
     url: Url,
     tweet: DraftTweet,
+    auth_ty: PhantomData<A>,
 }
 
-impl<A> TweetBuilder<A>
-where
-    A: Authorization,
-{
-    pub(crate) fn new(client: &TwitterApi<A>, url: Url) -> Self {
-        Self {
-            client: client.clone(),
-            url,
-            tweet: Default::default(),
-        }
-    }
+impl<A, C> TweetBuilder<A, C> {
     pub fn text(&mut self, text: String) -> &mut Self {
         self.tweet.text = Some(text);
         self
@@ -98,7 +97,7 @@ where
     pub fn add_media(
         &mut self,
         media_ids: impl IntoIterator<Item = impl IntoNumericId>,
-        tagged_user_ids: impl IntoIterator<Item = impl IntoNumericId>,
+        tagged_user_ids: impl IntoIterator<Item = impl Into<UserId>>,
     ) -> &mut Self {
         if let Some(media) = self.tweet.media.as_mut() {
             media
@@ -106,13 +105,13 @@ where
                 .extend(media_ids.into_iter().map(|id| id.to_string()));
             media
                 .tagged_user_ids
-                .extend(tagged_user_ids.into_iter().map(|id| id.to_string()));
+                .extend(tagged_user_ids.into_iter().map(|id| id.into().to_string()));
         } else {
             self.tweet.media = Some(DraftTweetMedia {
                 media_ids: media_ids.into_iter().map(|id| id.to_string()).collect(),
                 tagged_user_ids: tagged_user_ids
                     .into_iter()
-                    .map(|id| id.to_string())
+                    .map(|id| id.into().to_string())
                     .collect(),
             });
         }
@@ -132,20 +131,27 @@ where
         });
         self
     }
-    pub fn quote_tweet_id(&mut self, id: impl IntoNumericId) -> &mut Self {
-        self.tweet.quote_tweet_id = Some(id.to_string());
+    /// Post this tweet as a new version of an existing (editable) tweet.
+    pub fn edit_of(&mut self, tweet_id: impl Into<TweetId>) -> &mut Self {
+        self.tweet.edit_options = Some(DraftTweetEditOptions {
+            previous_post_id: tweet_id.into().to_string(),
+        });
         self
     }
-    pub fn add_exclude_reply_user_id(&mut self, user_id: impl IntoNumericId) -> &mut Self {
+    pub fn quote_tweet_id(&mut self, id: impl Into<TweetId>) -> &mut Self {
+        self.tweet.quote_tweet_id = Some(id.into().to_string());
+        self
+    }
+    pub fn add_exclude_reply_user_id(&mut self, user_id: impl Into<UserId>) -> &mut Self {
         self.add_exclude_reply_user_ids([user_id])
     }
     pub fn add_exclude_reply_user_ids(
         &mut self,
-        user_ids: impl IntoIterator<Item = impl IntoNumericId>,
+        user_ids: impl IntoIterator<Item = impl Into<UserId>>,
     ) -> &mut Self {
         let mut user_ids = user_ids
             .into_iter()
-            .map(|id| id.to_string())
+            .map(|id| id.into().to_string())
             .collect::<Vec<_>>();
         if let Some(reply) = self.tweet.reply.as_mut() {
             if let Some(exclude_reply_user_ids) = reply.exclude_reply_user_ids.as_mut() {
@@ -161,7 +167,8 @@ where
         }
         self
     }
-    pub fn in_reply_to_tweet_id(&mut self, tweet_id: impl IntoNumericId) -> &mut Self {
+    pub fn in_reply_to_tweet_id(&mut self, tweet_id: impl Into<TweetId>) -> &mut Self {
+        let tweet_id = tweet_id.into();
         if let Some(reply) = self.tweet.reply.as_mut() {
             reply.in_reply_to_tweet_id = Some(tweet_id.to_string());
         } else {
@@ -176,6 +183,20 @@ where
         self.tweet.reply_settings = Some(reply_settings);
         self
     }
+}
+
+impl<A> TweetBuilder<A>
+where
+    A: Authorization,
+{
+    pub(crate) fn new(client: &TwitterApi<A>, url: Url) -> Self {
+        Self {
+            client: client.clone(),
+            url,
+            tweet: Default::default(),
+            auth_ty: PhantomData,
+        }
+    }
     pub async fn send(&self) -> ApiResult<A, Tweet, ()> {
         self.client
             .send(
@@ -187,12 +208,45 @@ where
     }
 }
 
-impl<A> Clone for TweetBuilder<A> {
+#[cfg(feature = "blocking")]
+impl<A> TweetBuilder<A> {
+    pub(crate) fn into_blocking(
+        self,
+        client: &crate::blocking::TwitterApi<A>,
+    ) -> TweetBuilder<A, crate::blocking::TwitterApi<A>> {
+        TweetBuilder {
+            client: client.clone(),
+            url: self.url,
+            tweet: self.tweet,
+            auth_ty: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl<A> TweetBuilder<A, crate::blocking::TwitterApi<A>>
+where
+    A: Authorization,
+{
+    pub fn send(&self) -> ApiResult<A, Tweet, ()> {
+        self.client.send(
+            self.client
+                .request(Method::POST, self.url.clone())
+                .json(&self.tweet),
+        )
+    }
+}
+
+impl<A, C> Clone for TweetBuilder<A, C>
+where
+    C: Clone,
+{
     fn clone(&self) -> Self {
         Self {
             client: self.client.clone(),
             url: self.url.clone(),
             tweet: self.tweet.clone(),
+            auth_ty: PhantomData,
         }
     }
 }
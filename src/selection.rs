@@ -0,0 +1,172 @@
+//! Projections of [`Tweet`] and [`User`] in which requested fields are not optional.
+//!
+//! The [`select!`](crate::select) macro declares a projection listing fields of the
+//! underlying type together with their types, without the `Option`. Calling `select` on a
+//! request builder requests those fields and deserializes the response into the projection,
+//! failing if the API leaves out one of them. All other fields remain available through
+//! `Deref` to the underlying type.
+//!
+//! ```no_run
+//! use time::OffsetDateTime;
+//! use twitter_v2::authorization::BearerToken;
+//! use twitter_v2::id::UserId;
+//! use twitter_v2::{select, Tweet, TwitterApi};
+//!
+//! select! {
+//!     pub struct DatedTweet: Tweet {
+//!         author_id: UserId,
+//!         created_at: OffsetDateTime,
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() -> twitter_v2::Result<()> {
+//! let tweet = TwitterApi::new(BearerToken::new(""))
+//!     .get_tweet(1261326399320715264)
+//!     .select::<DatedTweet>()
+//!     .send()
+//!     .await?
+//!     .into_data()
+//!     .expect("this tweet should exist");
+//! println!("{} wrote {} at {}", tweet.author_id, tweet.text, tweet.created_at);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::data::{Tweet, User};
+use serde::de::DeserializeOwned;
+use std::fmt;
+
+/// A type whose fields are requested with a `*.fields` query parameter
+pub trait Selectable {
+    /// The query parameter listing the fields, e.g. `tweet.fields`
+    const FIELDS_KEY: &'static str;
+}
+
+impl Selectable for Tweet {
+    const FIELDS_KEY: &'static str = "tweet.fields";
+}
+
+impl Selectable for User {
+    const FIELDS_KEY: &'static str = "user.fields";
+}
+
+/// A projection of [`Source`](Self::Source), usually declared with [`select!`](crate::select)
+pub trait Selection: DeserializeOwned {
+    type Source: Selectable;
+
+    /// The names of the fields to request
+    fn fields() -> &'static [&'static str];
+}
+
+/// The response type of a request returning `Self` once projected to `S`
+pub trait Project<S> {
+    type Output: DeserializeOwned;
+}
+
+impl<S: Selection<Source = Tweet>> Project<S> for Tweet {
+    type Output = S;
+}
+
+impl<S: Selection<Source = Tweet>> Project<S> for Vec<Tweet> {
+    type Output = Vec<S>;
+}
+
+impl<S: Selection<Source = User>> Project<S> for User {
+    type Output = S;
+}
+
+impl<S: Selection<Source = User>> Project<S> for Vec<User> {
+    type Output = Vec<S>;
+}
+
+/// A selected field was not part of the response
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct MissingField(pub &'static str);
+
+impl fmt::Display for MissingField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "selected field `{}` is missing from the response",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for MissingField {}
+
+/// Declare a [`Selection`](crate::selection::Selection), a projection of [`Tweet`] or
+/// [`User`] in which the listed fields are not optional. The fields must have the names
+/// and, without the `Option`, the types of fields of the underlying type.
+#[macro_export]
+macro_rules! select {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident: $source:ty {
+            $($field:ident: $ty:ty),* $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        #[derive(Clone, Debug)]
+        $vis struct $name {
+            $(pub $field: $ty,)*
+            inner: $source,
+        }
+
+        impl $name {
+            pub fn into_inner(self) -> $source {
+                self.inner
+            }
+        }
+
+        impl std::ops::Deref for $name {
+            type Target = $source;
+            fn deref(&self) -> &Self::Target {
+                &self.inner
+            }
+        }
+
+        impl std::convert::TryFrom<$source> for $name {
+            type Error = $crate::selection::MissingField;
+            fn try_from(inner: $source) -> std::result::Result<Self, Self::Error> {
+                Ok(Self {
+                    $($field: inner
+                        .$field
+                        .clone()
+                        .ok_or($crate::selection::MissingField(stringify!($field)))?,)*
+                    inner,
+                })
+            }
+        }
+
+        impl<'de> $crate::__private::serde::Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+            where
+                D: $crate::__private::serde::Deserializer<'de>,
+            {
+                let inner = <$source as $crate::__private::serde::Deserialize>::deserialize(
+                    deserializer,
+                )?;
+                std::convert::TryFrom::try_from(inner)
+                    .map_err(<D::Error as $crate::__private::serde::de::Error>::custom)
+            }
+        }
+
+        impl $crate::__private::serde::Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+            where
+                S: $crate::__private::serde::Serializer,
+            {
+                $crate::__private::serde::Serialize::serialize(&self.inner, serializer)
+            }
+        }
+
+        impl $crate::selection::Selection for $name {
+            type Source = $source;
+            fn fields() -> &'static [&'static str] {
+                &[$(stringify!($field)),*]
+            }
+        }
+    };
+}
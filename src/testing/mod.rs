@@ -0,0 +1,397 @@
+//! An in-process mock of the Twitter API for tests, enabled with the `testing` feature.
+//!
+//! [`MockTwitter`] serves stateful fakes of the tweet, user, follow, like, list, stream rule
+//! and filtered stream endpoints on a local port. Data can be seeded from files in the
+//! `fixtures/data` format (`{"data": [...], "includes": {...}}`) and faults can be
+//! injected to exercise error handling.
+//!
+//! ```no_run
+//! use twitter_v2::testing::{Fault, MockTwitter};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> twitter_v2::Result<()> {
+//! let mock = MockTwitter::start().await?;
+//! mock.load_fixture("./fixtures/data/tweet/example_tweet.json")?;
+//! let api = mock.api();
+//! let tweet = api.get_tweet(1307025659294674945).send().await?;
+//!
+//! mock.inject(Fault::TooManyRequests);
+//! assert!(api.get_tweet(1307025659294674945).send().await.is_err());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Responses always contain every stored field, `*.fields` and `expansions` parameters
+//! are ignored.
+
+mod state;
+
+use self::state::{error, parse_id, Collection, MockState, Reply};
+use crate::api::TwitterApi;
+use crate::authorization::{Authorization, BearerToken};
+use crate::error::{Error, Result};
+use crate::id::{ListId, TweetId, UserId};
+use axum::body::{Bytes, StreamBody};
+use axum::extract::Extension;
+use axum::http::{header, Method, StatusCode, Uri};
+use axum::response::{IntoResponse, Response};
+use axum::Router;
+use futures::prelude::*;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::net::{SocketAddr, TcpListener};
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use time::OffsetDateTime;
+use tokio::sync::{broadcast, oneshot};
+use url::Url;
+
+/// A failure returned by the mock server instead of a regular response
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Fault {
+    /// `429 Too Many Requests` with exhausted rate limit headers
+    TooManyRequests,
+    /// `503 Service Unavailable`
+    ServiceUnavailable,
+    /// An error response with the given status code
+    Status(u16),
+    /// Drop the connection after sending the response headers
+    Disconnect,
+}
+
+#[derive(Clone, Debug)]
+enum StreamEvent {
+    Tweet(Value),
+    Disconnect,
+}
+
+#[derive(Debug)]
+struct Shared {
+    state: Mutex<MockState>,
+    stream: broadcast::Sender<StreamEvent>,
+}
+
+#[derive(Debug)]
+pub struct MockTwitter {
+    addr: SocketAddr,
+    shared: Arc<Shared>,
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+impl MockTwitter {
+    /// Start a server on a random local port. Must be called from within a tokio runtime,
+    /// the server stops when the `MockTwitter` is dropped.
+    pub async fn start() -> Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").map_err(Error::custom)?;
+        let addr = listener.local_addr().map_err(Error::custom)?;
+        let shared = Arc::new(Shared {
+            state: Mutex::new(MockState::default()),
+            stream: broadcast::channel(256).0,
+        });
+        let app = Router::new()
+            .fallback(axum::handler::Handler::into_service(handle))
+            .layer(Extension(shared.clone()));
+        let (shutdown, on_shutdown) = oneshot::channel::<()>();
+        let server = axum::Server::from_tcp(listener)
+            .map_err(Error::custom)?
+            .serve(app.into_make_service())
+            .with_graceful_shutdown(async {
+                let _ = on_shutdown.await;
+            });
+        tokio::spawn(server);
+        Ok(Self {
+            addr,
+            shared,
+            shutdown: Some(shutdown),
+        })
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// The URL to use as the base of the API (`http://127.0.0.1:<port>/2/`)
+    pub fn base_url(&self) -> Url {
+        Url::parse(&format!("http://{}/2/", self.addr)).unwrap()
+    }
+
+    /// A client pointed at the mock server. Any credentials are accepted.
+    pub fn api(&self) -> TwitterApi<BearerToken> {
+        self.api_with_auth(BearerToken::new("mock"))
+    }
+
+    pub fn api_with_auth<A: Authorization>(&self, auth: A) -> TwitterApi<A> {
+        TwitterApi::new(auth).with_base_url(self.base_url())
+    }
+
+    /// Seed the server from a file in the `fixtures/data` format. Tweets and users in
+    /// `data` as well as in `includes` are added.
+    pub fn load_fixture(&self, path: impl AsRef<Path>) -> Result<()> {
+        let fixture: Value =
+            serde_json::from_reader(std::fs::File::open(path.as_ref()).map_err(Error::custom)?)?;
+        self.load_fixture_value(fixture)
+    }
+
+    pub fn load_fixture_value(&self, fixture: Value) -> Result<()> {
+        let mut state = self.state();
+        let data = match &fixture["data"] {
+            Value::Array(data) => data.clone(),
+            Value::Null => Vec::new(),
+            data => vec![data.clone()],
+        };
+        for item in data {
+            // tweets carry text, users a username and lists only a name
+            let collection = if item.get("text").is_some() {
+                Collection::Tweets
+            } else if item.get("username").is_some() {
+                Collection::Users
+            } else {
+                Collection::Lists
+            };
+            state.insert(collection, item);
+        }
+        for (key, collection) in [("tweets", Collection::Tweets), ("users", Collection::Users)] {
+            for item in fixture["includes"][key].as_array().into_iter().flatten() {
+                state.insert(collection, item.clone());
+            }
+        }
+        Ok(())
+    }
+
+    pub fn insert_tweet(&self, tweet: Value) -> Option<TweetId> {
+        self.state()
+            .insert(Collection::Tweets, tweet)
+            .map(TweetId::new)
+    }
+
+    pub fn insert_user(&self, user: Value) -> Option<UserId> {
+        self.state()
+            .insert(Collection::Users, user)
+            .map(UserId::new)
+    }
+
+    pub fn insert_list(&self, list: Value) -> Option<ListId> {
+        self.state()
+            .insert(Collection::Lists, list)
+            .map(ListId::new)
+    }
+
+    /// Set the authenticated user returned by `users/me`. Defaults to the user with
+    /// the lowest ID.
+    pub fn set_me(&self, id: impl Into<UserId>) {
+        self.state().me = Some(id.into().as_u64());
+    }
+
+    pub fn follow(&self, source: impl Into<UserId>, target: impl Into<UserId>) {
+        self.state()
+            .following
+            .insert((source.into().as_u64(), target.into().as_u64()));
+    }
+
+    pub fn like(&self, user: impl Into<UserId>, tweet: impl Into<TweetId>) {
+        self.state()
+            .likes
+            .insert((user.into().as_u64(), tweet.into().as_u64()));
+    }
+
+    pub fn add_list_member(&self, list: impl Into<ListId>, user: impl Into<UserId>) {
+        self.state()
+            .list_members
+            .insert((list.into().as_u64(), user.into().as_u64()));
+    }
+
+    pub fn tweet(&self, id: impl Into<TweetId>) -> Option<Value> {
+        self.state().tweets.get(&id.into().as_u64()).cloned()
+    }
+
+    pub fn user(&self, id: impl Into<UserId>) -> Option<Value> {
+        self.state().users.get(&id.into().as_u64()).cloned()
+    }
+
+    pub fn list(&self, id: impl Into<ListId>) -> Option<Value> {
+        self.state().lists.get(&id.into().as_u64()).cloned()
+    }
+
+    pub fn followers(&self, id: impl Into<UserId>) -> Vec<UserId> {
+        let id = id.into().as_u64();
+        self.state()
+            .following
+            .iter()
+            .filter(|(_, target)| *target == id)
+            .map(|(source, _)| UserId::new(*source))
+            .collect()
+    }
+
+    pub fn stream_rules(&self) -> Vec<Value> {
+        self.state().rules.values().cloned().collect()
+    }
+
+    /// Deliver `tweet` to connected filtered streams if it matches any stream rule. Tweets
+    /// posted through the API are delivered the same way.
+    pub fn push_stream_tweet(&self, tweet: Value) {
+        let _ = self.shared.stream.send(StreamEvent::Tweet(tweet));
+    }
+
+    /// Drop all connected filtered streams
+    pub fn disconnect_streams(&self) {
+        let _ = self.shared.stream.send(StreamEvent::Disconnect);
+    }
+
+    /// Fail the next request with `fault`
+    pub fn inject(&self, fault: Fault) {
+        self.state().faults.push_back((None, fault));
+    }
+
+    /// Fail the next request whose path, relative to `/2/`, starts with `path`
+    pub fn inject_for(&self, path: impl AsRef<str>, fault: Fault) {
+        let path = path.as_ref().trim_start_matches('/').to_string();
+        self.state().faults.push_back((Some(path), fault));
+    }
+
+    fn state(&self) -> MutexGuard<'_, MockState> {
+        self.shared.state.lock().unwrap()
+    }
+}
+
+impl Drop for MockTwitter {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+    }
+}
+
+async fn handle(
+    Extension(shared): Extension<Arc<Shared>>,
+    method: Method,
+    uri: Uri,
+    body: Bytes,
+) -> Response {
+    let path = uri.path().trim_start_matches("/2/").trim_matches('/');
+    let query = uri
+        .query()
+        .map(|query| {
+            url::form_urlencoded::parse(query.as_bytes())
+                .into_owned()
+                .collect::<HashMap<_, _>>()
+        })
+        .unwrap_or_default();
+    let segments = path.split('/').collect::<Vec<_>>();
+
+    let fault = shared.state.lock().unwrap().take_fault(path);
+    if let Some(fault) = fault {
+        return fault_response(fault);
+    }
+
+    if method == Method::GET && segments == ["tweets", "search", "stream"] {
+        return stream_response(&shared);
+    }
+
+    let reply = {
+        let mut state = shared.state.lock().unwrap();
+        let reply = state.route(method.as_str(), &segments, &query, &body);
+        if method == Method::POST && segments == ["tweets"] && reply.0 == 200 {
+            if let Some(tweet) = parse_id(&reply.1["data"]["id"])
+                .and_then(|id| state.tweets.get(&id))
+                .cloned()
+            {
+                let _ = shared.stream.send(StreamEvent::Tweet(tweet));
+            }
+        }
+        reply
+    };
+    json_response(reply)
+}
+
+fn json_response((status, body): Reply) -> Response {
+    (
+        StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+        axum::Json(body),
+    )
+        .into_response()
+}
+
+fn fault_response(fault: Fault) -> Response {
+    match fault {
+        Fault::TooManyRequests => {
+            let reset = OffsetDateTime::now_utc() + Duration::from_secs(15 * 60);
+            let mut response = json_response(error(429, "Too Many Requests", "Too Many Requests"));
+            let headers = response.headers_mut();
+            headers.insert("x-rate-limit-limit", 300.into());
+            headers.insert("x-rate-limit-remaining", 0.into());
+            headers.insert("x-rate-limit-reset", reset.unix_timestamp().into());
+            response
+        }
+        Fault::ServiceUnavailable => {
+            json_response(error(503, "Service Unavailable", "Service Unavailable"))
+        }
+        Fault::Status(status) => json_response(error(
+            status,
+            StatusCode::from_u16(status)
+                .ok()
+                .and_then(|status| status.canonical_reason())
+                .unwrap_or("Error"),
+            "Injected fault",
+        )),
+        Fault::Disconnect => {
+            let body = stream::once(async {
+                Err::<Bytes, _>(std::io::Error::new(
+                    std::io::ErrorKind::ConnectionAborted,
+                    "injected disconnect",
+                ))
+            });
+            json_stream_response(body)
+        }
+    }
+}
+
+fn stream_response(shared: &Arc<Shared>) -> Response {
+    let receiver = shared.stream.subscribe();
+    let shared = shared.clone();
+    let body = stream::unfold((receiver, shared), |(mut receiver, shared)| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(StreamEvent::Tweet(tweet)) => {
+                    let matching_rules = shared
+                        .state
+                        .lock()
+                        .unwrap()
+                        .matching_rules(tweet["text"].as_str().unwrap_or_default());
+                    if matching_rules.is_empty() {
+                        continue;
+                    }
+                    let mut line = serde_json::to_vec(&json!({
+                        "data": tweet,
+                        "matching_rules": matching_rules,
+                    }))
+                    .unwrap();
+                    line.extend_from_slice(b"\r\n");
+                    return Some((Ok(Bytes::from(line)), (receiver, shared)));
+                }
+                Ok(StreamEvent::Disconnect) => {
+                    let err = std::io::Error::new(
+                        std::io::ErrorKind::ConnectionAborted,
+                        "stream disconnected",
+                    );
+                    return Some((Err(err), (receiver, shared)));
+                }
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    });
+    json_stream_response(body)
+}
+
+fn json_stream_response<S>(body: S) -> Response
+where
+    S: Stream<Item = std::io::Result<Bytes>> + Send + 'static,
+{
+    let mut response = StreamBody::new(body).into_response();
+    response.headers_mut().insert(
+        header::CONTENT_TYPE,
+        header::HeaderValue::from_static("application/json"),
+    );
+    response
+}
@@ -0,0 +1,522 @@
+use super::Fault;
+use crate::id::NumericId;
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+/// A JSON response of the mock server
+pub(crate) type Reply = (u16, Value);
+
+#[derive(Debug, Default)]
+pub(crate) struct MockState {
+    pub tweets: BTreeMap<u64, Value>,
+    pub users: BTreeMap<u64, Value>,
+    pub lists: BTreeMap<u64, Value>,
+    pub rules: BTreeMap<u64, Value>,
+    /// (source user, target user)
+    pub following: BTreeSet<(u64, u64)>,
+    /// (user, tweet)
+    pub likes: BTreeSet<(u64, u64)>,
+    /// (list, user)
+    pub list_members: BTreeSet<(u64, u64)>,
+    pub me: Option<u64>,
+    pub faults: VecDeque<(Option<String>, Fault)>,
+    last_id: u64,
+}
+
+impl MockState {
+    pub fn next_id(&mut self) -> u64 {
+        let id = NumericId::from_time(OffsetDateTime::now_utc())
+            .as_u64()
+            .max(self.last_id + 1);
+        self.last_id = id;
+        id
+    }
+
+    pub fn take_fault(&mut self, path: &str) -> Option<Fault> {
+        let index = self.faults.iter().position(|(prefix, _)| {
+            prefix
+                .as_deref()
+                .map(|prefix| path.starts_with(prefix))
+                .unwrap_or(true)
+        })?;
+        self.faults.remove(index).map(|(_, fault)| fault)
+    }
+
+    pub fn me(&self) -> Option<u64> {
+        self.me.or_else(|| self.users.keys().next().copied())
+    }
+
+    pub fn insert(&mut self, collection: Collection, value: Value) -> Option<u64> {
+        let id = value.get("id").and_then(parse_id)?;
+        self.collection(collection).insert(id, value);
+        Some(id)
+    }
+
+    fn collection(&mut self, collection: Collection) -> &mut BTreeMap<u64, Value> {
+        match collection {
+            Collection::Tweets => &mut self.tweets,
+            Collection::Users => &mut self.users,
+            Collection::Lists => &mut self.lists,
+        }
+    }
+
+    /// Whether `text` matches any of the stream rules, returning the matched rules. Rules
+    /// match when every whitespace separated term of their value occurs in the text.
+    pub fn matching_rules(&self, text: &str) -> Vec<Value> {
+        let text = text.to_lowercase();
+        self.rules
+            .values()
+            .filter(|rule| {
+                rule["value"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .split_whitespace()
+                    .all(|term| text.contains(&term.trim_matches('"').to_lowercase()))
+            })
+            .map(|rule| json!({ "id": rule["id"], "tag": rule["tag"] }))
+            .collect()
+    }
+
+    pub fn route(
+        &mut self,
+        method: &str,
+        segments: &[&str],
+        query: &HashMap<String, String>,
+        body: &[u8],
+    ) -> Reply {
+        let body: Value = serde_json::from_slice(body).unwrap_or(Value::Null);
+        let ids = |key: &str| -> Vec<String> {
+            query
+                .get(key)
+                .map(|ids| ids.split(',').map(str::to_string).collect())
+                .unwrap_or_default()
+        };
+        let me = self.me();
+        match (method, segments) {
+            // tweets
+            ("GET", ["tweets"]) => self.lookup_many(Collection::Tweets, ids("ids")),
+            ("POST", ["tweets"]) => self.post_tweet(me, &body),
+            ("GET", ["tweets", id]) => self.lookup(Collection::Tweets, id),
+            ("DELETE", ["tweets", id]) => {
+                let deleted = parse_str_id(id)
+                    .and_then(|id| self.tweets.remove(&id))
+                    .is_some();
+                ok(json!({ "deleted": deleted }))
+            }
+            ("GET", ["tweets", id, "liking_users"]) => {
+                let id = parse_str_id(id).unwrap_or_default();
+                let users = self
+                    .likes
+                    .iter()
+                    .filter(|(_, tweet)| *tweet == id)
+                    .map(|(user, _)| *user)
+                    .collect::<Vec<_>>();
+                self.page(Collection::Users, users, query)
+            }
+            ("GET", ["users", id, "tweets"]) => {
+                let id = parse_str_id(id).unwrap_or_default();
+                let tweets = self
+                    .tweets
+                    .iter()
+                    .rev()
+                    .filter(|(_, tweet)| tweet.get("author_id").and_then(parse_id) == Some(id))
+                    .map(|(id, _)| *id)
+                    .collect::<Vec<_>>();
+                self.page(Collection::Tweets, tweets, query)
+            }
+            ("GET", ["users", id, "liked_tweets"]) => {
+                let id = parse_str_id(id).unwrap_or_default();
+                let tweets = self
+                    .likes
+                    .iter()
+                    .filter(|(user, _)| *user == id)
+                    .map(|(_, tweet)| *tweet)
+                    .collect::<Vec<_>>();
+                self.page(Collection::Tweets, tweets, query)
+            }
+            ("POST", ["users", id, "likes"]) => {
+                let pair = (parse_str_id(id), body.get("tweet_id").and_then(parse_id));
+                if let (Some(user), Some(tweet)) = pair {
+                    self.likes.insert((user, tweet));
+                }
+                ok(json!({ "liked": true }))
+            }
+            ("DELETE", ["users", id, "likes", tweet_id]) => {
+                if let (Some(user), Some(tweet)) = (parse_str_id(id), parse_str_id(tweet_id)) {
+                    self.likes.remove(&(user, tweet));
+                }
+                ok(json!({ "liked": false }))
+            }
+
+            // users
+            ("GET", ["users"]) => self.lookup_many(Collection::Users, ids("ids")),
+            ("GET", ["users", "me"]) => match me {
+                Some(me) => self.lookup(Collection::Users, &me.to_string()),
+                None => error(401, "Unauthorized", "No authenticated user"),
+            },
+            ("GET", ["users", "by"]) => {
+                let ids = ids("usernames")
+                    .into_iter()
+                    .map(|username| {
+                        self.user_by_username(&username)
+                            .map(|id| id.to_string())
+                            .unwrap_or(username)
+                    })
+                    .collect();
+                self.lookup_many(Collection::Users, ids)
+            }
+            ("GET", ["users", "by", "username", username]) => {
+                match self.user_by_username(username) {
+                    Some(id) => self.lookup(Collection::Users, &id.to_string()),
+                    None => not_found("user", "username", username),
+                }
+            }
+            ("GET", ["users", id]) => self.lookup(Collection::Users, id),
+            ("GET", ["users", id, "followers"]) => {
+                let id = parse_str_id(id).unwrap_or_default();
+                let users = self
+                    .following
+                    .iter()
+                    .filter(|(_, target)| *target == id)
+                    .map(|(source, _)| *source)
+                    .collect::<Vec<_>>();
+                self.page(Collection::Users, users, query)
+            }
+            ("GET", ["users", id, "following"]) => {
+                let id = parse_str_id(id).unwrap_or_default();
+                let users = self
+                    .following
+                    .iter()
+                    .filter(|(source, _)| *source == id)
+                    .map(|(_, target)| *target)
+                    .collect::<Vec<_>>();
+                self.page(Collection::Users, users, query)
+            }
+            ("POST", ["users", id, "following"]) => {
+                let pair = (
+                    parse_str_id(id),
+                    body.get("target_user_id").and_then(parse_id),
+                );
+                if let (Some(source), Some(target)) = pair {
+                    self.following.insert((source, target));
+                }
+                ok(json!({ "following": true, "pending_follow": false }))
+            }
+            ("DELETE", ["users", source, "following", target]) => {
+                if let (Some(source), Some(target)) = (parse_str_id(source), parse_str_id(target)) {
+                    self.following.remove(&(source, target));
+                }
+                ok(json!({ "following": false }))
+            }
+
+            // lists
+            ("POST", ["lists"]) => {
+                let id = self.next_id();
+                let mut list = body.as_object().cloned().unwrap_or_default();
+                list.insert("id".to_string(), Value::String(id.to_string()));
+                if let Some(me) = me {
+                    list.insert("owner_id".to_string(), Value::String(me.to_string()));
+                }
+                list.insert("created_at".to_string(), Value::String(now()));
+                let reply = json!({ "id": list["id"], "name": list["name"] });
+                self.lists.insert(id, Value::Object(list));
+                ok(reply)
+            }
+            ("GET", ["lists", id]) => self.lookup(Collection::Lists, id),
+            ("PUT", ["lists", id]) => {
+                let list = parse_str_id(id).and_then(|id| self.lists.get_mut(&id));
+                let updated = if let Some(Value::Object(list)) = list {
+                    for (key, value) in body.as_object().cloned().unwrap_or_default() {
+                        list.insert(key, value);
+                    }
+                    true
+                } else {
+                    false
+                };
+                ok(json!({ "updated": updated }))
+            }
+            ("DELETE", ["lists", id]) => {
+                let id = parse_str_id(id).unwrap_or_default();
+                let deleted = self.lists.remove(&id).is_some();
+                self.list_members.retain(|(list, _)| *list != id);
+                ok(json!({ "deleted": deleted }))
+            }
+            ("GET", ["lists", id, "members"]) => {
+                let id = parse_str_id(id).unwrap_or_default();
+                let users = self
+                    .list_members
+                    .iter()
+                    .filter(|(list, _)| *list == id)
+                    .map(|(_, user)| *user)
+                    .collect::<Vec<_>>();
+                self.page(Collection::Users, users, query)
+            }
+            ("POST", ["lists", id, "members"]) => {
+                let pair = (parse_str_id(id), body.get("user_id").and_then(parse_id));
+                if let (Some(list), Some(user)) = pair {
+                    self.list_members.insert((list, user));
+                }
+                ok(json!({ "is_member": true }))
+            }
+            ("DELETE", ["lists", id, "members", user_id]) => {
+                if let (Some(list), Some(user)) = (parse_str_id(id), parse_str_id(user_id)) {
+                    self.list_members.remove(&(list, user));
+                }
+                ok(json!({ "is_member": false }))
+            }
+            ("GET", ["users", id, "owned_lists"]) => {
+                let lists = self
+                    .lists
+                    .iter()
+                    .filter(|(_, list)| list["owner_id"].as_str() == Some(id))
+                    .map(|(id, _)| *id)
+                    .collect::<Vec<_>>();
+                self.page(Collection::Lists, lists, query)
+            }
+
+            // stream rules
+            ("GET", ["tweets", "search", "stream", "rules"]) => {
+                let rules = self.rules.values().cloned().collect::<Vec<_>>();
+                (
+                    200,
+                    json!({
+                        "data": rules,
+                        "meta": { "sent": now(), "result_count": rules.len() },
+                    }),
+                )
+            }
+            ("POST", ["tweets", "search", "stream", "rules"]) => {
+                self.post_rules(&body, query.contains_key("dry_run"))
+            }
+
+            _ => error(
+                404,
+                "Not Found",
+                &format!("No mock for {method} /2/{}", segments.join("/")),
+            ),
+        }
+    }
+
+    fn post_tweet(&mut self, me: Option<u64>, body: &Value) -> Reply {
+        let id = self.next_id();
+        let text = body["text"].as_str().unwrap_or_default().to_string();
+        let mut tweet = json!({
+            "id": id.to_string(),
+            "text": text,
+            "created_at": now(),
+            "edit_history_tweet_ids": [id.to_string()],
+        });
+        if let Some(me) = me {
+            tweet["author_id"] = Value::String(me.to_string());
+        }
+        if let Some(reply_to) = body["reply"]["in_reply_to_tweet_id"].as_str() {
+            tweet["referenced_tweets"] = json!([{ "type": "replied_to", "id": reply_to }]);
+        } else if let Some(quoted) = body["quote_tweet_id"].as_str() {
+            tweet["referenced_tweets"] = json!([{ "type": "quoted", "id": quoted }]);
+        }
+        self.tweets.insert(id, tweet);
+        ok(json!({ "id": id.to_string(), "text": text }))
+    }
+
+    fn post_rules(&mut self, body: &Value, dry_run: bool) -> Reply {
+        let mut added = Vec::new();
+        for rule in body["add"].as_array().into_iter().flatten() {
+            let id = self.next_id();
+            let mut rule = rule.clone();
+            rule["id"] = Value::String(id.to_string());
+            if rule.get("tag").map(Value::is_null).unwrap_or(false) {
+                rule.as_object_mut().unwrap().remove("tag");
+            }
+            added.push((id, rule));
+        }
+        let mut deleted = 0;
+        let mut not_deleted = 0;
+        for id in body["delete"]["ids"].as_array().into_iter().flatten() {
+            match parse_id(id) {
+                Some(id) if self.rules.contains_key(&id) => {
+                    if !dry_run {
+                        self.rules.remove(&id);
+                    }
+                    deleted += 1
+                }
+                _ => not_deleted += 1,
+            }
+        }
+        let data = added
+            .iter()
+            .map(|(_, rule)| rule.clone())
+            .collect::<Vec<_>>();
+        let created = added.len();
+        if !dry_run {
+            self.rules.extend(added);
+        }
+        (
+            200,
+            json!({
+                "data": data,
+                "meta": {
+                    "sent": now(),
+                    "summary": {
+                        "created": created,
+                        "not_created": 0,
+                        "deleted": deleted,
+                        "not_deleted": not_deleted,
+                        "valid": created,
+                        "invalid": 0,
+                    }
+                }
+            }),
+        )
+    }
+
+    fn user_by_username(&self, username: &str) -> Option<u64> {
+        self.users
+            .iter()
+            .find(|(_, user)| {
+                user["username"]
+                    .as_str()
+                    .map(|name| name.eq_ignore_ascii_case(username))
+                    .unwrap_or(false)
+            })
+            .map(|(id, _)| *id)
+    }
+
+    fn lookup(&mut self, collection: Collection, id: &str) -> Reply {
+        match parse_str_id(id).and_then(|key| self.collection(collection).get(&key)) {
+            Some(value) => ok(value.clone()),
+            None => not_found(collection.resource_type(), "id", id),
+        }
+    }
+
+    fn lookup_many(&mut self, collection: Collection, ids: Vec<String>) -> Reply {
+        let mut data = Vec::new();
+        let mut errors = Vec::new();
+        for id in ids {
+            match parse_str_id(&id).and_then(|key| self.collection(collection).get(&key)) {
+                Some(value) => data.push(value.clone()),
+                None => errors.push(not_found_error(collection.resource_type(), "ids", &id)),
+            }
+        }
+        let mut reply = Map::new();
+        if !data.is_empty() {
+            reply.insert("data".to_string(), Value::Array(data));
+        }
+        if !errors.is_empty() {
+            reply.insert("errors".to_string(), Value::Array(errors));
+        }
+        (200, Value::Object(reply))
+    }
+
+    /// A page of `ids` from `collection` honouring `max_results` and `pagination_token`
+    fn page(
+        &mut self,
+        collection: Collection,
+        ids: Vec<u64>,
+        query: &HashMap<String, String>,
+    ) -> Reply {
+        let max_results = query
+            .get("max_results")
+            .and_then(|max| max.parse::<usize>().ok())
+            .unwrap_or(100)
+            .max(1);
+        let offset = query
+            .get("pagination_token")
+            .and_then(|token| token.parse::<usize>().ok())
+            .unwrap_or(0);
+        let items = self.collection(collection);
+        let data = ids
+            .iter()
+            .skip(offset)
+            .take(max_results)
+            .filter_map(|id| items.get(id).cloned())
+            .collect::<Vec<_>>();
+        let mut meta = json!({ "result_count": data.len() });
+        if offset + max_results < ids.len() {
+            meta["next_token"] = Value::String((offset + max_results).to_string());
+        }
+        if offset > 0 {
+            meta["previous_token"] = Value::String(offset.saturating_sub(max_results).to_string());
+        }
+        if collection == Collection::Tweets {
+            if let (Some(newest), Some(oldest)) = (data.first(), data.last()) {
+                meta["newest_id"] = newest["id"].clone();
+                meta["oldest_id"] = oldest["id"].clone();
+            }
+        }
+        if data.is_empty() {
+            (200, json!({ "meta": meta }))
+        } else {
+            (200, json!({ "data": data, "meta": meta }))
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) enum Collection {
+    Tweets,
+    Users,
+    Lists,
+}
+
+impl Collection {
+    fn resource_type(self) -> &'static str {
+        match self {
+            Collection::Tweets => "tweet",
+            Collection::Users => "user",
+            Collection::Lists => "list",
+        }
+    }
+}
+
+pub(crate) fn now() -> String {
+    OffsetDateTime::now_utc().format(&Rfc3339).unwrap()
+}
+
+pub(crate) fn parse_id(value: &Value) -> Option<u64> {
+    match value {
+        Value::String(id) => id.parse().ok(),
+        Value::Number(id) => id.as_u64(),
+        _ => None,
+    }
+}
+
+fn parse_str_id(id: &str) -> Option<u64> {
+    id.parse().ok()
+}
+
+fn ok(data: Value) -> Reply {
+    (200, json!({ "data": data }))
+}
+
+pub(crate) fn error(status: u16, title: &str, detail: &str) -> Reply {
+    (
+        status,
+        json!({
+            "title": title,
+            "detail": detail,
+            "type": "about:blank",
+            "status": status,
+        }),
+    )
+}
+
+fn not_found_error(resource_type: &str, parameter: &str, value: &str) -> Value {
+    json!({
+        "value": value,
+        "detail": format!("Could not find {resource_type} with {parameter}: [{value}]."),
+        "title": "Not Found Error",
+        "resource_type": resource_type,
+        "parameter": parameter,
+        "resource_id": value,
+        "type": "https://api.twitter.com/2/problems/resource-not-found",
+    })
+}
+
+fn not_found(resource_type: &str, parameter: &str, value: &str) -> Reply {
+    (
+        200,
+        json!({ "errors": [not_found_error(resource_type, parameter, value)] }),
+    )
+}
@@ -0,0 +1,203 @@
+//! Pluggable HTTP layer of [`TwitterApi`](crate::TwitterApi).
+//!
+//! Every request goes through a [`TwitterTransport`] after it has been built and authorized.
+//! The default [`ReqwestTransport`] sends it over the network, but any implementation can be
+//! installed with [`TwitterApi::with_transport`](crate::TwitterApi::with_transport), for
+//! example to answer requests in unit tests without opening a socket, to add caching or
+//! logging, or to use another HTTP stack.
+//!
+//! ```
+//! use async_trait::async_trait;
+//! use reqwest::{Request, StatusCode};
+//! use twitter_v2::authorization::BearerToken;
+//! use twitter_v2::transport::{TransportResponse, TwitterTransport};
+//! use twitter_v2::TwitterApi;
+//!
+//! struct Canned;
+//!
+//! #[async_trait]
+//! impl TwitterTransport for Canned {
+//!     async fn execute(&self, _request: Request) -> twitter_v2::Result<TransportResponse> {
+//!         Ok(TransportResponse::new(
+//!             StatusCode::OK,
+//!             r#"{"data": {"id": "20", "text": "just setting up my twttr"}}"#,
+//!         ))
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() -> twitter_v2::Result<()> {
+//! let api = TwitterApi::new(BearerToken::new("")).with_transport(Canned);
+//! let tweet = api.get_tweet(20).send().await?.into_data().unwrap();
+//! assert_eq!(tweet.text, "just setting up my twttr");
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{Error, Result};
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::prelude::*;
+use futures::stream::BoxStream;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Client, Request, Response, StatusCode};
+use std::fmt;
+use time::OffsetDateTime;
+
+#[async_trait]
+pub trait TwitterTransport: Send + Sync + 'static {
+    /// Send an authorized request. Non-success status codes must be returned as responses,
+    /// they are turned into errors by the client.
+    async fn execute(&self, request: Request) -> Result<TransportResponse>;
+}
+
+pub enum TransportBody {
+    Bytes(Bytes),
+    Stream(BoxStream<'static, Result<Bytes>>),
+}
+
+impl fmt::Debug for TransportBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bytes(bytes) => f.debug_tuple("Bytes").field(bytes).finish(),
+            Self::Stream(_) => f.debug_tuple("Stream").finish(),
+        }
+    }
+}
+
+impl From<Bytes> for TransportBody {
+    fn from(bytes: Bytes) -> Self {
+        Self::Bytes(bytes)
+    }
+}
+
+impl From<Vec<u8>> for TransportBody {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::Bytes(bytes.into())
+    }
+}
+
+impl From<String> for TransportBody {
+    fn from(text: String) -> Self {
+        Self::Bytes(text.into())
+    }
+}
+
+impl From<&'static str> for TransportBody {
+    fn from(text: &'static str) -> Self {
+        Self::Bytes(text.into())
+    }
+}
+
+#[derive(Debug)]
+pub struct TransportResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: TransportBody,
+}
+
+impl TransportResponse {
+    pub fn new(status: StatusCode, body: impl Into<TransportBody>) -> Self {
+        Self {
+            status,
+            headers: HeaderMap::new(),
+            body: body.into(),
+        }
+    }
+
+    /// A response whose body is read incrementally, as for the streaming endpoints
+    pub fn streaming(
+        status: StatusCode,
+        body: impl Stream<Item = Result<Bytes>> + Send + 'static,
+    ) -> Self {
+        Self::new(status, TransportBody::Stream(body.boxed()))
+    }
+
+    pub fn header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.insert(name, value);
+        self
+    }
+
+    pub fn rate_limit(&self) -> Option<RateLimit> {
+        RateLimit::from_headers(&self.headers)
+    }
+
+    pub(crate) fn into_response(self) -> Response {
+        let body = match self.body {
+            TransportBody::Bytes(bytes) => reqwest::Body::from(bytes),
+            TransportBody::Stream(stream) => reqwest::Body::wrap_stream(stream),
+        };
+        let mut response = http::Response::new(body);
+        *response.status_mut() = self.status;
+        *response.headers_mut() = self.headers;
+        response.into()
+    }
+}
+
+impl From<Response> for TransportResponse {
+    fn from(response: Response) -> Self {
+        Self {
+            status: response.status(),
+            headers: response.headers().clone(),
+            body: TransportBody::Stream(response.bytes_stream().map_err(Error::from).boxed()),
+        }
+    }
+}
+
+/// The rate limit of an endpoint as reported by the `x-rate-limit-*` response headers
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct RateLimit {
+    pub limit: u32,
+    pub remaining: u32,
+    pub reset: OffsetDateTime,
+}
+
+impl RateLimit {
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let header = |name: &str| headers.get(name)?.to_str().ok()?.parse::<i64>().ok();
+        Some(Self {
+            limit: header("x-rate-limit-limit")?.try_into().ok()?,
+            remaining: header("x-rate-limit-remaining")?.try_into().ok()?,
+            reset: OffsetDateTime::from_unix_timestamp(header("x-rate-limit-reset")?).ok()?,
+        })
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining == 0 && self.reset > OffsetDateTime::now_utc()
+    }
+}
+
+/// Sends requests with a [`reqwest::Client`]
+#[derive(Clone, Debug)]
+pub struct ReqwestTransport {
+    client: Client,
+}
+
+impl ReqwestTransport {
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+}
+
+impl Default for ReqwestTransport {
+    fn default() -> Self {
+        Self::new(Client::builder().pool_max_idle_per_host(0).build().unwrap())
+    }
+}
+
+impl From<Client> for ReqwestTransport {
+    fn from(client: Client) -> Self {
+        Self::new(client)
+    }
+}
+
+#[async_trait]
+impl TwitterTransport for ReqwestTransport {
+    async fn execute(&self, request: Request) -> Result<TransportResponse> {
+        Ok(self.client.execute(request).await?.into())
+    }
+}
@@ -1,3 +1,4 @@
+use super::trace_event;
 use crate::{Error, Result};
 use futures::prelude::*;
 use pin_project_lite::pin_project;
@@ -47,6 +48,7 @@ where
                 if err.is_eof() {
                     None
                 } else {
+                    trace_event!(tracing::Level::WARN, error = %err, "invalid stream item");
                     Some(Err(err.into()))
                 }
             }
@@ -76,8 +78,15 @@ where
                         return Poll::Ready(Some(item));
                     }
                 }
-                Some(Err(err)) => return Poll::Ready(Some(Err(err.into()))),
-                None => return Poll::Ready(None),
+                Some(Err(err)) => {
+                    let err = err.into();
+                    trace_event!(tracing::Level::WARN, error = %err, "stream disconnected");
+                    return Poll::Ready(Some(Err(err)));
+                }
+                None => {
+                    trace_event!(tracing::Level::INFO, "stream ended");
+                    return Poll::Ready(None);
+                }
             }
         }
         Poll::Pending
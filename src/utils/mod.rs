@@ -4,3 +4,103 @@ pub mod serde;
 
 pub use self::percent_encoding::{percent_encode, url};
 pub use json_stream::JsonStream;
+
+/// Emit a `tracing` event if the `tracing` feature is enabled
+macro_rules! trace_event {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        tracing::event!($($arg)*);
+    };
+}
+pub(crate) use trace_event;
+
+/// The endpoints called by this crate relative to the API base URL, or to the host for v1.1,
+/// in which segments starting with `:` match any value. Literal segments come before
+/// parameters they overlap.
+const ENDPOINTS: &[&str] = &[
+    "1.1/account_activity/all/:env_name/subscriptions.json",
+    "1.1/account_activity/all/:env_name/subscriptions/list.json",
+    "1.1/account_activity/all/:env_name/subscriptions/:user_id.json",
+    "1.1/account_activity/all/:env_name/webhooks.json",
+    "1.1/account_activity/all/:env_name/webhooks/:webhook_id.json",
+    "compliance/jobs",
+    "compliance/jobs/:id",
+    "compliance/job/:id",
+    "lists",
+    "lists/:id",
+    "lists/:id/followers",
+    "lists/:id/members",
+    "lists/:id/members/:user_id",
+    "lists/:id/tweets",
+    "spaces",
+    "spaces/by/creator_ids",
+    "spaces/search",
+    "spaces/:id",
+    "spaces/:id/buyers",
+    "spaces/:id/tweets",
+    "tweets",
+    "tweets/counts/all",
+    "tweets/counts/recent",
+    "tweets/sample/stream",
+    "tweets/search/all",
+    "tweets/search/recent",
+    "tweets/search/stream",
+    "tweets/search/stream/rules",
+    "tweets/:id",
+    "tweets/:id/hidden",
+    "tweets/:id/liking_users",
+    "tweets/:id/quote_tweets",
+    "tweets/:id/retweeted_by",
+    "usage/tweets",
+    "users",
+    "users/by",
+    "users/me",
+    "users/by/username/:username",
+    "users/:id",
+    "users/:id/blocking",
+    "users/:id/blocking/:target_user_id",
+    "users/:id/bookmarks",
+    "users/:id/bookmarks/:tweet_id",
+    "users/:id/followed_lists",
+    "users/:id/followed_lists/:list_id",
+    "users/:id/followers",
+    "users/:id/following",
+    "users/:id/following/:target_user_id",
+    "users/:id/liked_tweets",
+    "users/:id/likes",
+    "users/:id/likes/:tweet_id",
+    "users/:id/list_memberships",
+    "users/:id/lists",
+    "users/:id/mentions",
+    "users/:id/muting",
+    "users/:id/muting/:target_user_id",
+    "users/:id/owned_lists",
+    "users/:id/pinned_lists",
+    "users/:id/pinned_lists/:list_id",
+    "users/:id/retweets",
+    "users/:id/retweets/:source_tweet_id",
+    "users/:id/tweets",
+];
+
+/// The endpoint of a request path, e.g. `users/by/username/:username`, so that requests to
+/// one endpoint share a label whatever their IDs. Paths outside of the API are `unknown`.
+pub fn endpoint_template(path: &str) -> String {
+    let path = path.trim_matches('/');
+    let path = path.strip_prefix("2/").unwrap_or(path);
+    ENDPOINTS
+        .iter()
+        .find(|endpoint| matches_endpoint(endpoint, path))
+        .unwrap_or(&"unknown")
+        .to_string()
+}
+
+/// Whether `path` matches `pattern`, in which segments starting with `:` match any value
+pub fn matches_endpoint(pattern: &str, path: &str) -> bool {
+    let pattern = pattern.trim_matches('/').split('/').collect::<Vec<_>>();
+    let path = path.split('/').collect::<Vec<_>>();
+    pattern.len() == path.len()
+        && pattern
+            .iter()
+            .zip(path)
+            .all(|(pattern, segment)| pattern.starts_with(':') || *pattern == segment)
+}
@@ -22,7 +22,7 @@ const URL: &AsciiSet = &CONTROLS
     .add(b'[')
     .add(b']');
 
-pub fn percent_encode<T>(input: &T) -> PercentEncode
+pub fn percent_encode<T>(input: &T) -> PercentEncode<'_>
 where
     T: AsRef<[u8]>,
 {
@@ -30,7 +30,7 @@ where
 }
 
 macro_rules! url {
-    ($fmt_str:literal, $($var:ident),*) => {
+    ($fmt_str:literal, $($var:expr),*) => {
         format!($fmt_str, $($crate::utils::percent_encode(&$var.to_string())),*)
     }
 }
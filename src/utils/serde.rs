@@ -97,3 +97,67 @@ pub mod option_duration_mins {
         Ok(Option::<i64>::deserialize(deserializer)?.map(Duration::minutes))
     }
 }
+
+pub mod string_u64 {
+    use super::*;
+
+    // serialize u64 as a string, as the API does for counts which may exceed 2^53
+    pub fn serialize<S: Serializer>(value: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        value.to_string().serialize(serializer)
+    }
+
+    struct StringU64Visitor;
+
+    impl<'de> Visitor<'de> for StringU64Visitor {
+        type Value = u64;
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("an unsigned integer or a string holding one")
+        }
+        fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+        where
+            E: DeError,
+        {
+            Ok(v)
+        }
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: DeError,
+        {
+            v.parse().map_err(E::custom)
+        }
+    }
+
+    // deserialize u64 from a string or a number
+    pub fn deserialize<'a, D: Deserializer<'a>>(deserializer: D) -> Result<u64, D::Error> {
+        deserializer.deserialize_any(StringU64Visitor)
+    }
+}
+
+pub mod vec_rfc3339 {
+    use super::*;
+    use time::format_description::well_known::Rfc3339;
+    use time::OffsetDateTime;
+
+    // serialize OffsetDateTimes as RFC 3339 strings
+    pub fn serialize<S: Serializer>(
+        times: &[OffsetDateTime],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        times
+            .iter()
+            .map(|time| time.format(&Rfc3339))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(serde::ser::Error::custom)?
+            .serialize(serializer)
+    }
+
+    // deserialize OffsetDateTimes from RFC 3339 strings
+    pub fn deserialize<'a, D: Deserializer<'a>>(
+        deserializer: D,
+    ) -> Result<Vec<OffsetDateTime>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|time| OffsetDateTime::parse(time, &Rfc3339).map_err(D::Error::custom))
+            .collect()
+    }
+}
@@ -0,0 +1,137 @@
+//! Receiving Account Activity API events through a webhook.
+//!
+//! Twitter sends a CRC challenge to a webhook when it is registered and about every hour
+//! after that, and signs every delivery of events with the consumer secret of the app.
+//! [`Webhook`] answers the challenges, verifies the `x-twitter-webhooks-signature` header
+//! and parses deliveries into [`AccountActivity`], either by hand or as an axum
+//! [`Router`] which is also a tower `Service`.
+//!
+//! ```no_run
+//! use twitter_v2::data::AccountActivityEvent;
+//! use twitter_v2::webhook::Webhook;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let app = Webhook::new("consumer secret").router(|activity| async move {
+//!     for event in activity.events {
+//!         if let AccountActivityEvent::Follow(follow) = event {
+//!             println!("followed by @{}", follow.source.screen_name);
+//!         }
+//!     }
+//! });
+//! axum::Server::bind(&"0.0.0.0:8080".parse()?)
+//!     .serve(app.into_make_service())
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The webhook itself and the subscriptions of users to it are managed with the
+//! `account_activity` calls of [`TwitterApi`](crate::TwitterApi).
+
+use crate::data::AccountActivity;
+use crate::error::{Error, Result};
+use axum::body::Bytes;
+use axum::http::{HeaderMap, StatusCode, Uri};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use futures::prelude::*;
+use hmac::{Hmac, Mac};
+use serde_json::json;
+use sha2::Sha256;
+use std::sync::Arc;
+
+/// The header holding the signature of a delivery
+pub const SIGNATURE_HEADER: &str = "x-twitter-webhooks-signature";
+
+#[derive(Clone)]
+pub struct Webhook {
+    consumer_secret: Arc<str>,
+}
+
+impl Webhook {
+    pub fn new(consumer_secret: impl AsRef<str>) -> Self {
+        Self {
+            consumer_secret: consumer_secret.as_ref().into(),
+        }
+    }
+
+    fn mac(&self) -> Hmac<Sha256> {
+        Hmac::new_from_slice(self.consumer_secret.as_bytes()).expect("HMAC takes keys of any size")
+    }
+
+    /// The `response_token` answering the CRC challenge `crc_token`
+    pub fn crc_response_token(&self, crc_token: &str) -> String {
+        let mut mac = self.mac();
+        mac.update(crc_token.as_bytes());
+        format!("sha256={}", base64::encode(mac.finalize().into_bytes()))
+    }
+
+    /// Check that `body` was signed by Twitter, given the value of the signature header
+    pub fn verify(&self, body: &[u8], signature: &str) -> Result<()> {
+        let signature = signature
+            .strip_prefix("sha256=")
+            .and_then(|signature| base64::decode(signature).ok())
+            .ok_or(Error::InvalidWebhookSignature)?;
+        let mut mac = self.mac();
+        mac.update(body);
+        mac.verify_slice(&signature)
+            .map_err(|_| Error::InvalidWebhookSignature)
+    }
+
+    /// Verify and parse a delivery of events
+    pub fn parse(&self, body: &[u8], signature: &str) -> Result<AccountActivity> {
+        self.verify(body, signature)?;
+        Ok(serde_json::from_slice(body)?)
+    }
+
+    /// A router answering CRC challenges with `GET /` and passing verified deliveries of
+    /// `POST /` to `handler`. Twitter expects a reply within 3 seconds, so long running work
+    /// should be spawned.
+    pub fn router<F, Fut>(self, handler: F) -> Router
+    where
+        F: Fn(AccountActivity) -> Fut + Clone + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let crc = self.clone();
+        Router::new().route(
+            "/",
+            get(move |uri: Uri| async move { crc.handle_crc(&uri) }).post(
+                move |headers: HeaderMap, body: Bytes| async move {
+                    let signature = headers
+                        .get(SIGNATURE_HEADER)
+                        .and_then(|value| value.to_str().ok())
+                        .unwrap_or_default();
+                    match self.parse(&body, signature) {
+                        Ok(activity) => {
+                            handler(activity).await;
+                            StatusCode::OK
+                        }
+                        Err(Error::InvalidWebhookSignature) => StatusCode::UNAUTHORIZED,
+                        Err(_) => StatusCode::BAD_REQUEST,
+                    }
+                },
+            ),
+        )
+    }
+
+    fn handle_crc(&self, uri: &Uri) -> Response {
+        let crc_token = url::form_urlencoded::parse(uri.query().unwrap_or_default().as_bytes())
+            .find(|(key, _)| key == "crc_token")
+            .map(|(_, value)| value);
+        match crc_token {
+            Some(crc_token) => Json(json!({
+                "response_token": self.crc_response_token(&crc_token)
+            }))
+            .into_response(),
+            None => StatusCode::BAD_REQUEST.into_response(),
+        }
+    }
+}
+
+impl std::fmt::Debug for Webhook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Webhook").finish_non_exhaustive()
+    }
+}
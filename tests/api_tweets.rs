@@ -14,7 +14,7 @@ fn rand_str(len: usize) -> String {
 async fn get_tweets() -> Result<()> {
     let res = get_api_user_ctx()
         .await
-        .get_tweets(&[1261326399320715264, 1278347468690915330])
+        .get_tweets([1261326399320715264, 1278347468690915330])
         .send()
         .await?;
     assert_eq!(res.data().unwrap().len(), 2);
@@ -46,7 +46,7 @@ async fn manage_tweet() -> Result<()> {
 async fn get_users() -> Result<()> {
     let res = get_api_user_ctx()
         .await
-        .get_users(&[2244994945, 6253282])
+        .get_users([2244994945, 6253282])
         .send()
         .await?;
     assert_eq!(res.data().unwrap().len(), 2);
@@ -7,7 +7,7 @@ use twitter_v2::Result;
 async fn get_users_by() -> Result<()> {
     let res = get_api_user_ctx()
         .await
-        .get_users_by_usernames(&["TwitterDev", "Twitter"])
+        .get_users_by_usernames(["TwitterDev", "Twitter"])
         .send()
         .await?;
     assert_eq!(res.data().unwrap().len(), 2);
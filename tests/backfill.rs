@@ -0,0 +1,195 @@
+mod common;
+
+use common::fake::{ok, query, FakeTransport};
+use futures::prelude::*;
+use serde_json::json;
+use std::time::Duration;
+use time::macros::datetime;
+use twitter_v2::authorization::BearerToken;
+use twitter_v2::requests::{BackfillWindow, FileCheckpointStore};
+use twitter_v2::TwitterApi;
+
+/// Serves two pages of counts and two pages per search window. Both windows contain
+/// Tweet 1.
+fn get_api() -> (TwitterApi<BearerToken>, FakeTransport) {
+    let transport = FakeTransport::new(|request| {
+        let url = request.url();
+        match url.path() {
+            "/2/tweets/counts/all" => match query(url, "next_token") {
+                None => ok(json!({
+                    "data": [
+                        {"start": "2022-01-01T00:00:00Z", "end": "2022-01-01T01:00:00Z", "tweet_count": 30},
+                    ],
+                    "meta": {"total_tweet_count": 30, "next_token": "counts2"}
+                })),
+                Some(_) => ok(json!({
+                    "data": [
+                        {"start": "2022-01-01T01:00:00Z", "end": "2022-01-01T02:00:00Z", "tweet_count": 80},
+                        {"start": "2022-01-01T02:00:00Z", "end": "2022-01-01T03:00:00Z", "tweet_count": 10},
+                    ],
+                    "meta": {"total_tweet_count": 90}
+                })),
+            },
+            _ => {
+                let day = query(url, "start_time").unwrap()[8..10]
+                    .parse::<u64>()
+                    .unwrap();
+                match query(url, "pagination_token") {
+                    None => ok(json!({
+                        "data": [
+                            {"id": "1", "text": "shared"},
+                            {"id": (day * 10).to_string(), "text": "first"}
+                        ],
+                        "meta": {"result_count": 2, "next_token": format!("page2-{day}")}
+                    })),
+                    Some(_) => ok(json!({
+                        "data": [{"id": (day * 10 + 1).to_string(), "text": "second"}],
+                        "meta": {"result_count": 1}
+                    })),
+                }
+            }
+        }
+    });
+    (transport.api(), transport)
+}
+
+/// The start time and pagination token of every search
+fn searches(transport: &FakeTransport) -> Vec<(String, Option<String>)> {
+    transport
+        .requests_to("/2/tweets/search/all")
+        .iter()
+        .map(|request| {
+            (
+                request.query("start_time").unwrap(),
+                request.query("pagination_token"),
+            )
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn backfill_windows_and_dedup() {
+    let (api, transport) = get_api();
+    let mut backfill = api.backfill(
+        "from:TwitterDev",
+        datetime!(2022-01-01 0:00 UTC),
+        datetime!(2022-01-03 0:00 UTC),
+    );
+    backfill.min_interval(Duration::ZERO);
+    let mut ids = backfill
+        .run()
+        .await
+        .unwrap()
+        .map_ok(|page| page.data.unwrap_or_default())
+        .try_concat()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|tweet| tweet.id.as_u64())
+        .collect::<Vec<_>>();
+    ids.sort_unstable();
+    assert_eq!(ids, [1, 10, 11, 20, 21]);
+    assert_eq!(searches(&transport).len(), 4);
+    assert!(backfill.progress().await.unwrap().unwrap().is_complete());
+
+    // a completed backfill does not fetch anything again
+    assert_eq!(backfill.run().await.unwrap().count().await, 0);
+    assert_eq!(searches(&transport).len(), 4);
+}
+
+#[tokio::test]
+async fn backfill_resume() {
+    let path = std::env::temp_dir().join(format!("backfill-{}.json", rand::random::<u64>()));
+    let (api, transport) = get_api();
+    let mut backfill = api.backfill(
+        "from:TwitterDev",
+        datetime!(2022-01-01 0:00 UTC),
+        datetime!(2022-01-03 0:00 UTC),
+    );
+    backfill
+        .min_interval(Duration::ZERO)
+        .concurrency(1)
+        .checkpoint(FileCheckpointStore::new(&path));
+    {
+        let mut pages = backfill.run().await.unwrap();
+        pages.try_next().await.unwrap().unwrap();
+        pages.try_next().await.unwrap().unwrap();
+        // crash while the last page is being processed
+    }
+
+    let (api, transport_after) = get_api();
+    let mut backfill = api.backfill(
+        "from:TwitterDev",
+        datetime!(2022-01-01 0:00 UTC),
+        datetime!(2022-01-03 0:00 UTC),
+    );
+    backfill
+        .min_interval(Duration::ZERO)
+        .checkpoint(FileCheckpointStore::new(&path));
+    let windows = backfill.progress().await.unwrap().unwrap().windows;
+    assert!(!windows[0].complete);
+    assert_eq!(windows[0].next_token.as_deref(), Some("page2-1"));
+    backfill
+        .run()
+        .await
+        .unwrap()
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(
+        searches(&transport),
+        [
+            ("2022-01-01T00:00:00Z".to_string(), None),
+            (
+                "2022-01-01T00:00:00Z".to_string(),
+                Some("page2-1".to_string())
+            ),
+        ]
+    );
+    // the second page of the first window is fetched again, not the first one
+    let mut searches = searches(&transport_after);
+    searches.sort();
+    assert_eq!(
+        searches,
+        [
+            (
+                "2022-01-01T00:00:00Z".to_string(),
+                Some("page2-1".to_string())
+            ),
+            ("2022-01-02T00:00:00Z".to_string(), None),
+            (
+                "2022-01-02T00:00:00Z".to_string(),
+                Some("page2-2".to_string())
+            ),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn backfill_plan_from_counts() {
+    let (api, transport) = get_api();
+    let mut backfill = api.backfill(
+        "from:TwitterDev",
+        datetime!(2022-01-01 0:00 UTC),
+        datetime!(2022-01-01 3:00 UTC),
+    );
+    backfill.min_interval(Duration::ZERO).tweets_per_window(100);
+    assert_eq!(
+        backfill.plan().await.unwrap(),
+        [
+            BackfillWindow::new(
+                datetime!(2022-01-01 0:00 UTC),
+                datetime!(2022-01-01 2:00 UTC)
+            ),
+            BackfillWindow::new(
+                datetime!(2022-01-01 2:00 UTC),
+                datetime!(2022-01-01 3:00 UTC)
+            ),
+        ]
+    );
+    let counts = transport.requests_to("/2/tweets/counts/all");
+    assert_eq!(counts.len(), 2);
+    assert_eq!(counts[1].query("next_token").as_deref(), Some("counts2"));
+}
@@ -0,0 +1,113 @@
+mod common;
+
+use common::fake::{ok, query, FakeTransport};
+use futures::prelude::*;
+use serde_json::json;
+use twitter_v2::authorization::BearerToken;
+use twitter_v2::query::{TweetField, UserField};
+use twitter_v2::TwitterApi;
+
+/// Serves every requested user or Tweet, except 404
+fn get_api() -> (TwitterApi<BearerToken>, FakeTransport) {
+    let transport = FakeTransport::new(|request| {
+        let url = request.url();
+        let ids = query(url, "ids")
+            .map(|ids| ids.split(',').map(str::to_string).collect::<Vec<_>>())
+            .unwrap_or_default();
+        let (found, missing): (Vec<_>, Vec<_>) = ids.into_iter().partition(|id| id != "404");
+        let data = found
+            .iter()
+            .map(|id| match url.path() {
+                "/2/users" => json!({"id": id, "name": "User", "username": format!("user{id}")}),
+                _ => json!({"id": id, "text": format!("tweet {id}")}),
+            })
+            .collect::<Vec<_>>();
+        let errors = missing
+            .iter()
+            .map(|id| {
+                json!({
+                    "value": id,
+                    "detail": format!("Could not find user with ids: [{id}]."),
+                    "title": "Not Found Error",
+                    "resource_type": "user",
+                    "parameter": "ids",
+                    "resource_id": id,
+                    "type": "https://api.twitter.com/2/problems/resource-not-found"
+                })
+            })
+            .collect::<Vec<_>>();
+        ok(json!({"data": data, "errors": errors}))
+    });
+    (transport.api(), transport)
+}
+
+#[tokio::test]
+async fn batcher_coalesces_lookups() {
+    let (api, transport) = get_api();
+    let batcher = api.batcher();
+    let (users, tweet) = future::join(
+        future::try_join_all([
+            batcher
+                .get_user(1)
+                .user_fields([UserField::CreatedAt])
+                .send()
+                .boxed(),
+            batcher
+                .get_user(2)
+                .user_fields([UserField::Verified])
+                .send()
+                .boxed(),
+            batcher.get_user(1).send().boxed(),
+        ]),
+        batcher
+            .get_tweet(20)
+            .tweet_fields([TweetField::AuthorId])
+            .send(),
+    )
+    .await;
+    let users = users.unwrap();
+    assert_eq!(users[0].data().unwrap().username, "user1");
+    assert_eq!(users[1].data().unwrap().username, "user2");
+    assert_eq!(users[2].data().unwrap().username, "user1");
+    assert_eq!(tweet.unwrap().data().unwrap().text, "tweet 20");
+
+    let mut urls = transport
+        .requests()
+        .iter()
+        .map(|request| request.url.to_string())
+        .collect::<Vec<_>>();
+    urls.sort();
+    assert_eq!(
+        urls,
+        [
+            "https://api.twitter.com/2/tweets?ids=20&tweet.fields=author_id",
+            "https://api.twitter.com/2/users?ids=1%2C2&user.fields=created_at%2Cverified",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn batcher_not_found() {
+    let (api, _) = get_api();
+    let batcher = api.batcher();
+    let (found, missing) =
+        future::try_join(batcher.get_user(1).send(), batcher.get_user(404).send())
+            .await
+            .unwrap();
+    assert!(found.errors().is_none());
+    assert!(missing.data().is_none());
+    assert_eq!(missing.errors().unwrap()[0].title, "Not Found Error");
+}
+
+#[tokio::test]
+async fn batcher_max_batch_size() {
+    let (api, transport) = get_api();
+    let batcher = api.batcher().max_batch_size(2);
+    let batcher = &batcher;
+    let users =
+        future::try_join_all((1..=5).map(|id| async move { batcher.get_user(id).send().await }))
+            .await
+            .unwrap();
+    assert_eq!(users.len(), 5);
+    assert_eq!(transport.requests().len(), 3);
+}
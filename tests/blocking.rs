@@ -0,0 +1,230 @@
+#![cfg(feature = "blocking")]
+
+mod common;
+
+use axum::routing::post;
+use axum::Router;
+use bytes::Bytes;
+use common::fake::{ok, FakeTransport};
+use futures::prelude::*;
+use reqwest::header::AUTHORIZATION;
+use reqwest::{Method, StatusCode};
+use serde_json::json;
+use std::net::{SocketAddr, TcpListener};
+use std::sync::{Arc, Mutex};
+use twitter_v2::authorization::{BearerToken, Oauth2Client, Oauth2Token, RefreshableOauth2Token};
+use twitter_v2::blocking::TwitterApi;
+use twitter_v2::transport::TransportResponse;
+use twitter_v2::Error;
+
+fn user(id: &str) -> serde_json::Value {
+    json!({"id": id, "name": "User", "username": format!("user{id}")})
+}
+
+/// A blocking client in front of a fake serving a user timeline of two pages, a tweet
+/// stream and the users endpoints
+fn get_api() -> (TwitterApi<BearerToken>, FakeTransport) {
+    let transport = FakeTransport::new(|request| match request.url().path() {
+        "/2/tweets/20" => ok(json!({"data": {"id": "20", "text": "just setting up my twttr"}})),
+        "/2/users/12/tweets" => match request
+            .url()
+            .query_pairs()
+            .any(|(key, _)| key == "pagination_token")
+        {
+            false => ok(json!({
+                "data": [{"id": "2", "text": "two"}, {"id": "1", "text": "one"}],
+                "meta": {"result_count": 2, "next_token": "page2"}
+            })),
+            true => ok(json!({
+                "data": [{"id": "0", "text": "zero"}],
+                "meta": {"result_count": 1}
+            })),
+        },
+        "/2/tweets/sample/stream" => Ok(TransportResponse::streaming(
+            StatusCode::OK,
+            stream::iter([
+                Ok(Bytes::from_static(
+                    b"{\"data\":{\"id\":\"1\",\"text\":\"a\"}}\r\n",
+                )),
+                Ok(Bytes::from_static(b"\r\n{\"data\":{\"id\":\"2\",")),
+                Ok(Bytes::from_static(b"\"text\":\"b\"}}\r\n")),
+            ]),
+        )),
+        "/2/users/me" => ok(json!({"data": user("12")})),
+        "/2/users/12/mentions" => ok(json!({
+            "data": [{"id": "3", "text": "@jack hi"}],
+            "meta": {"result_count": 1}
+        })),
+        "/2/users/12/followers" => ok(json!({"data": [user("13")], "meta": {"result_count": 1}})),
+        "/2/users/12/following" => ok(json!({"data": {"following": true}})),
+        path => panic!("unexpected request to {path}"),
+    });
+    (transport.api().into(), transport)
+}
+
+#[test]
+fn blocking_send() {
+    let (api, transport) = get_api();
+    let tweet = api.get_tweet(20).send().unwrap().into_data().unwrap();
+    assert_eq!(tweet.text, "just setting up my twttr");
+    let following = api
+        .post_user_following(12, 13)
+        .unwrap()
+        .into_data()
+        .unwrap();
+    assert!(following.following);
+    let follow = &transport.requests_to("/2/users/12/following")[0];
+    assert_eq!(follow.method, Method::POST);
+    assert_eq!(follow.json(), json!({"target_user_id": "13"}));
+}
+
+#[test]
+fn blocking_pages() {
+    let (api, transport) = get_api();
+    let ids = api
+        .get_user_tweets(12)
+        .pages()
+        .flat_map(|page| page.unwrap().into_data().unwrap())
+        .map(|tweet| tweet.id.as_u64())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, [2, 1, 0]);
+    let requests = transport.requests_to("/2/users/12/tweets");
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].query("pagination_token"), None);
+    assert_eq!(
+        requests[1].query("pagination_token").as_deref(),
+        Some("page2")
+    );
+}
+
+#[test]
+fn blocking_stream() {
+    let (api, _) = get_api();
+    let texts = api
+        .get_tweets_sample_stream()
+        .stream()
+        .unwrap()
+        .map(|payload| payload.unwrap().into_data().unwrap().text)
+        .collect::<Vec<_>>();
+    assert_eq!(texts, ["a", "b"]);
+}
+
+#[test]
+fn blocking_with_user_ctx() {
+    let (api, transport) = get_api();
+    let ctx = api.with_user_ctx().unwrap();
+    let followers = ctx.get_my_followers().send().unwrap().into_data().unwrap();
+    assert_eq!(followers[0].id, 13);
+    let mentions = ctx.get_my_mentions().send().unwrap().into_data().unwrap();
+    assert_eq!(mentions[0].text, "@jack hi");
+    assert!(
+        ctx.post_my_following(13)
+            .unwrap()
+            .into_data()
+            .unwrap()
+            .following
+    );
+    assert_eq!(transport.requests_to("/2/users/me").len(), 1);
+}
+
+/// A token endpoint issuing the access token `new`, recording the form bodies it receives
+fn start_token_server() -> (SocketAddr, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let bodies = Arc::new(Mutex::new(Vec::new()));
+    let seen = bodies.clone();
+    let app = Router::new().route(
+        "/token",
+        post(move |body: String| async move {
+            seen.lock().unwrap().push(body);
+            axum::Json(json!({
+                "access_token": "new",
+                "token_type": "bearer",
+                "expires_in": 7200,
+                "refresh_token": "refresh2",
+                "scope": "tweet.read users.read offline.access"
+            }))
+        }),
+    );
+    std::thread::spawn(move || {
+        tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(async move {
+                axum::Server::from_tcp(listener)
+                    .unwrap()
+                    .serve(app.into_make_service())
+                    .await
+            })
+    });
+    (addr, bodies)
+}
+
+fn oauth2_token(refresh_token: Option<&str>) -> Oauth2Token {
+    serde_json::from_value(json!({
+        "access_token": "old",
+        "refresh_token": refresh_token,
+        "expires": "2100-01-01T00:00:00Z",
+        "scopes": ["tweet.read", "users.read", "offline.access"]
+    }))
+    .unwrap()
+}
+
+fn oauth2_client(addr: SocketAddr) -> Oauth2Client {
+    Oauth2Client::new(
+        "client id",
+        "client secret",
+        "http://localhost/callback".parse().unwrap(),
+    )
+    .with_token_endpoints(
+        format!("http://{addr}/token").parse().unwrap(),
+        format!("http://{addr}/revoke").parse().unwrap(),
+    )
+}
+
+#[test]
+fn blocking_refresh_auth() {
+    let (addr, bodies) = start_token_server();
+    let refreshed = Arc::new(Mutex::new(Vec::new()));
+    let on_refresh = refreshed.clone();
+    let auth = RefreshableOauth2Token::new(oauth2_client(addr), oauth2_token(Some("refresh1")))
+        .with_callback(move |token: Oauth2Token| {
+            on_refresh
+                .lock()
+                .unwrap()
+                .push(token.access_token().secret().clone());
+            future::ok(())
+        });
+    let transport = FakeTransport::new(|_| ok(json!({"data": user("12")})));
+    let api = TwitterApi::from(twitter_v2::TwitterApi::new(auth).with_transport(transport.clone()));
+
+    api.get_users_me().send().unwrap();
+    api.refresh_auth().unwrap();
+    api.get_users_me().send().unwrap();
+
+    assert_eq!(*refreshed.lock().unwrap(), ["new"]);
+    assert!(bodies.lock().unwrap()[0].contains("refresh_token=refresh1"));
+    let requests = transport.requests();
+    assert_eq!(requests[0].headers[AUTHORIZATION], "Bearer old");
+    assert_eq!(requests[1].headers[AUTHORIZATION], "Bearer new");
+}
+
+#[test]
+fn blocking_refresh_auth_without_refresh_token() {
+    let (addr, bodies) = start_token_server();
+    let api = TwitterApi::new(RefreshableOauth2Token::new(
+        oauth2_client(addr),
+        oauth2_token(None),
+    ));
+    assert!(matches!(api.refresh_auth(), Err(Error::NoRefreshToken)));
+    assert!(bodies.lock().unwrap().is_empty());
+}
+
+#[test]
+fn blocking_revoke_auth_insecure_url() {
+    let api = TwitterApi::new(RefreshableOauth2Token::new(
+        oauth2_client(([127, 0, 0, 1], 1).into()),
+        oauth2_token(Some("refresh1")),
+    ));
+    // tokens are only ever revoked over https
+    assert!(matches!(api.revoke_auth(), Err(Error::Custom(_))));
+}
@@ -0,0 +1,189 @@
+mod common;
+
+use common::fake::{ok, too_many_requests, FakeTransport};
+use reqwest::header::HeaderValue;
+use reqwest::Method;
+use serde_json::json;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use time::OffsetDateTime;
+use twitter_v2::authorization::BearerToken;
+use twitter_v2::requests::{BulkAction, BulkOutcome, BulkReport};
+use twitter_v2::TwitterApi;
+use url::Url;
+
+/// User 2 is protected, user 3 does not exist, user 4 is rate limited once, user 7 is
+/// rate limited once until the next second and requests about user 5 fail in transport.
+/// The acting user follows user 1.
+fn get_api() -> (TwitterApi<BearerToken>, FakeTransport) {
+    let rate_limited = Arc::new(AtomicBool::new(false));
+    let rate_limited_until_reset = Arc::new(AtomicBool::new(false));
+    let transport = FakeTransport::new(move |request| {
+        if request.method() == Method::GET {
+            assert_eq!(request.url().path(), "/2/users/2244994945/following");
+            return ok(json!({
+                "data": [{"id": "1", "name": "One", "username": "one"}],
+                "meta": {"result_count": 1}
+            }));
+        }
+        let body = request.body().map(|body| body.as_bytes().unwrap());
+        match target(request.url(), body).as_str() {
+            "4" if !rate_limited.swap(true, Ordering::SeqCst) => too_many_requests(),
+            "7" if !rate_limited_until_reset.swap(true, Ordering::SeqCst) => {
+                let reset = OffsetDateTime::now_utc().unix_timestamp() + 1;
+                too_many_requests().map(|response| {
+                    response
+                        .header("x-rate-limit-limit".parse().unwrap(), HeaderValue::from(50))
+                        .header(
+                            "x-rate-limit-remaining".parse().unwrap(),
+                            HeaderValue::from(0),
+                        )
+                        .header(
+                            "x-rate-limit-reset".parse().unwrap(),
+                            HeaderValue::from(reset),
+                        )
+                })
+            }
+            "2" => ok(json!({"data": {"following": false, "pending_follow": true}})),
+            "3" => ok(json!({"errors": [{
+                "title": "Not Found Error",
+                "type": "https://api.twitter.com/2/problems/resource-not-found",
+                "detail": "Could not find user with target_user_id: [3]."
+            }]})),
+            "5" => Err(twitter_v2::Error::custom("connection reset")),
+            _ if request.method() == Method::DELETE => ok(json!({"data": {"following": false}})),
+            _ => ok(json!({"data": {"following": true}})),
+        }
+    });
+    (transport.api(), transport)
+}
+
+/// The target of a follow, in the body, or of an unfollow, at the end of the path
+fn target(url: &Url, body: Option<&[u8]>) -> String {
+    match body {
+        Some(body) => serde_json::from_slice::<serde_json::Value>(body).unwrap()["target_user_id"]
+            .as_str()
+            .unwrap()
+            .to_string(),
+        None => url
+            .path_segments()
+            .unwrap()
+            .next_back()
+            .unwrap()
+            .to_string(),
+    }
+}
+
+fn targets(transport: &FakeTransport) -> Vec<String> {
+    transport
+        .requests()
+        .iter()
+        .filter(|request| request.method != Method::GET)
+        .map(|request| target(&request.url, request.body.as_deref()))
+        .collect()
+}
+
+#[tokio::test]
+async fn bulk_follow_outcomes() {
+    let (api, transport) = get_api();
+    let mut report = BulkReport::new();
+    api.bulk(BulkAction::Follow(2244994945.into()))
+        .rate_limit_wait(Duration::ZERO)
+        .existing([6])
+        .run([1, 2, 3, 4, 6], &mut report)
+        .await
+        .unwrap();
+    assert_eq!(targets(&transport), ["1", "2", "3", "4", "4"]);
+    assert_eq!(report.outcome(1), Some(&BulkOutcome::Applied));
+    assert_eq!(report.outcome(2), Some(&BulkOutcome::Pending));
+    assert_eq!(report.outcome(3), Some(&BulkOutcome::NotFound));
+    assert_eq!(report.outcome(4), Some(&BulkOutcome::Applied));
+    assert_eq!(report.outcome(6), Some(&BulkOutcome::AlreadyApplied));
+}
+
+#[tokio::test]
+async fn bulk_dry_run() {
+    let (api, transport) = get_api();
+    let mut report = BulkReport::new();
+    api.bulk(BulkAction::Block(2244994945.into()))
+        .dry_run(true)
+        .run([1, 2], &mut report)
+        .await
+        .unwrap();
+    assert!(targets(&transport).is_empty());
+    assert_eq!(report.count(&BulkOutcome::WouldApply), 2);
+}
+
+#[tokio::test]
+async fn bulk_resume() {
+    let (api, transport) = get_api();
+    let mut report = BulkReport::new();
+    let mut bulk = api.bulk(BulkAction::Follow(2244994945.into()));
+    bulk.rate_limit_wait(Duration::ZERO);
+    assert!(bulk.run([1, 5, 6], &mut report).await.is_err());
+    assert_eq!(report.entries.len(), 1);
+
+    // the report survives a restart
+    let mut report: BulkReport =
+        serde_json::from_str(&serde_json::to_string(&report).unwrap()).unwrap();
+    assert!(bulk.run([1, 6], &mut report).await.is_ok());
+    assert_eq!(targets(&transport), ["1", "5", "6"]);
+    assert_eq!(report.count(&BulkOutcome::Applied), 2);
+}
+
+#[tokio::test]
+async fn bulk_fetch_existing() {
+    let (api, transport) = get_api();
+    let mut report = BulkReport::new();
+    api.bulk(BulkAction::Follow(2244994945.into()))
+        .fetch_existing(true)
+        .run([1, 6], &mut report)
+        .await
+        .unwrap();
+    assert_eq!(targets(&transport), ["6"]);
+    assert_eq!(report.outcome(1), Some(&BulkOutcome::AlreadyApplied));
+    assert_eq!(report.outcome(6), Some(&BulkOutcome::Applied));
+
+    // only the users not followed are in the desired state of an unfollow
+    let mut report = BulkReport::new();
+    api.bulk(BulkAction::Unfollow(2244994945.into()))
+        .fetch_existing(true)
+        .run([1, 6], &mut report)
+        .await
+        .unwrap();
+    assert_eq!(targets(&transport), ["6", "1"]);
+    assert_eq!(report.outcome(6), Some(&BulkOutcome::AlreadyApplied));
+}
+
+#[tokio::test]
+async fn bulk_write_limit_survives_resume() {
+    let (api, transport) = get_api();
+    let mut report = BulkReport::new();
+    let mut bulk = api.bulk(BulkAction::Follow(2244994945.into()));
+    bulk.limit(2, Duration::from_millis(300));
+    bulk.run([1, 6], &mut report).await.unwrap();
+
+    let mut report: BulkReport =
+        serde_json::from_str(&serde_json::to_string(&report).unwrap()).unwrap();
+    let started = Instant::now();
+    bulk.run([8], &mut report).await.unwrap();
+    assert!(started.elapsed() >= Duration::from_millis(200));
+    assert_eq!(targets(&transport), ["1", "6", "8"]);
+}
+
+#[tokio::test]
+async fn bulk_rate_limit_reset() {
+    let (api, transport) = get_api();
+    let mut report = BulkReport::new();
+    let started = Instant::now();
+    // waits for the reset instead of the hour
+    api.bulk(BulkAction::Follow(2244994945.into()))
+        .rate_limit_wait(Duration::from_secs(3600))
+        .run([7], &mut report)
+        .await
+        .unwrap();
+    assert!(started.elapsed() < Duration::from_secs(3));
+    assert_eq!(targets(&transport), ["7", "7"]);
+    assert_eq!(report.outcome(7), Some(&BulkOutcome::Applied));
+}
@@ -0,0 +1,198 @@
+mod common;
+
+use common::fake::{ok, FakeTransport};
+use reqwest::header::{HeaderValue, ETAG, IF_NONE_MATCH};
+use reqwest::{Method, StatusCode};
+use serde_json::json;
+use std::time::Duration;
+use time::OffsetDateTime;
+use twitter_v2::authorization::BearerToken;
+use twitter_v2::cache::{CacheBackend, CachedResponse, MemoryCache, ResponseCache};
+use twitter_v2::query::UserField;
+use twitter_v2::transport::TransportResponse;
+use twitter_v2::TwitterApi;
+
+fn get_api(cache: ResponseCache) -> (TwitterApi<BearerToken>, FakeTransport) {
+    let transport = FakeTransport::new(|request| {
+        if request.headers().get(IF_NONE_MATCH) == Some(&HeaderValue::from_static("\"v1\"")) {
+            return Ok(TransportResponse::new(StatusCode::NOT_MODIFIED, ""));
+        }
+        match request.url().path() {
+            "/2/users/2244994945" => Ok(TransportResponse::new(
+                StatusCode::OK,
+                r#"{"data": {"id": "2244994945", "name": "Twitter Dev", "username": "TwitterDev"}}"#,
+            )
+            .header(ETAG, HeaderValue::from_static("\"v1\""))),
+            "/2/lists/1" => ok(json!({"data": {"id": "1", "name": "list"}})),
+            "/2/lists/1/members" if request.method() == Method::POST => {
+                ok(json!({"data": {"is_member": true}}))
+            }
+            "/2/lists/1/members" | "/2/users/2244994945/followers" => ok(json!({
+                "data": [{"id": "2244994945", "name": "Twitter Dev", "username": "TwitterDev"}],
+                "meta": {"result_count": 1}
+            })),
+            _ => ok(json!({"errors": [{
+                "title": "Not Found Error",
+                "type": "https://api.twitter.com/2/problems/resource-not-found"
+            }]})),
+        }
+    });
+    let api = transport.api().with_cache(cache);
+    (api, transport)
+}
+
+#[tokio::test]
+async fn cache_hit() {
+    let (api, transport) = get_api(ResponseCache::new());
+    for fields in [
+        [UserField::CreatedAt, UserField::Username],
+        [UserField::Username, UserField::CreatedAt],
+    ] {
+        let user = api
+            .get_user(2244994945)
+            .user_fields(fields)
+            .send()
+            .await
+            .unwrap()
+            .into_data()
+            .unwrap();
+        assert_eq!(user.username, "TwitterDev");
+    }
+    assert_eq!(transport.requests().len(), 1);
+
+    api.get_user(2244994945).send().await.unwrap();
+    assert_eq!(transport.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn cache_not_found() {
+    let (api, transport) = get_api(ResponseCache::new());
+    for _ in 0..2 {
+        let res = api.get_user(20).send().await.unwrap();
+        assert!(res.data().is_none());
+    }
+    assert_eq!(transport.requests().len(), 1);
+
+    let (api, transport) = get_api(ResponseCache::new().not_found_ttl(Duration::ZERO));
+    for _ in 0..2 {
+        api.get_user(20).send().await.unwrap();
+    }
+    assert_eq!(transport.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn cache_bypass() {
+    let (api, transport) = get_api(ResponseCache::new().endpoint_ttl("lists/:id", Duration::ZERO));
+    for _ in 0..2 {
+        api.get_list(1).send().await.unwrap();
+    }
+    assert_eq!(transport.requests().len(), 2);
+    for _ in 0..2 {
+        let _ = api.delete_list(1).await;
+    }
+    assert_eq!(transport.requests().len(), 4);
+}
+
+#[tokio::test]
+async fn cache_lookups_only() {
+    let (api, transport) = get_api(ResponseCache::new());
+    for _ in 0..2 {
+        api.get_user_followers(2244994945).send().await.unwrap();
+        api.get_list_members(1).send().await.unwrap();
+    }
+    assert_eq!(transport.requests().len(), 4);
+
+    let (api, transport) =
+        get_api(ResponseCache::new().endpoint_ttl("users/:id/followers", Duration::from_secs(60)));
+    for _ in 0..2 {
+        api.get_user_followers(2244994945).send().await.unwrap();
+    }
+    assert_eq!(transport.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn cache_write_invalidates() {
+    let (api, transport) =
+        get_api(ResponseCache::new().endpoint_ttl("lists/:id/members", Duration::from_secs(60)));
+    for _ in 0..2 {
+        api.get_list_members(1).send().await.unwrap();
+    }
+    assert_eq!(transport.requests_to("/2/lists/1/members").len(), 1);
+
+    api.post_list_member(1, 2244994945).await.unwrap();
+    api.get_list_members(1).send().await.unwrap();
+    api.get_list_members(1).send().await.unwrap();
+    let requests = transport.requests_to("/2/lists/1/members");
+    assert_eq!(
+        requests
+            .iter()
+            .map(|request| request.method.clone())
+            .collect::<Vec<_>>(),
+        [Method::GET, Method::POST, Method::GET]
+    );
+}
+
+#[tokio::test]
+async fn cache_without_cache() {
+    let (api, transport) = get_api(ResponseCache::new());
+    let uncached = api.clone().without_cache();
+    for _ in 0..2 {
+        uncached.get_user(2244994945).send().await.unwrap();
+    }
+    assert_eq!(transport.requests().len(), 2);
+    for _ in 0..2 {
+        api.get_user(2244994945).send().await.unwrap();
+    }
+    assert_eq!(transport.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn cache_revalidate() {
+    let (api, transport) = get_api(ResponseCache::new().ttl(Duration::from_millis(20)));
+    api.get_user(2244994945).send().await.unwrap();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    let user = api
+        .get_user(2244994945)
+        .send()
+        .await
+        .unwrap()
+        .into_data()
+        .unwrap();
+    assert_eq!(user.username, "TwitterDev");
+    assert_eq!(transport.requests().len(), 2);
+    assert_eq!(
+        transport
+            .requests()
+            .iter()
+            .filter(|request| request.headers.contains_key(IF_NONE_MATCH))
+            .count(),
+        1
+    );
+
+    // refreshed by the revalidation
+    api.get_user(2244994945).send().await.unwrap();
+    assert_eq!(transport.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn cache_memory_lru() {
+    let cache = MemoryCache::new(2);
+    let response = CachedResponse {
+        status: 200,
+        body: "{}".to_string(),
+        etag: None,
+        last_modified: None,
+        cached_at: OffsetDateTime::now_utc(),
+        expires_at: OffsetDateTime::now_utc(),
+    };
+    cache.put("a", response.clone()).await;
+    cache.put("b", response.clone()).await;
+    assert!(cache.get("a").await.is_some());
+    cache.put("c", response.clone()).await;
+    assert_eq!(cache.len(), 2);
+    assert!(cache.get("a").await.is_some());
+    assert!(cache.get("b").await.is_none());
+    assert!(cache.get("c").await.is_some());
+    cache.remove("c").await;
+    assert_eq!(cache.len(), 1);
+}
@@ -0,0 +1,106 @@
+#![cfg(feature = "cassette")]
+
+mod common;
+
+use common::fake::{ok, FakeTransport};
+use serde_json::json;
+use twitter_v2::authorization::BearerToken;
+use twitter_v2::cassette::{Cassette, CassetteMode};
+use twitter_v2::prelude::*;
+use twitter_v2::query::TweetField;
+use twitter_v2::{Error, TwitterApi};
+
+fn get_api() -> TwitterApi<BearerToken> {
+    let cassette = Cassette::replay("./fixtures/cassettes/replay_example.json").unwrap();
+    assert_eq!(cassette.mode(), CassetteMode::Replay);
+    TwitterApi::new(BearerToken::new("")).with_cassette(cassette)
+}
+
+#[tokio::test]
+async fn cassette_replay() {
+    let api = get_api();
+    let tweet = api
+        .get_tweet(1261326399320715264)
+        .tweet_fields([TweetField::AuthorId, TweetField::CreatedAt])
+        .send()
+        .await
+        .unwrap()
+        .into_data()
+        .unwrap();
+    assert_eq!(tweet.id, 1261326399320715264);
+    assert_eq!(tweet.author_id.unwrap(), 2244994945);
+}
+
+#[tokio::test]
+async fn cassette_replay_pagination() {
+    let api = get_api();
+    let page = api
+        .get_user_followers(2244994945)
+        .max_results(1)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(page.data().unwrap()[0].username, "TwitterDev");
+    let page = page.next_page().await.unwrap().unwrap();
+    assert_eq!(page.data().unwrap()[0].username, "Twitter");
+    assert!(page.next_page().await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn cassette_replay_errors() {
+    let api = get_api();
+    match api.get_tweet(1).send().await {
+        Err(Error::Api(error)) => assert_eq!(error.status, 404),
+        res => panic!("expected API error, got {res:?}"),
+    }
+    // every interaction is only played once
+    assert!(matches!(
+        api.get_tweet(1).send().await,
+        Err(Error::CassetteMiss(_))
+    ));
+    assert!(matches!(
+        api.get_tweet(2).send().await,
+        Err(Error::CassetteMiss(_))
+    ));
+}
+
+#[tokio::test]
+async fn cassette_record() {
+    let path = std::env::temp_dir().join(format!(
+        "twitter-v2-cassette-record-{}.json",
+        std::process::id()
+    ));
+    let transport = FakeTransport::new(|request| match request.url().path() {
+        "/2/tweets/20" => ok(json!({"data": {"id": "20", "text": "just setting up my twttr"}})),
+        path => panic!("unexpected request to {path}"),
+    });
+    let api = TwitterApi::new(BearerToken::new("secret"))
+        .with_transport(transport.clone())
+        .with_cassette(Cassette::record(&path));
+    assert_eq!(api.cassette().unwrap().mode(), CassetteMode::Record);
+    let tweet = api.get_tweet(20).send().await.unwrap().into_data().unwrap();
+    assert_eq!(
+        transport.requests()[0].headers["authorization"],
+        "Bearer secret"
+    );
+
+    // written to disk as soon as the response body is read, without the credentials
+    let saved = std::fs::read_to_string(&path).unwrap();
+    assert!(!saved.contains("secret"));
+    let interactions = api.cassette().unwrap().interactions();
+    assert_eq!(interactions.len(), 1);
+    assert_eq!(interactions[0].request.headers["authorization"], "REDACTED");
+    assert_eq!(interactions[0].response.status, 200);
+
+    let replayed = TwitterApi::new(BearerToken::new(""))
+        .with_cassette(Cassette::replay(&path).unwrap())
+        .get_tweet(20)
+        .send()
+        .await
+        .unwrap()
+        .into_data()
+        .unwrap();
+    assert_eq!(replayed.text, tweet.text);
+    assert_eq!(transport.requests().len(), 1);
+    std::fs::remove_file(&path).unwrap();
+}
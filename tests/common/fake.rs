@@ -0,0 +1,107 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use reqwest::header::HeaderMap;
+use reqwest::{Method, Request, StatusCode};
+use std::sync::{Arc, Mutex};
+use twitter_v2::authorization::BearerToken;
+use twitter_v2::transport::{TransportResponse, TwitterTransport};
+use twitter_v2::TwitterApi;
+use url::Url;
+
+type Respond = dyn Fn(&Request) -> twitter_v2::Result<TransportResponse> + Send + Sync;
+
+/// A request as seen by a [`FakeTransport`]
+#[derive(Clone, Debug)]
+pub struct SeenRequest {
+    pub method: Method,
+    pub url: Url,
+    pub headers: HeaderMap,
+    pub body: Option<Bytes>,
+}
+
+impl SeenRequest {
+    pub fn query(&self, name: &str) -> Option<String> {
+        query(&self.url, name)
+    }
+
+    pub fn json(&self) -> serde_json::Value {
+        serde_json::from_slice(self.body.as_deref().expect("request has no body")).unwrap()
+    }
+}
+
+/// Answers requests in-process with `respond` and records them
+#[derive(Clone)]
+pub struct FakeTransport {
+    respond: Arc<Respond>,
+    requests: Arc<Mutex<Vec<SeenRequest>>>,
+}
+
+impl FakeTransport {
+    pub fn new(
+        respond: impl Fn(&Request) -> twitter_v2::Result<TransportResponse> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            respond: Arc::new(respond),
+            requests: Default::default(),
+        }
+    }
+
+    /// A client sending all requests to this transport
+    pub fn api(&self) -> TwitterApi<BearerToken> {
+        TwitterApi::new(BearerToken::new("")).with_transport(self.clone())
+    }
+
+    pub fn requests(&self) -> Vec<SeenRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// The recorded requests to `path`
+    pub fn requests_to(&self, path: &str) -> Vec<SeenRequest> {
+        self.requests()
+            .into_iter()
+            .filter(|request| request.url.path() == path)
+            .collect()
+    }
+}
+
+#[async_trait]
+impl TwitterTransport for FakeTransport {
+    async fn execute(&self, request: Request) -> twitter_v2::Result<TransportResponse> {
+        self.requests.lock().unwrap().push(SeenRequest {
+            method: request.method().clone(),
+            url: request.url().clone(),
+            headers: request.headers().clone(),
+            body: request
+                .body()
+                .and_then(|body| body.as_bytes())
+                .map(Bytes::copy_from_slice),
+        });
+        (self.respond)(&request)
+    }
+}
+
+pub fn query(url: &Url, name: &str) -> Option<String> {
+    url.query_pairs()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.into_owned())
+}
+
+pub fn json(status: StatusCode, body: serde_json::Value) -> twitter_v2::Result<TransportResponse> {
+    Ok(TransportResponse::new(status, body.to_string()))
+}
+
+pub fn ok(body: serde_json::Value) -> twitter_v2::Result<TransportResponse> {
+    json(StatusCode::OK, body)
+}
+
+pub fn too_many_requests() -> twitter_v2::Result<TransportResponse> {
+    json(
+        StatusCode::TOO_MANY_REQUESTS,
+        serde_json::json!({
+            "title": "Too Many Requests",
+            "detail": "Too Many Requests",
+            "type": "about:blank",
+            "status": 429
+        }),
+    )
+}
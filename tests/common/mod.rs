@@ -3,13 +3,82 @@ use tokio::sync::Mutex;
 use twitter_v2::authorization::{BearerToken, Oauth2Client, Oauth2Token};
 use twitter_v2::TwitterApi;
 
+#[allow(dead_code)]
+pub mod fake;
+
 lazy_static::lazy_static! {
     static ref OAUTH2_TOKEN: Mutex<Oauth2Token> = Mutex::new(serde_json::from_reader(
         std::fs::File::open("./.oauth2_token.json").expect(".oauth2_token.json not found"),
     )
     .expect(".oauth2_token.json not valid json"));
 }
+
+// With the `cassette` feature, `TWITTER_CASSETTE=record` records the API tests to
+// `fixtures/cassettes/<test binary>/<test>_<ctx>.json` and `TWITTER_CASSETTE=replay` runs
+// them offline without credentials. Every test gets its own file so that test binaries
+// running in parallel never write to the same cassette. The cassettes are not part of the
+// repository yet, so the API tests need a recording run with credentials before they can
+// be replayed.
+#[cfg(feature = "cassette")]
+mod cassette {
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+    use twitter_v2::cassette::Cassette;
+
+    lazy_static::lazy_static! {
+        static ref CASSETTES: Mutex<HashMap<PathBuf, Arc<Cassette>>> = Default::default();
+    }
+
+    /// The cassette of the running test for clients of `ctx`, shared by all of them
+    pub fn load(ctx: &str) -> Option<Arc<Cassette>> {
+        let mode = std::env::var("TWITTER_CASSETTE").ok()?;
+        let thread = std::thread::current();
+        let test = thread.name().expect("tests run on named threads");
+        let path = PathBuf::from(format!(
+            "./fixtures/cassettes/{}/{}_{ctx}.json",
+            env!("CARGO_CRATE_NAME"),
+            test.replace("::", "_")
+        ));
+        if let Some(cassette) = CASSETTES.lock().unwrap().get(&path) {
+            return Some(cassette.clone());
+        }
+        let cassette = Arc::new(match mode.as_str() {
+            "record" => Cassette::record(&path),
+            "replay" => Cassette::replay(&path).unwrap_or_else(|err| {
+                panic!(
+                    "could not load {}, record it with TWITTER_CASSETTE=record: {err}",
+                    path.display()
+                )
+            }),
+            mode => panic!("unknown TWITTER_CASSETTE mode '{mode}'"),
+        });
+        Some(
+            CASSETTES
+                .lock()
+                .unwrap()
+                .entry(path)
+                .or_insert(cassette)
+                .clone(),
+        )
+    }
+
+    pub fn is_replaying() -> bool {
+        matches!(std::env::var("TWITTER_CASSETTE").as_deref(), Ok("replay"))
+    }
+}
+
 async fn get_token() -> Oauth2Token {
+    #[cfg(feature = "cassette")]
+    if cassette::is_replaying() {
+        return serde_json::from_value(serde_json::json!({
+            "access_token": "REDACTED",
+            "refresh_token": null,
+            "expires": "2100-01-01T00:00:00Z",
+            "scopes": [],
+        }))
+        .unwrap();
+    }
     let oauth2_client = Oauth2Client::new(
         std::env::var("CLIENT_ID").expect("could not find CLIENT_ID"),
         std::env::var("CLIENT_SECRET").expect("could not find CLIENT_SECRET"),
@@ -31,10 +100,20 @@ async fn get_token() -> Oauth2Token {
 }
 #[allow(dead_code)]
 pub async fn get_api_user_ctx() -> TwitterApi<Oauth2Token> {
-    TwitterApi::new(get_token().await)
+    let api = TwitterApi::new(get_token().await);
+    #[cfg(feature = "cassette")]
+    if let Some(cassette) = cassette::load("user_ctx") {
+        return api.with_cassette(cassette);
+    }
+    api
 }
 #[allow(dead_code)]
 pub fn get_api_app_ctx() -> TwitterApi<BearerToken> {
+    #[cfg(feature = "cassette")]
+    if let Some(cassette) = cassette::load("app_ctx") {
+        let token = std::env::var("APP_BEARER_TOKEN").unwrap_or_default();
+        return TwitterApi::new(BearerToken::new(token)).with_cassette(cassette);
+    }
     TwitterApi::new(BearerToken::new(
         std::env::var("APP_BEARER_TOKEN").expect("BEARER_TOKEN not found"),
     ))
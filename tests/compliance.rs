@@ -0,0 +1,146 @@
+mod common;
+
+use common::fake::{ok, FakeTransport};
+use futures::prelude::*;
+use reqwest::header::AUTHORIZATION;
+use reqwest::{Method, StatusCode};
+use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use twitter_v2::authorization::BearerToken;
+use twitter_v2::data::{ComplianceActionKind, ComplianceJobKind};
+use twitter_v2::transport::TransportResponse;
+use twitter_v2::{Error, TwitterApi};
+
+const UPLOAD_URL: &str = "https://storage.googleapis.com/twttr-tweet-compliance/upload";
+const DOWNLOAD_URL: &str = "https://storage.googleapis.com/twttr-tweet-compliance/download";
+
+fn job(id: &str, status: &str) -> serde_json::Value {
+    json!({"data": {
+        "id": id,
+        "type": "tweets",
+        "status": status,
+        "created_at": "2021-05-04T18:36:00.000Z",
+        "upload_url": UPLOAD_URL,
+        "upload_expires_at": "2021-05-04T18:51:00.000Z",
+        "download_url": DOWNLOAD_URL,
+        "download_expires_at": "2021-05-11T18:36:00.000Z"
+    }})
+}
+
+/// Job 1 completes on the third status check, job 2 fails, job 3 never completes, job 4
+/// expires and job 5 reports a status unknown to the crate
+fn get_api() -> (TwitterApi<BearerToken>, FakeTransport) {
+    let checks = Arc::new(AtomicUsize::new(0));
+    let transport = FakeTransport::new(move |request| match request.url().path() {
+        "/2/compliance/jobs" => ok(job("1", "created")),
+        "/2/compliance/job/1" => match checks.fetch_add(1, Ordering::SeqCst) {
+            0 | 1 => ok(job("1", "in_progress")),
+            _ => ok(job("1", "complete")),
+        },
+        "/2/compliance/job/2" => ok(job("2", "failed")),
+        "/2/compliance/job/3" => ok(job("3", "in_progress")),
+        "/2/compliance/job/4" => ok(job("4", "expired")),
+        "/2/compliance/job/5" => ok(job("5", "archived")),
+        "/twttr-tweet-compliance/upload" => Ok(TransportResponse::new(StatusCode::OK, "")),
+        "/twttr-tweet-compliance/download" => Ok(TransportResponse::new(
+            StatusCode::OK,
+            include_str!("../fixtures/data/compliance/tweets.jsonl"),
+        )),
+        path => panic!("unexpected request to {path}"),
+    });
+    (transport.api(), transport)
+}
+
+#[tokio::test]
+async fn compliance_run() {
+    let (api, transport) = get_api();
+    let actions = api
+        .compliance_job_runner(ComplianceJobKind::Tweets)
+        .poll_interval(Duration::from_millis(1))
+        .run([1389661826296700928u64, 1389661826296700929])
+        .await
+        .unwrap()
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    assert_eq!(actions.len(), 5);
+    assert_eq!(actions[0].action, ComplianceActionKind::Delete);
+    assert_eq!(
+        actions[2].withheld_in_countries.as_deref().unwrap(),
+        ["DE", "FR"]
+    );
+
+    let requests = transport.requests();
+    assert_eq!(
+        requests
+            .iter()
+            .map(|request| (request.method.clone(), request.url.path()))
+            .collect::<Vec<_>>(),
+        [
+            (Method::POST, "/2/compliance/jobs"),
+            (Method::PUT, "/twttr-tweet-compliance/upload"),
+            (Method::GET, "/2/compliance/job/1"),
+            (Method::GET, "/2/compliance/job/1"),
+            (Method::GET, "/2/compliance/job/1"),
+            (Method::GET, "/twttr-tweet-compliance/download"),
+        ]
+    );
+    assert_eq!(
+        requests[1].body.as_deref().unwrap(),
+        b"1389661826296700928\n1389661826296700929\n"
+    );
+    // the pre-signed URLs must not receive the credentials
+    assert!(requests[0].headers.contains_key(AUTHORIZATION));
+    assert!(!requests[1].headers.contains_key(AUTHORIZATION));
+    assert!(!requests[5].headers.contains_key(AUTHORIZATION));
+}
+
+#[tokio::test]
+async fn compliance_upload_stream() {
+    let (api, transport) = get_api();
+    let runner = api.compliance_job_runner(ComplianceJobKind::Tweets);
+    let job = runner.create().await.unwrap();
+    runner
+        .upload_stream(&job, stream::iter([20.into(), 21.into()]))
+        .await
+        .unwrap();
+    let upload = &transport.requests_to("/twttr-tweet-compliance/upload")[0];
+    assert_eq!(upload.method, Method::PUT);
+    assert_eq!(upload.headers["content-type"], "text/plain");
+}
+
+#[tokio::test]
+async fn compliance_wait_failed() {
+    let (api, _) = get_api();
+    let err = api
+        .compliance_job_runner(ComplianceJobKind::Tweets)
+        .wait(2)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::ComplianceJobFailed(id) if id == 2));
+
+    let runner = api.compliance_job_runner(ComplianceJobKind::Tweets);
+    let err = runner.wait(4).await.unwrap_err();
+    assert!(matches!(err, Error::ComplianceJobExpired(id) if id == 4));
+    let err = runner.wait(5).await.unwrap_err();
+    assert!(matches!(err, Error::ComplianceJobUnknownStatus(id) if id == 5));
+}
+
+#[tokio::test]
+async fn compliance_wait_timeout() {
+    let (api, transport) = get_api();
+    let err = api
+        .compliance_job_runner(ComplianceJobKind::Tweets)
+        .poll_interval(Duration::from_millis(10))
+        .max_poll_interval(Duration::from_millis(40))
+        .timeout(Duration::from_millis(100))
+        .wait(3)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::ComplianceJobTimeout(id) if id == 3));
+    // checks after 0, 10, 30, 70 and 100ms at most, backing off in between
+    let checks = transport.requests_to("/2/compliance/job/3").len();
+    assert!((2..=5).contains(&checks), "{checks} checks");
+}
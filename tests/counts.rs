@@ -0,0 +1,117 @@
+mod common;
+
+use common::fake::{ok, query, FakeTransport};
+use serde_json::json;
+use time::macros::datetime;
+use twitter_v2::authorization::BearerToken;
+use twitter_v2::data::TweetsCountComparison;
+use twitter_v2::query::Granularity;
+use twitter_v2::TwitterApi;
+
+/// Serves hourly counts over two pages, with no bucket for 02:00
+fn get_api() -> (TwitterApi<BearerToken>, FakeTransport) {
+    let transport = FakeTransport::new(|request| {
+        let scale = if query(request.url(), "query").unwrap() == "rust" {
+            1
+        } else {
+            2
+        };
+        match query(request.url(), "next_token") {
+            None => ok(json!({
+                "data": [
+                    {"start": "2022-01-01T00:00:00Z", "end": "2022-01-01T01:00:00Z", "tweet_count": 5 * scale},
+                    {"start": "2022-01-01T01:00:00Z", "end": "2022-01-01T02:00:00Z", "tweet_count": 7 * scale},
+                ],
+                "meta": {"total_tweet_count": 12 * scale, "next_token": "page2"}
+            })),
+            Some(_) => ok(json!({
+                "data": [
+                    {"start": "2022-01-01T03:00:00Z", "end": "2022-01-01T04:00:00Z", "tweet_count": 3 * scale},
+                ],
+                "meta": {"total_tweet_count": 3 * scale}
+            })),
+        }
+    });
+    (transport.api(), transport)
+}
+
+#[tokio::test]
+async fn counts_series_fills_gaps() {
+    let (api, transport) = get_api();
+    let series = api
+        .get_tweets_counts_all("rust")
+        .granularity(Granularity::Hour)
+        .start_time(datetime!(2022-01-01 0:00 UTC))
+        .end_time(datetime!(2022-01-01 5:00 UTC))
+        .series()
+        .await
+        .unwrap();
+    assert_eq!(
+        transport
+            .requests()
+            .iter()
+            .map(|request| request.query("next_token"))
+            .collect::<Vec<_>>(),
+        [None, Some("page2".to_string())]
+    );
+    assert_eq!(series.granularity, Granularity::Hour);
+    assert_eq!(
+        series
+            .buckets
+            .iter()
+            .map(|bucket| bucket.tweet_count)
+            .collect::<Vec<_>>(),
+        [5, 7, 0, 3, 0]
+    );
+    assert_eq!(series.total(), 15);
+    assert_eq!(series.get(datetime!(2022-01-01 1:30 UTC)), Some(7));
+    assert_eq!(series.peak().unwrap().start, datetime!(2022-01-01 1:00 UTC));
+}
+
+#[tokio::test]
+async fn counts_series_rebucket() {
+    let (api, _) = get_api();
+    let series = api
+        .get_tweets_counts_all("rust")
+        .start_time(datetime!(2021-12-31 22:00 UTC))
+        .end_time(datetime!(2022-01-01 5:00 UTC))
+        .series()
+        .await
+        .unwrap();
+    let daily = series.rebucket(Granularity::Day).unwrap();
+    assert_eq!(daily.buckets.len(), 2);
+    assert_eq!(daily.buckets[0].end, datetime!(2022-01-01 0:00 UTC));
+    assert_eq!(daily.buckets[0].tweet_count, 0);
+    assert_eq!(daily.buckets[1].tweet_count, 15);
+    assert_eq!(daily.total(), series.total());
+    assert!(daily.rebucket(Granularity::Minute).is_err());
+}
+
+#[tokio::test]
+async fn counts_comparison() {
+    let (api, _) = get_api();
+    let rust = api
+        .get_tweets_counts_all("rust")
+        .start_time(datetime!(2022-01-01 0:00 UTC))
+        .end_time(datetime!(2022-01-01 4:00 UTC))
+        .series()
+        .await
+        .unwrap();
+    let go = api
+        .get_tweets_counts_all("go")
+        .start_time(datetime!(2022-01-01 0:00 UTC))
+        .end_time(datetime!(2022-01-01 4:00 UTC))
+        .series()
+        .await
+        .unwrap()
+        .rebucket(Granularity::Day)
+        .unwrap();
+    let comparison = TweetsCountComparison::new([("rust", rust), ("go", go)]);
+    assert_eq!(comparison.labels, ["rust", "go"]);
+    assert_eq!(comparison.series[0].granularity, Granularity::Day);
+    assert_eq!(
+        comparison.rows().collect::<Vec<_>>(),
+        [(datetime!(2022-01-01 0:00 UTC), vec![15, 30])]
+    );
+    assert_eq!(comparison.totals(), [15, 30]);
+}
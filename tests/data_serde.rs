@@ -33,10 +33,13 @@ fn tweet_serde() {
     for (path, example) in get_examples("./fixtures/data/tweet") {
         let decoded: Example<Vec<Tweet>> = serde_json::from_value(example.clone())
             .unwrap_or_else(|e| panic!("Could not read example '{}': {}", path.display(), e));
+        let encoded = serde_json::to_value(&decoded).unwrap();
+        // numbers only survive the round trip unchanged with arbitrary precision
         #[cfg(feature = "arbitrary_precision")]
+        assert_eq!(encoded, example, "{}", path.display());
         assert_eq!(
-            serde_json::to_value(&decoded).unwrap(),
-            example,
+            serde_json::from_value::<Example<Vec<Tweet>>>(encoded).unwrap(),
+            decoded,
             "{}",
             path.display()
         );
@@ -48,12 +51,116 @@ fn space_serde() {
     for (path, example) in get_examples("./fixtures/data/space") {
         let decoded: Example<Vec<Space>> = serde_json::from_value(example.clone())
             .unwrap_or_else(|e| panic!("Could not read example '{}': {}", path.display(), e));
+        let encoded = serde_json::to_value(&decoded).unwrap();
+        // numbers only survive the round trip unchanged with arbitrary precision
         #[cfg(feature = "arbitrary_precision")]
+        assert_eq!(encoded, example, "{}", path.display());
         assert_eq!(
-            serde_json::to_value(&decoded).unwrap(),
-            example,
+            serde_json::from_value::<Example<Vec<Space>>>(encoded).unwrap(),
+            decoded,
             "{}",
             path.display()
         );
     }
 }
+
+#[cfg(all(feature = "extra-fields", not(feature = "deny-unknown-fields")))]
+#[test]
+fn tweet_extra_fields() {
+    let tweet: Tweet = serde_json::from_value(serde_json::json!({
+        "id": "1261326399320715264",
+        "text": "hello",
+        "not_yet_modeled": { "a": 1 },
+        "public_metrics": {
+            "retweet_count": 1,
+            "reply_count": 2,
+            "like_count": 3,
+            "new_count": 4
+        }
+    }))
+    .unwrap();
+    assert_eq!(
+        tweet.extra["not_yet_modeled"],
+        serde_json::json!({ "a": 1 })
+    );
+    assert_eq!(tweet.public_metrics.unwrap().extra["new_count"], 4);
+}
+
+#[cfg(feature = "deny-unknown-fields")]
+#[test]
+fn tweet_deny_unknown_fields() {
+    let res = serde_json::from_value::<Tweet>(serde_json::json!({
+        "id": "1261326399320715264",
+        "text": "hello",
+        "not_yet_modeled": true
+    }));
+    assert!(res.is_err());
+}
+
+#[test]
+fn tweet_edit_history() {
+    let tweets: Vec<Tweet> = serde_json::from_value(serde_json::json!([
+        {
+            "id": "1584616519476883458",
+            "text": "hello world",
+            "edit_history_tweet_ids": ["1584616519476883456", "1584616519476883458"],
+            "edit_controls": {
+                "edits_remaining": 4,
+                "is_edit_eligible": true,
+                "editable_until": "2022-10-24T19:05:31Z"
+            }
+        },
+        {
+            "id": "1584616519476883456",
+            "text": "helo world",
+            "edit_history_tweet_ids": ["1584616519476883456", "1584616519476883458"]
+        }
+    ]))
+    .unwrap();
+    let includes: Expansions = serde_json::from_value(serde_json::json!({
+        "tweets": [tweets[1]]
+    }))
+    .unwrap();
+    let latest = &tweets[0];
+    assert_eq!(latest.is_latest_version(), Some(true));
+    assert_eq!(tweets[1].is_latest_version(), Some(false));
+    assert_eq!(latest.is_edited(), Some(true));
+    assert_eq!(latest.edits_remaining(), Some(4));
+    assert_eq!(latest.is_editable(), Some(false));
+    assert_eq!(latest.original_tweet_id(), Some(tweets[1].id));
+    let versions = latest.versions(Some(&includes));
+    assert_eq!(
+        versions.iter().map(|t| t.text.as_str()).collect::<Vec<_>>(),
+        ["helo world", "hello world"]
+    );
+}
+
+#[test]
+fn compliance_action_serde() {
+    use twitter_v2::data::{ComplianceAction, ComplianceActionKind};
+    let actions = serde_json::Deserializer::from_reader(
+        File::open("./fixtures/data/compliance/tweets.jsonl").unwrap(),
+    )
+    .into_iter::<ComplianceAction>()
+    .collect::<Result<Vec<_>, _>>()
+    .unwrap();
+    assert_eq!(
+        actions
+            .iter()
+            .map(|action| action.action.clone())
+            .collect::<Vec<_>>(),
+        vec![
+            ComplianceActionKind::Delete,
+            ComplianceActionKind::ScrubGeo,
+            ComplianceActionKind::Withheld,
+            ComplianceActionKind::Suspend,
+            ComplianceActionKind::Protect,
+        ]
+    );
+    assert_eq!(actions[0].id, 1389661826296700928);
+    assert!(actions[0].redacted_at.is_some());
+    assert_eq!(
+        actions[2].withheld_in_countries.as_deref(),
+        Some(&["DE".to_string(), "FR".to_string()][..])
+    );
+}
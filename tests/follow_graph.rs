@@ -0,0 +1,87 @@
+mod common;
+
+use common::fake::{ok, query, too_many_requests, FakeTransport};
+use serde_json::json;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use twitter_v2::requests::{FollowDirection, FollowSnapshot};
+
+/// Serves followers 1, 2 and 3 over two pages. The first request is rate limited.
+async fn snapshot() -> (FollowSnapshot, FakeTransport) {
+    let rate_limited = Arc::new(AtomicBool::new(false));
+    let transport = FakeTransport::new(move |request| {
+        if !rate_limited.swap(true, Ordering::SeqCst) {
+            return too_many_requests();
+        }
+        assert_eq!(request.url().path(), "/2/users/2244994945/followers");
+        match query(request.url(), "pagination_token") {
+            None => ok(json!({
+                "data": [
+                    {"id": "1", "name": "One", "username": "one"},
+                    {"id": "2", "name": "Two", "username": "two"}
+                ],
+                "meta": {"result_count": 2, "next_token": "page2"}
+            })),
+            Some(_) => ok(json!({
+                "data": [{"id": "3", "name": "Three", "username": "three"}],
+                "meta": {"result_count": 1}
+            })),
+        }
+    });
+    let api = transport.api();
+    let snapshot = api
+        .follow_graph(2244994945, FollowDirection::Followers)
+        .min_interval(Duration::ZERO)
+        .rate_limit_wait(Duration::ZERO)
+        .snapshot()
+        .await
+        .unwrap();
+    (snapshot, transport)
+}
+
+#[tokio::test]
+async fn follow_graph_snapshot() {
+    let (snapshot, transport) = snapshot().await;
+    assert_eq!(transport.requests().len(), 3);
+    assert_eq!(snapshot.user_id, 2244994945);
+    assert_eq!(
+        snapshot
+            .users
+            .iter()
+            .map(|user| user.id.as_u64())
+            .collect::<Vec<_>>(),
+        [1, 2, 3]
+    );
+    assert!(snapshot.contains(2));
+
+    let json = serde_json::to_string(&snapshot).unwrap();
+    assert_eq!(
+        serde_json::from_str::<FollowSnapshot>(&json).unwrap(),
+        snapshot
+    );
+}
+
+#[tokio::test]
+async fn follow_graph_diff() {
+    let (later, _) = snapshot().await;
+    let mut earlier = later.clone();
+    earlier.taken_at -= time::Duration::DAY;
+    earlier.users.remove(2);
+    let mut lost = earlier.users[0].clone();
+    lost.id = 4.into();
+    earlier.users.push(lost);
+
+    let diff = earlier.diff(&later).unwrap();
+    assert_eq!(diff.since, earlier.taken_at);
+    assert_eq!(diff.until, later.taken_at);
+    assert_eq!(diff.gained.len(), 1);
+    assert_eq!(diff.gained[0].id, 3);
+    assert_eq!(diff.lost.len(), 1);
+    assert_eq!(diff.lost[0].id, 4);
+    assert!(later.diff(&later).unwrap().is_empty());
+
+    let mut following = later.clone();
+    following.direction = FollowDirection::Following;
+    assert!(earlier.diff(&following).is_err());
+}
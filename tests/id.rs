@@ -0,0 +1,49 @@
+use time::macros::datetime;
+use twitter_v2::id::{MediaKey, NumericId, TweetId, UserId};
+
+#[test]
+fn snowflake_timestamp() {
+    let id = NumericId::new(1261326399320715264);
+    assert_eq!(id.timestamp(), Some(datetime!(2020-05-15 16:03:42.566 UTC)));
+    assert!(id.worker().is_some());
+    assert!(id.sequence().is_some());
+    assert_eq!(NumericId::new(20).timestamp(), None);
+}
+
+#[test]
+fn snowflake_bounds() {
+    let time = datetime!(2020-05-15 16:03:42.566 UTC);
+    let id = NumericId::new(1261326399320715264);
+    let lower = NumericId::from_time(time);
+    let upper = NumericId::from_time_upper(time);
+    assert!(lower <= id && id <= upper);
+    assert_eq!(lower.timestamp(), Some(time));
+    assert_eq!(upper.timestamp(), Some(time));
+    assert_eq!(lower.sequence(), Some(0));
+    assert_eq!(upper.sequence(), Some(0xfff));
+    assert_eq!(NumericId::from_time(datetime!(2000-01-01 0:00 UTC)), 0);
+}
+
+#[test]
+fn typed_id_serde() {
+    let id: TweetId = serde_json::from_str("\"1261326399320715264\"").unwrap();
+    assert_eq!(id, 1261326399320715264);
+    let id: UserId = serde_json::from_str("2244994945").unwrap();
+    assert_eq!(serde_json::to_string(&id).unwrap(), "\"2244994945\"");
+    assert_eq!(
+        serde_json::to_string(&id).unwrap(),
+        serde_json::to_string(&NumericId::new(2244994945)).unwrap()
+    );
+    let key: MediaKey = serde_json::from_str("\"7_1293565706408038401\"").unwrap();
+    assert_eq!(key, "7_1293565706408038401");
+}
+
+#[test]
+#[allow(deprecated)]
+fn deprecated_request_names() {
+    let request = twitter_v2::requests::TweetId::from(TweetId::new(20));
+    assert_eq!(
+        serde_json::to_value(request).unwrap(),
+        serde_json::json!({"tweet_id": "20"})
+    );
+}
@@ -0,0 +1,102 @@
+use twitter_v2::id::{SpaceId, TweetId};
+use twitter_v2::links::TwitterUrl;
+
+#[test]
+fn parse_tweet_urls() {
+    for url in [
+        "https://twitter.com/TwitterDev/status/1261326399320715264",
+        "https://x.com/TwitterDev/status/1261326399320715264?s=20&t=abc",
+        "https://mobile.twitter.com/TwitterDev/status/1261326399320715264/photo/1",
+        "twitter.com/TwitterDev/statuses/1261326399320715264",
+        "http://www.x.com/TwitterDev/status/1261326399320715264/",
+    ] {
+        assert_eq!(
+            TwitterUrl::parse(url).unwrap(),
+            TwitterUrl::Tweet {
+                username: Some("TwitterDev".to_string()),
+                id: TweetId::new(1261326399320715264)
+            },
+            "{url}"
+        );
+    }
+    assert_eq!(
+        TwitterUrl::parse("https://twitter.com/i/web/status/1261326399320715264")
+            .unwrap()
+            .tweet_id(),
+        Some(TweetId::new(1261326399320715264))
+    );
+    assert!(matches!(
+        TwitterUrl::parse("https://twitter.com/TwitterDev/status/1261326399320715264/hidden"),
+        Ok(TwitterUrl::ModeratedTweets { .. })
+    ));
+}
+
+#[test]
+fn parse_other_urls() {
+    assert_eq!(
+        TwitterUrl::parse("https://x.com/TwitterDev/with_replies").unwrap(),
+        TwitterUrl::User {
+            username: "TwitterDev".to_string()
+        }
+    );
+    assert_eq!(
+        TwitterUrl::parse("https://twitter.com/i/spaces/1DXxyRYNejbKM?s=20").unwrap(),
+        TwitterUrl::Space {
+            id: SpaceId::from("1DXxyRYNejbKM")
+        }
+    );
+    assert!(matches!(
+        TwitterUrl::parse("https://twitter.com/i/lists/1234"),
+        Ok(TwitterUrl::List { id }) if id == 1234
+    ));
+    assert!(TwitterUrl::parse("https://twitter.com/home").is_err());
+    assert!(TwitterUrl::parse("https://example.com/TwitterDev/status/1").is_err());
+}
+
+#[test]
+fn roundtrip_urls() {
+    let url = TwitterUrl::Tweet {
+        username: Some("TwitterDev".to_string()),
+        id: TweetId::new(1261326399320715264),
+    };
+    assert_eq!(
+        url.to_string(),
+        "https://twitter.com/TwitterDev/status/1261326399320715264"
+    );
+    assert_eq!(TwitterUrl::parse(&url.to_string()).unwrap(), url);
+}
+
+#[test]
+fn roundtrip_every_variant() {
+    let urls = [
+        TwitterUrl::Tweet {
+            username: Some("TwitterDev".to_string()),
+            id: TweetId::new(1261326399320715264),
+        },
+        TwitterUrl::Tweet {
+            username: None,
+            id: TweetId::new(1261326399320715264),
+        },
+        TwitterUrl::ModeratedTweets {
+            username: Some("TwitterDev".to_string()),
+            id: TweetId::new(1261326399320715264),
+        },
+        TwitterUrl::ModeratedTweets {
+            username: None,
+            id: TweetId::new(1261326399320715264),
+        },
+        TwitterUrl::User {
+            username: "TwitterDev".to_string(),
+        },
+        TwitterUrl::UserId {
+            id: 2244994945.into(),
+        },
+        TwitterUrl::List { id: 1234.into() },
+        TwitterUrl::Space {
+            id: "1DXxyRYNejbKM".into(),
+        },
+    ];
+    for url in urls {
+        assert_eq!(TwitterUrl::from_url(&url.to_url()), Some(url.clone()));
+    }
+}
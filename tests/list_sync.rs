@@ -0,0 +1,134 @@
+mod common;
+
+use common::fake::{ok, query, FakeTransport};
+use reqwest::Method;
+use serde_json::json;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use twitter_v2::authorization::BearerToken;
+use twitter_v2::requests::{BulkOutcome, ListSyncReport, MAX_LIST_MEMBERS};
+use twitter_v2::TwitterApi;
+
+/// Serves members 1 and 2 of list 7 over two pages. Adding user 5 fails in transport once.
+fn get_api() -> (TwitterApi<BearerToken>, FakeTransport) {
+    let failed = Arc::new(AtomicBool::new(false));
+    let transport = FakeTransport::new(move |request| match *request.method() {
+        Method::GET => {
+            assert_eq!(request.url().path(), "/2/lists/7/members");
+            match query(request.url(), "pagination_token") {
+                None => ok(json!({
+                    "data": [{"id": "1", "name": "One", "username": "one"}],
+                    "meta": {"result_count": 1, "next_token": "page2"}
+                })),
+                Some(_) => ok(json!({
+                    "data": [{"id": "2", "name": "Two", "username": "two"}],
+                    "meta": {"result_count": 1}
+                })),
+            }
+        }
+        Method::POST
+            if request.body().unwrap().as_bytes().unwrap() == br#"{"user_id":"5"}"#
+                && !failed.swap(true, Ordering::SeqCst) =>
+        {
+            Err(twitter_v2::Error::custom("connection reset"))
+        }
+        Method::POST => ok(json!({"data": {"is_member": true}})),
+        _ => ok(json!({"data": {"is_member": false}})),
+    });
+    (transport.api(), transport)
+}
+
+/// The added or removed member of every write
+fn writes(transport: &FakeTransport) -> Vec<(Method, String)> {
+    transport
+        .requests()
+        .into_iter()
+        .filter(|request| request.method != Method::GET)
+        .map(|request| {
+            let target = match request.method {
+                Method::POST => request.json()["user_id"].as_str().unwrap().to_string(),
+                _ => request
+                    .url
+                    .path_segments()
+                    .unwrap()
+                    .next_back()
+                    .unwrap()
+                    .to_string(),
+            };
+            (request.method, target)
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn list_sync_members() {
+    let (api, transport) = get_api();
+    let mut report = ListSyncReport::default();
+    api.sync_list_members(7, [2, 3, 4])
+        .min_interval(Duration::ZERO)
+        .run(&mut report)
+        .await
+        .unwrap();
+    assert_eq!(
+        writes(&transport),
+        [
+            (Method::DELETE, "1".to_string()),
+            (Method::POST, "3".to_string()),
+            (Method::POST, "4".to_string()),
+        ]
+    );
+    assert_eq!(report.unchanged, [2]);
+    assert_eq!(report.removed.outcome(1), Some(&BulkOutcome::Applied));
+    assert_eq!(report.added.count(&BulkOutcome::Applied), 2);
+    assert!(report.is_complete());
+}
+
+#[tokio::test]
+async fn list_sync_dry_run() {
+    let (api, transport) = get_api();
+    let mut report = ListSyncReport::default();
+    api.sync_list_members(7, [2, 3])
+        .min_interval(Duration::ZERO)
+        .dry_run(true)
+        .run(&mut report)
+        .await
+        .unwrap();
+    assert!(writes(&transport).is_empty());
+    assert_eq!(report.removed.outcome(1), Some(&BulkOutcome::WouldApply));
+    assert_eq!(report.added.outcome(3), Some(&BulkOutcome::WouldApply));
+}
+
+#[tokio::test]
+async fn list_sync_size_limit() {
+    let (api, _) = get_api();
+    assert!(api
+        .sync_list_members(7, 1..=MAX_LIST_MEMBERS as u64 + 1)
+        .run(&mut ListSyncReport::default())
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn list_sync_resume() {
+    let (api, transport) = get_api();
+    let mut report = ListSyncReport::default();
+    let mut sync = api.sync_list_members(7, [2, 5, 6]);
+    sync.min_interval(Duration::ZERO);
+    assert!(sync.run(&mut report).await.is_err());
+    // the changes made before the error are kept
+    assert_eq!(report.removed.outcome(1), Some(&BulkOutcome::Applied));
+    assert_eq!(report.added.entries.len(), 0);
+
+    sync.run(&mut report).await.unwrap();
+    assert_eq!(
+        writes(&transport),
+        [
+            (Method::DELETE, "1".to_string()),
+            (Method::POST, "5".to_string()),
+            (Method::POST, "5".to_string()),
+            (Method::POST, "6".to_string()),
+        ]
+    );
+    assert!(report.is_complete());
+}
@@ -0,0 +1,131 @@
+mod common;
+
+use common::fake::{json, ok, FakeTransport};
+use reqwest::{Method, StatusCode};
+use std::sync::Arc;
+use twitter_v2::authorization::BearerToken;
+use twitter_v2::id::SpaceId;
+use twitter_v2::metrics::{EndpointCount, EndpointCounters};
+use twitter_v2::TwitterApi;
+
+fn get_api() -> TwitterApi<BearerToken> {
+    let transport = FakeTransport::new(|request| match request.url().path() {
+        "/2/tweets/20" => ok(serde_json::json!({
+            "data": {"id": "20", "text": "just setting up my twttr"}
+        })),
+        _ => json(StatusCode::NOT_FOUND, serde_json::json!({})),
+    });
+    TwitterApi::new(BearerToken::new("secret")).with_transport(transport)
+}
+
+#[tokio::test]
+async fn metrics_endpoint_counters() {
+    let counters = Arc::new(EndpointCounters::new());
+    let api = get_api().with_metrics(counters.clone());
+    api.get_tweet(20).send().await.unwrap();
+    api.get_tweet(21).send().await.unwrap_err();
+    api.get_user(20).send().await.unwrap_err();
+
+    assert_eq!(
+        counters.get(&Method::GET, "tweets/:id"),
+        EndpointCount {
+            requests: 2,
+            errors: 1
+        }
+    );
+    assert_eq!(
+        counters.get(&Method::GET, "users/:id"),
+        EndpointCount {
+            requests: 1,
+            errors: 1
+        }
+    );
+    assert_eq!(counters.counts().len(), 2);
+}
+
+#[tokio::test]
+async fn metrics_endpoint_templates() {
+    let counters = Arc::new(EndpointCounters::new());
+    let api = get_api().with_metrics(counters.clone());
+    api.get_user_by_username("jack").send().await.unwrap_err();
+    api.get_user_by_username("TwitterDev")
+        .send()
+        .await
+        .unwrap_err();
+    api.get_users_me().send().await.unwrap_err();
+    api.get_space(SpaceId::new("1DXxyRYNejbKM"))
+        .send()
+        .await
+        .unwrap_err();
+    api.get_list(84839422).send().await.unwrap_err();
+
+    assert_eq!(
+        counters.counts().into_keys().collect::<Vec<_>>(),
+        [
+            "GET lists/:id",
+            "GET spaces/:id",
+            "GET users/by/username/:username",
+            "GET users/me"
+        ]
+    );
+    assert_eq!(
+        counters
+            .get(&Method::GET, "users/by/username/:username")
+            .requests,
+        2
+    );
+}
+
+#[cfg(feature = "tracing")]
+mod tracing_spans {
+    use super::*;
+    use std::sync::Mutex;
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::Subscriber;
+    use tracing_subscriber::layer::{Context, SubscriberExt};
+    use tracing_subscriber::Layer;
+
+    #[derive(Clone, Default)]
+    struct Fields(Arc<Mutex<Vec<(String, String)>>>);
+
+    impl Visit for Fields {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            self.0
+                .lock()
+                .unwrap()
+                .push((field.name().to_string(), format!("{value:?}")));
+        }
+    }
+
+    impl<S: Subscriber> Layer<S> for Fields {
+        fn on_new_span(&self, attrs: &Attributes<'_>, _id: &Id, _ctx: Context<'_, S>) {
+            attrs.record(&mut self.clone());
+        }
+        fn on_record(&self, _id: &Id, values: &Record<'_>, _ctx: Context<'_, S>) {
+            values.record(&mut self.clone());
+        }
+    }
+
+    #[tokio::test]
+    async fn metrics_tracing_span() {
+        let fields = Fields::default();
+        let subscriber = tracing_subscriber::registry().with(fields.clone());
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        get_api().get_tweet(20).send().await.unwrap();
+
+        let fields = fields.0.lock().unwrap();
+        let field = |name: &str| {
+            fields
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.as_str())
+        };
+        assert_eq!(field("method"), Some("GET"));
+        assert_eq!(field("endpoint"), Some("tweets/:id"));
+        assert_eq!(field("status"), Some("200"));
+        assert!(field("latency_ms").is_some());
+        assert!(fields.iter().all(|(_, value)| !value.contains("secret")));
+    }
+}
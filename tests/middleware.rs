@@ -0,0 +1,207 @@
+#![cfg(feature = "tower")]
+
+use async_trait::async_trait;
+use reqwest::header::{HeaderValue, AUTHORIZATION};
+use reqwest::{Request, StatusCode};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use time::OffsetDateTime;
+use tower::retry::RetryLayer;
+use tower::timeout::TimeoutLayer;
+use tower::{service_fn, ServiceBuilder};
+use twitter_v2::authorization::{Authorization, BearerToken};
+use twitter_v2::middleware::{AuthorizeLayer, RateLimitLayer, RetryPolicy};
+use twitter_v2::transport::TransportResponse;
+use twitter_v2::{Error, TwitterApi};
+
+const TWEET: &str = r#"{"data": {"id": "20", "text": "just setting up my twttr"}}"#;
+
+fn rate_limited(remaining: u32, reset_in: Duration) -> TransportResponse {
+    let reset = OffsetDateTime::now_utc() + reset_in;
+    let status = if remaining == 0 {
+        StatusCode::TOO_MANY_REQUESTS
+    } else {
+        StatusCode::OK
+    };
+    TransportResponse::new(status, if remaining == 0 { "{}" } else { TWEET })
+        .header(
+            "x-rate-limit-limit".parse().unwrap(),
+            HeaderValue::from(300),
+        )
+        .header(
+            "x-rate-limit-remaining".parse().unwrap(),
+            HeaderValue::from(remaining),
+        )
+        .header(
+            "x-rate-limit-reset".parse().unwrap(),
+            HeaderValue::from(reset.unix_timestamp()),
+        )
+}
+
+#[tokio::test]
+async fn middleware_retry_server_errors() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let service = ServiceBuilder::new()
+        .layer(RetryLayer::new(
+            RetryPolicy::new(3).backoff(Duration::from_millis(1)),
+        ))
+        .service(service_fn(move |_: Request| {
+            let call = counter.fetch_add(1, Ordering::SeqCst);
+            async move {
+                Ok::<_, Error>(if call < 2 {
+                    TransportResponse::new(StatusCode::SERVICE_UNAVAILABLE, "")
+                } else {
+                    TransportResponse::new(StatusCode::OK, TWEET)
+                })
+            }
+        }));
+    let api = TwitterApi::new(BearerToken::new("")).with_service(service);
+    let tweet = api.get_tweet(20).send().await.unwrap().into_data().unwrap();
+    assert_eq!(tweet.id, 20);
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+}
+
+/// Counts the failed calls until the service answers with `TWEET`
+fn unavailable_once(
+    calls: Arc<AtomicUsize>,
+) -> impl Fn(Request) -> futures::future::Ready<Result<TransportResponse, Error>> + Clone {
+    move |_: Request| {
+        futures::future::ok(if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+            TransportResponse::new(StatusCode::SERVICE_UNAVAILABLE, "")
+        } else {
+            TransportResponse::new(StatusCode::OK, r#"{"data": {"liked": true}}"#)
+        })
+    }
+}
+
+#[tokio::test]
+async fn middleware_retry_writes() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let policy = RetryPolicy::new(3).backoff(Duration::from_millis(1));
+    let service = ServiceBuilder::new()
+        .layer(RetryLayer::new(policy.clone()))
+        .service(service_fn(unavailable_once(calls.clone())));
+    let api = TwitterApi::new(BearerToken::new("")).with_service(service);
+    assert!(api.post_user_like(12, 20).await.is_err());
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let service = ServiceBuilder::new()
+        .layer(RetryLayer::new(policy.retry_writes(true)))
+        .service(service_fn(unavailable_once(calls.clone())));
+    let api = TwitterApi::new(BearerToken::new("")).with_service(service);
+    assert!(
+        api.post_user_like(12, 20)
+            .await
+            .unwrap()
+            .into_data()
+            .unwrap()
+            .liked
+    );
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+/// Signs every request with the number of requests signed before
+#[derive(Clone, Debug, Default)]
+struct Counting(Arc<AtomicUsize>);
+
+#[async_trait]
+impl Authorization for Counting {
+    async fn header(&self, _request: &Request) -> twitter_v2::Result<HeaderValue> {
+        Ok(HeaderValue::from(self.0.fetch_add(1, Ordering::SeqCst)))
+    }
+}
+
+#[tokio::test]
+async fn middleware_authorize_every_attempt() {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let headers = seen.clone();
+    let calls = Arc::new(AtomicUsize::new(0));
+    let respond = unavailable_once(calls);
+    let auth = Counting::default();
+    let service = ServiceBuilder::new()
+        .layer(RetryLayer::new(
+            RetryPolicy::new(3)
+                .backoff(Duration::from_millis(1))
+                .retry_writes(true),
+        ))
+        .layer(AuthorizeLayer::new(auth.clone()))
+        .service(service_fn(move |request: Request| {
+            headers
+                .lock()
+                .unwrap()
+                .push(request.headers()[AUTHORIZATION].clone());
+            respond(request)
+        }));
+    let api = TwitterApi::new(auth).with_service(service);
+    api.post_user_like(12, 20).await.unwrap();
+    // the client signed the request with 0 before it entered the stack
+    assert_eq!(
+        *seen.lock().unwrap(),
+        [HeaderValue::from(1), HeaderValue::from(2)]
+    );
+}
+
+#[tokio::test]
+async fn middleware_retry_gives_up() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let service = ServiceBuilder::new()
+        .layer(RetryLayer::new(
+            RetryPolicy::new(1).max_wait(Duration::from_secs(1)),
+        ))
+        .service(service_fn(move |_: Request| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            async move { Ok::<_, Error>(rate_limited(0, Duration::from_secs(600))) }
+        }));
+    let api = TwitterApi::new(BearerToken::new("")).with_service(service);
+    match api.get_tweet(20).send().await {
+        Err(Error::Api(err)) => assert_eq!(err.status, StatusCode::TOO_MANY_REQUESTS),
+        res => panic!("expected rate limit error, got {res:?}"),
+    }
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn middleware_rate_limit_layer() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let rate_limits = RateLimitLayer::new().max_wait(Duration::from_secs(1));
+    let service = ServiceBuilder::new()
+        .layer(rate_limits.clone())
+        .service(service_fn(move |_: Request| {
+            let call = counter.fetch_add(1, Ordering::SeqCst);
+            async move { Ok::<_, Error>(rate_limited(1 - call as u32, Duration::from_secs(600))) }
+        }));
+    let api = TwitterApi::new(BearerToken::new("")).with_service(service);
+    assert!(api.get_tweet(20).send().await.is_ok());
+    assert!(api.get_tweet(21).send().await.is_err());
+    let limit = rate_limits.rate_limit("GET", "tweets/22").unwrap();
+    assert_eq!(limit.remaining, 0);
+    assert!(limit.is_exhausted());
+
+    // held back without reaching the service
+    match api.get_tweet(22).send().await {
+        Err(Error::Api(err)) => assert_eq!(err.status, StatusCode::TOO_MANY_REQUESTS),
+        res => panic!("expected rate limit error, got {res:?}"),
+    }
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    assert!(rate_limits.rate_limit("GET", "users/22").is_none());
+}
+
+#[tokio::test]
+async fn middleware_foreign_errors() {
+    let service = ServiceBuilder::new()
+        .layer(TimeoutLayer::new(Duration::from_millis(10)))
+        .service(service_fn(|_: Request| async {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            Ok::<_, Error>(TransportResponse::new(StatusCode::OK, TWEET))
+        }));
+    let api = TwitterApi::new(BearerToken::new("")).with_service(service);
+    assert!(matches!(
+        api.get_tweet(20).send().await,
+        Err(Error::Middleware(_))
+    ));
+}
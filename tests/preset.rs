@@ -0,0 +1,91 @@
+mod common;
+
+use common::fake::{ok, FakeTransport};
+use std::collections::HashMap;
+use twitter_v2::authorization::BearerToken;
+use twitter_v2::query::{FieldPreset, TweetExpansion, TweetField, UserField};
+use twitter_v2::TwitterApi;
+
+fn get_api() -> (TwitterApi<BearerToken>, FakeTransport) {
+    let transport = FakeTransport::new(|_| ok(serde_json::json!({"data": []})));
+    (transport.api(), transport)
+}
+
+fn last_query(transport: &FakeTransport) -> Vec<(String, String)> {
+    transport
+        .requests()
+        .last()
+        .unwrap()
+        .url
+        .query_pairs()
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect()
+}
+
+#[test]
+fn preset_fields() {
+    assert!(TweetField::all().any(|field| field == TweetField::NoteTweet));
+    let archive = TweetField::preset(FieldPreset::FullArchive);
+    assert_eq!(archive.len(), TweetField::all().count() - 3);
+    assert!(!archive.contains(&TweetField::NonPublicMetrics));
+    assert!(TweetField::preset(FieldPreset::Owner).contains(&TweetField::PromotedMetrics));
+    assert_eq!(
+        UserField::preset(FieldPreset::PublicMetrics),
+        [
+            UserField::Id,
+            UserField::Name,
+            UserField::Username,
+            UserField::PublicMetrics
+        ]
+    );
+    assert!(TweetExpansion::preset(FieldPreset::Minimal).is_empty());
+}
+
+#[tokio::test]
+async fn preset_with_all_fields() {
+    let (api, transport) = get_api();
+    api.get_tweets([20])
+        .tweet_fields([TweetField::NonPublicMetrics])
+        .with_all_fields()
+        .send()
+        .await
+        .unwrap();
+    let query = last_query(&transport);
+    let params = query.iter().cloned().collect::<HashMap<_, _>>();
+    assert_eq!(params.len(), query.len());
+    let tweet_fields = params["tweet.fields"].split(',').collect::<Vec<_>>();
+    assert_eq!(tweet_fields[0], "non_public_metrics");
+    assert!(tweet_fields.contains(&"note_tweet"));
+    assert!(!tweet_fields.contains(&"promoted_metrics"));
+    assert!(params["expansions"].contains("referenced_tweets.id.author_id"));
+    assert!(params.contains_key("media.fields"));
+    assert!(params.contains_key("poll.fields"));
+}
+
+#[tokio::test]
+async fn preset_only_applies_endpoint_params() {
+    let (api, transport) = get_api();
+    api.get_users([20])
+        .with_preset(FieldPreset::Minimal)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(
+        last_query(&transport),
+        [
+            ("ids".to_string(), "20".to_string()),
+            ("user.fields".to_string(), "id,name,username".to_string()),
+            (
+                "tweet.fields".to_string(),
+                "id,text,author_id,created_at".to_string()
+            ),
+        ]
+    );
+
+    api.get_users([20]).with_all_fields().send().await.unwrap();
+    let query = last_query(&transport);
+    assert!(query
+        .iter()
+        .any(|(key, value)| key == "expansions" && value == "pinned_tweet_id"));
+    assert!(query.iter().all(|(key, _)| key != "media.fields"));
+}
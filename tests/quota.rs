@@ -0,0 +1,119 @@
+mod common;
+
+use bytes::Bytes;
+use common::fake::{ok, FakeTransport};
+use futures::prelude::*;
+use reqwest::StatusCode;
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use twitter_v2::authorization::BearerToken;
+use twitter_v2::quota::{QuotaAction, QuotaGuard, QuotaStatus};
+use twitter_v2::transport::TransportResponse;
+use twitter_v2::{Error, TwitterApi};
+
+fn usage_tweets(usage: u64) -> serde_json::Value {
+    json!({"data": {
+        "cap_reset_day": 19,
+        "project_id": "1",
+        "project_cap": "1000",
+        "project_usage": usage.to_string(),
+        "daily_project_usage": {
+            "project_id": "1",
+            "usage": [{"date": "2022-11-19T00:00:00.000Z", "usage": "80"}]
+        }
+    }})
+}
+
+fn get_api(guard: Arc<QuotaGuard>, usage: u64) -> (TwitterApi<BearerToken>, FakeTransport) {
+    let transport = FakeTransport::new(move |request| match request.url().path() {
+        "/2/usage/tweets" => ok(usage_tweets(usage)),
+        "/2/tweets" => ok(json!({"data": []})),
+        _ => ok(json!({"data": [], "meta": {"result_count": 0}})),
+    });
+    let api = transport.api().with_quota_guard(guard);
+    (api, transport)
+}
+
+#[tokio::test]
+async fn quota_get_usage_tweets() {
+    let (api, _) = get_api(Arc::new(QuotaGuard::new()), 250);
+    let usage = api
+        .get_usage_tweets()
+        .days(1)
+        .send()
+        .await
+        .unwrap()
+        .into_data()
+        .unwrap();
+    assert_eq!(usage.cap_reset_day, 19);
+    assert_eq!(usage.project_usage, 250);
+    assert_eq!(usage.remaining(), 750);
+    assert_eq!(usage.daily_project_usage.unwrap().usage[0].usage, 80);
+}
+
+#[tokio::test]
+async fn quota_refuse_near_cap() {
+    let guard = Arc::new(QuotaGuard::new());
+    let (api, transport) = get_api(guard.clone(), 850);
+    api.get_tweets_search_recent("from:TwitterDev")
+        .max_results(100)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(
+        guard.status(),
+        Some(QuotaStatus {
+            usage: 950,
+            cap: 1000
+        })
+    );
+    let err = api
+        .get_tweets_search_recent("from:TwitterDev")
+        .send()
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        Error::QuotaExceeded {
+            usage: 950,
+            cap: 1000
+        }
+    ));
+    // unguarded endpoints are not affected
+    api.get_tweets([20]).send().await.unwrap();
+    assert_eq!(transport.requests_to("/2/usage/tweets").len(), 1);
+}
+
+#[tokio::test]
+async fn quota_warn() {
+    let guard = Arc::new(QuotaGuard::new().action(QuotaAction::Warn));
+    let (api, _) = get_api(guard.clone(), 990);
+    api.get_user_tweets(2244994945).send().await.unwrap();
+    assert_eq!(guard.status().unwrap().usage, 1000);
+}
+
+#[tokio::test]
+async fn quota_refresh_once() {
+    // the usage arrives slowly, while all the other requests check the quota
+    let transport = FakeTransport::new(|request| match request.url().path() {
+        "/2/usage/tweets" => Ok(TransportResponse::streaming(
+            StatusCode::OK,
+            stream::once(async {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                Ok(Bytes::from(usage_tweets(100).to_string()))
+            }),
+        )),
+        _ => ok(json!({"data": [], "meta": {"result_count": 0}})),
+    });
+    let guard = Arc::new(QuotaGuard::new());
+    let api = transport.api().with_quota_guard(guard.clone());
+    future::try_join_all((0..5).map(|_| {
+        let api = api.clone();
+        async move { api.get_tweets_search_recent("from:TwitterDev").send().await }
+    }))
+    .await
+    .unwrap();
+    assert_eq!(transport.requests_to("/2/usage/tweets").len(), 1);
+    assert_eq!(guard.status().unwrap().usage, 150);
+}
@@ -0,0 +1,132 @@
+use pretty_assertions::assert_eq;
+use twitter_v2::data::{FullTextEntities, HashtagEntity, MentionEntity, UrlEntity};
+use twitter_v2::render::{EntityKind, RenderFormat, TextRenderer};
+use twitter_v2::Tweet;
+
+fn entities() -> FullTextEntities {
+    FullTextEntities {
+        urls: Some(vec![UrlEntity {
+            start: Some(24),
+            end: Some(47),
+            url: "https://t.co/abcdefghij".to_string(),
+            expanded_url: Some("https://example.com/a?b=1&c=2".to_string()),
+            display_url: Some("example.com/a?b=1…".to_string()),
+            images: None,
+            status: None,
+            title: None,
+            description: None,
+            unwound_url: None,
+            media_key: None,
+            extra: Default::default(),
+        }]),
+        hashtags: Some(vec![HashtagEntity {
+            start: 7,
+            end: 12,
+            tag: "rust".to_string(),
+            extra: Default::default(),
+        }]),
+        annotations: None,
+        cashtags: None,
+        mentions: Some(vec![MentionEntity {
+            start: 16,
+            end: 23,
+            username: "ferris".to_string(),
+            id: None,
+            extra: Default::default(),
+        }]),
+        extra: Default::default(),
+    }
+}
+
+const TEXT: &str = "🦀 <3 & #rust by @ferris https://t.co/abcdefghij";
+
+#[test]
+fn render_html() {
+    let entities = entities();
+    assert_eq!(
+        TextRenderer::new(TEXT, Some(&entities)).to_html(),
+        "🦀 &lt;3 &amp; <a href=\"https://twitter.com/hashtag/rust\">#rust</a> by \
+         <a href=\"https://twitter.com/ferris\">@ferris</a> \
+         <a href=\"https://example.com/a?b=1&amp;c=2\">example.com/a?b=1…</a>"
+    );
+}
+
+#[test]
+fn render_markdown() {
+    let entities = entities();
+    assert_eq!(
+        TextRenderer::new(TEXT, Some(&entities)).to_markdown(),
+        "🦀 \\<3 & [\\#rust](https://twitter.com/hashtag/rust) by \
+         [@ferris](https://twitter.com/ferris) \
+         [example.com/a?b=1…](https://example.com/a?b=1&c=2)"
+    );
+}
+
+#[test]
+fn render_plain() {
+    let entities = entities();
+    assert_eq!(
+        TextRenderer::new(TEXT, Some(&entities)).to_plain(),
+        "🦀 <3 & #rust by @ferris https://example.com/a?b=1&c=2"
+    );
+}
+
+#[test]
+fn render_callback() {
+    let entities = entities();
+    let plain = TextRenderer::new(TEXT, Some(&entities))
+        .on_entity(|format, span| match span.kind {
+            EntityKind::Mention(mention) => {
+                Some(format.link(span.text, &format!("/u/{}", mention.username)))
+            }
+            _ => None,
+        })
+        .to_plain();
+    assert_eq!(
+        plain,
+        "🦀 <3 & #rust by @ferris https://example.com/a?b=1&c=2"
+    );
+    let html = TextRenderer::new(TEXT, Some(&entities))
+        .on_entity(|format, span| match span.kind {
+            EntityKind::Mention(mention) => {
+                Some(format.link(span.text, &format!("/u/{}", mention.username)))
+            }
+            EntityKind::Url(_) => Some(String::new()),
+            _ => None,
+        })
+        .render(RenderFormat::Html);
+    assert_eq!(
+        html,
+        "🦀 &lt;3 &amp; <a href=\"https://twitter.com/hashtag/rust\">#rust</a> by \
+         <a href=\"/u/ferris\">@ferris</a> "
+    );
+}
+
+#[test]
+fn render_strips_media_urls() {
+    let example: serde_json::Value = serde_json::from_reader(
+        std::fs::File::open("./fixtures/data/tweet/example_with_media_photo.json").unwrap(),
+    )
+    .unwrap();
+    let tweet: Tweet = serde_json::from_value(example["data"][0].clone()).unwrap();
+    let plain = tweet.render().to_plain();
+    assert!(plain.ends_with(
+        "only the beginning.\n\nhttps://blog.twitter.com/developer/en_us/topics/tools/2020/introducing_new_twitter_api.html"
+    ));
+    let plain = tweet.render().strip_media_urls(false).to_plain();
+    assert!(plain.ends_with("https://twitter.com/TwitterDev/status/1293593516040269825/video/1"));
+}
+
+#[test]
+fn render_note_tweet() {
+    let example: serde_json::Value = serde_json::from_reader(
+        std::fs::File::open("./fixtures/data/tweet/example_note_tweet.json").unwrap(),
+    )
+    .unwrap();
+    let tweet: Tweet = serde_json::from_value(example["data"][0].clone()).unwrap();
+    assert!(tweet.full_text().len() > tweet.text.len());
+    assert!(tweet.render().to_html().ends_with(
+        "full text of longer Tweets with the \
+         <a href=\"https://twitter.com/hashtag/TwitterAPI\">#TwitterAPI</a> v2."
+    ));
+}
@@ -0,0 +1,89 @@
+mod common;
+
+use common::fake::{ok, FakeTransport};
+use serde_json::json;
+use time::macros::datetime;
+use time::OffsetDateTime;
+use twitter_v2::authorization::BearerToken;
+use twitter_v2::data::UserPublicMetrics;
+use twitter_v2::id::UserId;
+use twitter_v2::query::TweetField;
+use twitter_v2::{select, Tweet, TwitterApi, User};
+
+select! {
+    struct DatedTweet: Tweet {
+        author_id: UserId,
+        created_at: OffsetDateTime,
+    }
+}
+
+select! {
+    /// A user with their follower counts
+    pub struct RankedUser: User {
+        public_metrics: UserPublicMetrics,
+    }
+}
+
+fn get_api() -> TwitterApi<BearerToken> {
+    FakeTransport::new(|request| {
+        let query = request.url().query().unwrap_or_default();
+        let body = match request.url().path() {
+            "/2/tweets/20" if query.contains("created_at") => json!({"data": {
+                "id": "20",
+                "text": "just setting up my twttr",
+                "author_id": "12",
+                "created_at": "2006-03-21T20:50:14.000Z"
+            }}),
+            "/2/tweets" => json!({"data": [{
+                "id": "20",
+                "text": "just setting up my twttr",
+                "author_id": "12"
+            }]}),
+            _ => json!({"data": {"id": "20", "text": "just setting up my twttr"}}),
+        };
+        ok(body)
+    })
+    .api()
+}
+
+#[tokio::test]
+async fn selection_fields_present() {
+    let tweet = get_api()
+        .get_tweet(20)
+        .tweet_fields([TweetField::Lang])
+        .select::<DatedTweet>()
+        .send()
+        .await
+        .unwrap()
+        .into_data()
+        .unwrap();
+    assert_eq!(tweet.author_id, 12);
+    assert_eq!(tweet.created_at, datetime!(2006-03-21 20:50:14 UTC));
+    assert_eq!(tweet.text, "just setting up my twttr");
+    assert_eq!(tweet.into_inner().id, 20);
+}
+
+#[tokio::test]
+async fn selection_field_missing() {
+    let err = get_api()
+        .get_tweets([20])
+        .select::<DatedTweet>()
+        .send()
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("created_at"), "{err}");
+}
+
+#[test]
+fn selection_user() {
+    let user: RankedUser = serde_json::from_str(
+        r#"{"id": "12", "name": "jack", "username": "jack", "public_metrics": {"followers_count": 1, "following_count": 2, "tweet_count": 3, "listed_count": 4}}"#,
+    )
+    .unwrap();
+    assert_eq!(user.public_metrics.followers_count, 1);
+    assert_eq!(user.username, "jack");
+    assert!(serde_json::from_str::<RankedUser>(
+        r#"{"id": "12", "name": "jack", "username": "jack"}"#
+    )
+    .is_err());
+}
@@ -0,0 +1,133 @@
+#![cfg(feature = "testing")]
+
+use futures::prelude::*;
+use serde_json::json;
+use twitter_v2::prelude::*;
+use twitter_v2::testing::{Fault, MockTwitter};
+use twitter_v2::Error;
+
+async fn get_mock() -> MockTwitter {
+    let mock = MockTwitter::start().await.unwrap();
+    mock.load_fixture("./fixtures/data/tweet/example_tweet.json")
+        .unwrap();
+    mock
+}
+
+#[tokio::test]
+async fn mock_get_and_post_tweet() {
+    let mock = get_mock().await;
+    let api = mock.api();
+    let tweet = api
+        .get_tweet(1307025659294674945)
+        .send()
+        .await
+        .unwrap()
+        .into_data()
+        .unwrap();
+    assert_eq!(tweet.id, 1307025659294674945);
+
+    let posted = api
+        .post_tweet()
+        .text("Hello from the mock".to_string())
+        .send()
+        .await
+        .unwrap()
+        .into_data()
+        .unwrap();
+    assert_eq!(
+        mock.tweet(posted.id).unwrap()["text"],
+        "Hello from the mock"
+    );
+
+    api.delete_tweet(posted.id).await.unwrap();
+    assert!(mock.tweet(posted.id).is_none());
+    let missing = api.get_tweet(posted.id).send().await.unwrap();
+    assert!(missing.data().is_none());
+}
+
+#[tokio::test]
+async fn mock_follows_pagination() {
+    let mock = MockTwitter::start().await.unwrap();
+    let target = mock
+        .insert_user(json!({"id": "1", "name": "Target", "username": "target"}))
+        .unwrap();
+    for id in 2..7 {
+        let follower = mock
+            .insert_user(json!({"id": id.to_string(), "name": "Follower", "username": format!("follower{id}")}))
+            .unwrap();
+        mock.follow(follower, target);
+    }
+    let api = mock.api();
+    let page = api
+        .get_user_followers(target)
+        .max_results(2)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(page.data().unwrap().len(), 2);
+    let mut count = page.data().unwrap().len();
+    let mut page = page;
+    while let Some(next) = page.next_page().await.unwrap() {
+        count += next.data().unwrap().len();
+        page = next;
+    }
+    assert_eq!(count, 5);
+
+    api.post_user_following(target, 2).await.unwrap();
+    assert_eq!(mock.followers(2), vec![target]);
+}
+
+#[tokio::test]
+async fn mock_filtered_stream() {
+    let mock = get_mock().await;
+    let api = mock.api();
+    api.post_tweets_search_stream_rule()
+        .add_tagged("rust", "rust tweets")
+        .send()
+        .await
+        .unwrap();
+    let rules = api
+        .get_tweets_search_stream_rules()
+        .send()
+        .await
+        .unwrap()
+        .into_data()
+        .unwrap();
+    assert_eq!(rules.len(), 1);
+
+    let mut stream = api.get_tweets_search_stream().stream().await.unwrap();
+    mock.push_stream_tweet(json!({"id": "10", "text": "nothing to see"}));
+    api.post_tweet()
+        .text("learning rust".to_string())
+        .send()
+        .await
+        .unwrap();
+    let payload = stream.next().await.unwrap().unwrap();
+    assert_eq!(payload.data().unwrap().text, "learning rust");
+
+    mock.disconnect_streams();
+    assert!(stream.next().await.unwrap().is_err());
+}
+
+#[tokio::test]
+async fn mock_fault_injection() {
+    let mock = get_mock().await;
+    let api = mock.api();
+
+    mock.inject(Fault::TooManyRequests);
+    match api.get_tweet(1307025659294674945).send().await {
+        Err(Error::Api(err)) => assert_eq!(err.status.as_u16(), 429),
+        res => panic!("expected rate limit error, got {res:?}"),
+    }
+
+    mock.inject_for("users", Fault::ServiceUnavailable);
+    assert!(api.get_tweet(1307025659294674945).send().await.is_ok());
+    match api.get_user(2244994945).send().await {
+        Err(Error::Api(err)) => assert_eq!(err.status.as_u16(), 503),
+        res => panic!("expected service unavailable, got {res:?}"),
+    }
+
+    mock.inject(Fault::Disconnect);
+    assert!(api.get_tweet(1307025659294674945).send().await.is_err());
+    assert!(api.get_tweet(1307025659294674945).send().await.is_ok());
+}
@@ -0,0 +1,74 @@
+mod common;
+
+use common::fake::{json, ok, FakeTransport};
+use futures::prelude::*;
+use reqwest::header::AUTHORIZATION;
+use reqwest::{Method, StatusCode};
+use serde_json::json;
+use twitter_v2::authorization::BearerToken;
+use twitter_v2::transport::TransportResponse;
+use twitter_v2::{Error, TwitterApi};
+
+fn get_api() -> (TwitterApi<BearerToken>, FakeTransport) {
+    let transport = FakeTransport::new(|request| match request.url().path() {
+        "/2/tweets/20" => ok(json!({"data": {"id": "20", "text": "just setting up my twttr"}})),
+        "/2/tweets/sample/stream" => Ok(TransportResponse::streaming(
+            StatusCode::OK,
+            stream::iter([
+                r#"{"data": {"id": "1", "text": "one"}}"#.to_string() + "\r\n",
+                "\r\n".to_string(),
+                r#"{"data": {"id": "2", "text": "two"}}"#.to_string() + "\r\n",
+            ])
+            .map(|line| Ok(line.into())),
+        )),
+        _ => json(
+            StatusCode::NOT_FOUND,
+            json!({"title": "Not Found Error", "type": "about:blank", "detail": "nope"}),
+        ),
+    });
+    let api = TwitterApi::new(BearerToken::new("secret")).with_transport(transport.clone());
+    (api, transport)
+}
+
+#[tokio::test]
+async fn transport_send() {
+    let (api, transport) = get_api();
+    let tweet = api.get_tweet(20).send().await.unwrap().into_data().unwrap();
+    assert_eq!(tweet.id, 20);
+    assert_eq!(tweet.text, "just setting up my twttr");
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, Method::GET);
+    assert_eq!(
+        requests[0].url.as_str(),
+        "https://api.twitter.com/2/tweets/20"
+    );
+    assert_eq!(requests[0].headers[AUTHORIZATION], "Bearer secret");
+}
+
+#[tokio::test]
+async fn transport_stream() {
+    let (api, _) = get_api();
+    let texts = api
+        .get_tweets_sample_stream()
+        .stream()
+        .await
+        .unwrap()
+        .map(|payload| payload.unwrap().into_data().unwrap().text)
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(texts, ["one", "two"]);
+}
+
+#[tokio::test]
+async fn transport_error_status() {
+    let (api, _) = get_api();
+    match api.get_tweet(21).send().await {
+        Err(Error::Api(err)) => {
+            assert_eq!(err.status, StatusCode::NOT_FOUND);
+            assert_eq!(err.detail, "nope");
+        }
+        res => panic!("expected api error, got {res:?}"),
+    }
+}
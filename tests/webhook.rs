@@ -0,0 +1,238 @@
+#![cfg(feature = "webhook")]
+
+mod common;
+
+use axum::body::{Body, HttpBody};
+use axum::http::{Request, StatusCode};
+use common::fake::{ok, FakeTransport};
+use reqwest::Method;
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use tower::ServiceExt;
+use twitter_v2::data::{
+    AccountActivity, AccountActivityEvent, DirectMessageEventKind, RelationshipEventKind,
+};
+use twitter_v2::transport::TransportResponse;
+use twitter_v2::webhook::{Webhook, SIGNATURE_HEADER};
+use twitter_v2::Error;
+
+fn payload() -> Vec<u8> {
+    let user = |id: &str, name: &str| json!({"id": id.parse::<u64>().unwrap(), "id_str": id, "name": name, "screen_name": name});
+    json!({
+        "for_user_id": "2244994945",
+        "tweet_create_events": [{
+            "id": 1, "id_str": "1", "created_at": "Wed Oct 10 20:19:24 +0000 2018",
+            "text": "@TwitterDev hello", "truncated": false, "user": user("12", "jack")
+        }],
+        "follow_events": [
+            {"type": "follow", "created_timestamp": "1517588749178", "source": user("12", "jack"), "target": user("2244994945", "TwitterDev")},
+            {"type": "unfollow", "created_timestamp": "1517588749179", "source": user("13", "ev"), "target": user("2244994945", "TwitterDev")},
+            {"type": "follow_request", "created_timestamp": "1517588749180", "source": user("14", "biz"), "target": user("2244994945", "TwitterDev")}
+        ],
+        "direct_message_events": [{
+            "type": "message_create", "id": "954491830116155396", "created_timestamp": "1516403560557",
+            "message_create": {
+                "target": {"recipient_id": "2244994945"},
+                "sender_id": "12",
+                "message_data": {"text": "Hello", "entities": {}}
+            }
+        }],
+        "favorite_events": [{
+            "id": "a7ba59eab0bfcba386f7acedac279542", "created_at": "Mon Mar 26 16:33:26 +0000 2018",
+            "timestamp_ms": 1522082006140u64,
+            "favorited_status": {
+                "id": 2, "id_str": "2", "created_at": "Mon Mar 26 16:30:00 +0000 2018",
+                "text": "hello world", "truncated": false, "user": user("2244994945", "TwitterDev")
+            },
+            "user": user("12", "jack")
+        }],
+        // without the `timestamp_ms` and `user_id` of the model
+        "tweet_delete_events": [{"status": {"id": "601"}}],
+        "direct_message_indicate_typing_events": [{"created_timestamp": "1518127183443", "sender_id": "12", "target": {"recipient_id": "2244994945"}}],
+        "users": {"12": user("12", "jack")}
+    })
+    .to_string()
+    .into_bytes()
+}
+
+fn sign(body: &[u8]) -> String {
+    use hmac::Mac;
+    let mut mac = hmac::Hmac::<sha2::Sha256>::new_from_slice(b"secret").unwrap();
+    mac.update(body);
+    format!("sha256={}", base64::encode(mac.finalize().into_bytes()))
+}
+
+#[test]
+fn webhook_crc_and_signature() {
+    let webhook = Webhook::new("secret");
+    assert_eq!(
+        webhook.crc_response_token("challenge"),
+        "sha256=oeUF6Wxqoezggrue+wbIDxKRPSF6esKwizR2MHh9HaA="
+    );
+
+    let body = payload();
+    let signature = sign(&body);
+    assert!(webhook.verify(&body, &signature).is_ok());
+    assert!(matches!(
+        Webhook::new("other").verify(&body, &signature),
+        Err(Error::InvalidWebhookSignature)
+    ));
+    assert!(webhook.verify(b"{}", &signature).is_err());
+    assert!(webhook.verify(&body, "garbage").is_err());
+}
+
+#[test]
+fn webhook_parse_events() {
+    let body = payload();
+    let activity = Webhook::new("secret").parse(&body, &sign(&body)).unwrap();
+    assert_eq!(activity.for_user_id, 2244994945);
+    assert_eq!(activity.users["12"].screen_name, "jack");
+    let mut kinds = activity
+        .events
+        .iter()
+        .map(|event| match event {
+            AccountActivityEvent::TweetCreate(tweet) => {
+                assert_eq!(tweet.full_text(), "@TwitterDev hello");
+                "tweet_create".to_string()
+            }
+            AccountActivityEvent::Follow(follow) => {
+                assert_eq!(follow.kind, RelationshipEventKind::Follow);
+                assert_eq!(follow.source.id, 12);
+                assert_eq!(follow.created_timestamp, 1517588749178);
+                "follow".to_string()
+            }
+            AccountActivityEvent::Unfollow(_) => "unfollow".to_string(),
+            AccountActivityEvent::Favorite(favorite) => {
+                assert_eq!(favorite.created_at, "Mon Mar 26 16:33:26 +0000 2018");
+                assert_eq!(favorite.timestamp_ms, 1522082006140);
+                assert_eq!(favorite.favorited_status.id, 2);
+                "favorite".to_string()
+            }
+            AccountActivityEvent::DirectMessage(message) => {
+                assert_eq!(message.kind, DirectMessageEventKind::MessageCreate);
+                assert_eq!(message.message_create.message_data.text, "Hello");
+                "direct_message".to_string()
+            }
+            AccountActivityEvent::Other { kind, .. } => kind.clone(),
+            event => panic!("unexpected {event:?}"),
+        })
+        .collect::<Vec<_>>();
+    kinds.sort();
+    assert_eq!(
+        kinds,
+        [
+            "direct_message",
+            "direct_message_indicate_typing_events",
+            "favorite",
+            "follow",
+            "follow_events",
+            "tweet_create",
+            "tweet_delete_events",
+            "unfollow"
+        ]
+    );
+
+    let json = serde_json::to_vec(&activity).unwrap();
+    assert_eq!(
+        serde_json::from_slice::<AccountActivity>(&json).unwrap(),
+        activity
+    );
+}
+
+#[tokio::test]
+async fn webhook_router() {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let app = Webhook::new("secret").router({
+        let received = received.clone();
+        move |activity: AccountActivity| {
+            let received = received.clone();
+            async move { received.lock().unwrap().push(activity.for_user_id) }
+        }
+    });
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::get("/?crc_token=challenge")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().data().await.unwrap().unwrap();
+    assert_eq!(
+        serde_json::from_slice::<serde_json::Value>(&body).unwrap(),
+        json!({"response_token": "sha256=oeUF6Wxqoezggrue+wbIDxKRPSF6esKwizR2MHh9HaA="})
+    );
+
+    let body = payload();
+    let post = |signature: String| {
+        Request::post("/")
+            .header(SIGNATURE_HEADER, signature)
+            .body(Body::from(body.clone()))
+            .unwrap()
+    };
+    let response = app.clone().oneshot(post(sign(&body))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = app.oneshot(post(sign(b"forged"))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(*received.lock().unwrap(), [2244994945]);
+}
+
+#[tokio::test]
+async fn webhook_management() {
+    let transport = FakeTransport::new(|request| match request.url().path() {
+        "/1.1/account_activity/all/dev/subscriptions/list.json" => ok(json!({
+            "environment": "dev",
+            "application_id": "13090192",
+            "subscriptions": [{"user_id": "3001969357"}]
+        })),
+        "/1.1/account_activity/all/dev/subscriptions.json" => {
+            Ok(TransportResponse::new(StatusCode::NO_CONTENT, ""))
+        }
+        _ => ok(json!({
+            "id": "1234567890",
+            "url": "https://example.com/webhook",
+            "valid": true,
+            "created_timestamp": "2016-06-02 23:54:02 +0000"
+        })),
+    });
+    let api = transport.api();
+    let webhook = api
+        .post_account_activity_webhook("dev", "https://example.com/webhook")
+        .await
+        .unwrap();
+    assert!(webhook.valid);
+    api.delete_account_activity_webhook("dev", &webhook.id)
+        .await
+        .unwrap();
+    assert!(api.get_account_activity_subscription("dev").await.unwrap());
+    let subscriptions = api.get_account_activity_subscriptions("dev").await.unwrap();
+    assert_eq!(subscriptions.subscriptions[0].user_id, 3001969357);
+    assert_eq!(
+        transport
+            .requests()
+            .into_iter()
+            .map(|request| (request.method, request.url.to_string()))
+            .collect::<Vec<_>>(),
+        [
+            (
+                Method::POST,
+                "https://api.twitter.com/1.1/account_activity/all/dev/webhooks.json?url=https%3A%2F%2Fexample.com%2Fwebhook".to_string()
+            ),
+            (
+                Method::DELETE,
+                "https://api.twitter.com/1.1/account_activity/all/dev/webhooks/1234567890.json".to_string()
+            ),
+            (
+                Method::GET,
+                "https://api.twitter.com/1.1/account_activity/all/dev/subscriptions.json".to_string()
+            ),
+            (
+                Method::GET,
+                "https://api.twitter.com/1.1/account_activity/all/dev/subscriptions/list.json".to_string()
+            ),
+        ]
+    );
+}